@@ -1,40 +1,70 @@
 //! Modul ini mendefinisikan fungsi-fungsi yang akan diekspos sebagai command Tauri.
 //! Fungsi-fungsi ini bisa dipanggil langsung dari frontend JavaScript.
 
-use crate::models::account::Account;
-use crate::services::account_service;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::account::{Account, AccountType};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::services::account_service::AccountService;
+use tauri::State;
 
 #[tauri::command]
 pub fn create_account(
+    db: State<'_, Database>,
     name: String,
     description: Option<String>,
     balance: f64,
-) -> Result<(), String> {
+    currency: Option<String>,
+    account_type: Option<AccountType>,
+) -> Result<(), AppError> {
     // Menggunakan as_deref() untuk mengubah Option<String> menjadi Option<&str>
-    account_service::create_account(&name, description.as_deref(), balance)
+    service(&db).create_account(
+        &name,
+        description.as_deref(),
+        balance,
+        currency.as_deref(),
+        account_type.unwrap_or_default(),
+    )
 }
 
 #[tauri::command]
-pub fn get_all_accounts() -> Result<Vec<Account>, String> {
-    account_service::get_all_accounts()
+pub fn get_all_accounts(db: State<'_, Database>) -> Result<Vec<Account>, AppError> {
+    service(&db).get_all_accounts()
 }
 
 #[tauri::command]
-pub fn get_account_by_id(id: i64) -> Result<Account, String> {
-    account_service::get_account_by_id(id)
+pub fn get_account_by_id(db: State<'_, Database>, id: i64) -> Result<Account, AppError> {
+    service(&db).get_account_by_id(id)
 }
 
 #[tauri::command]
 pub fn update_account(
+    db: State<'_, Database>,
     id: i64,
     name: String,
     description: Option<String>,
     balance: f64,
-) -> Result<(), String> {
-    account_service::update_account(id, &name, description.as_deref(), balance)
+    currency: Option<String>,
+) -> Result<(), AppError> {
+    service(&db).update_account(
+        id,
+        &name,
+        description.as_deref(),
+        balance,
+        currency.as_deref(),
+    )
 }
 
 #[tauri::command]
-pub fn delete_account(id: i64) -> Result<(), String> {
-    account_service::delete_account(id)
+pub fn delete_account(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_account(id)
+}
+
+#[tauri::command]
+pub fn recalculate_account_balance(db: State<'_, Database>, id: i64) -> Result<Account, AppError> {
+    service(&db).recalculate_balance(id)
+}
+
+fn service(db: &Database) -> AccountService<SqliteAccountRepository<'_>> {
+    AccountService::new(SqliteAccountRepository::new(db))
 }
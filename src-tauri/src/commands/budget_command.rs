@@ -0,0 +1,54 @@
+//! Command Tauri untuk anggaran per kategori.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::budget::{Budget, BudgetInput, BudgetProgress};
+use crate::repositories::budget_repository::SqliteBudgetRepository;
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::services::budget_service::BudgetService;
+use chrono::NaiveDate;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_budget(db: State<'_, Database>, input: BudgetInput) -> Result<(), AppError> {
+    service(&db).create_budget(&input)
+}
+
+#[tauri::command]
+pub fn get_all_budgets(db: State<'_, Database>) -> Result<Vec<Budget>, AppError> {
+    service(&db).get_all_budgets()
+}
+
+#[tauri::command]
+pub fn get_budget_by_id(db: State<'_, Database>, id: i64) -> Result<Budget, AppError> {
+    service(&db).get_budget_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_budget(db: State<'_, Database>, id: i64, input: BudgetInput) -> Result<(), AppError> {
+    service(&db).update_budget(id, &input)
+}
+
+#[tauri::command]
+pub fn delete_budget(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_budget(id)
+}
+
+/// Terpakai vs batas vs sisa untuk setiap anggaran pada periode yang memuat `date`
+/// (default hari ini), beserta status terlampaui atau cenderung terlampaui.
+#[tauri::command]
+pub fn get_budget_progress(
+    db: State<'_, Database>,
+    date: Option<NaiveDate>,
+) -> Result<Vec<BudgetProgress>, AppError> {
+    service(&db).get_budget_progress(date)
+}
+
+fn service(
+    db: &Database,
+) -> BudgetService<SqliteBudgetRepository<'_>, SqliteCategoryRepository<'_>> {
+    BudgetService::new(
+        SqliteBudgetRepository::new(db),
+        SqliteCategoryRepository::new(db),
+    )
+}
@@ -0,0 +1,105 @@
+//! Command Tauri untuk kategori pemasukan dan pengeluaran.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::category::{Category, CategoryNode};
+use crate::models::default_category::{DefaultCategorySet, DEFAULT_CATEGORY_SETS};
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::services::category_service::CategoryService;
+use tauri::State;
+
+/// `category_type`: 1 untuk Income, 2 untuk Expense. `parent_id` opsional untuk sub-kategori.
+/// `icon` adalah nama ikon lucide dan `color` berformat `#RRGGBB`; keduanya opsional.
+#[tauri::command]
+pub fn create_category(
+    db: State<'_, Database>,
+    name: String,
+    category_type: i16,
+    parent_id: Option<i64>,
+    icon: Option<String>,
+    color: Option<String>,
+) -> Result<(), AppError> {
+    service(&db).create_category(
+        &name,
+        category_type,
+        parent_id,
+        icon.as_deref(),
+        color.as_deref(),
+    )
+}
+
+#[tauri::command]
+pub fn get_all_categories(db: State<'_, Database>) -> Result<Vec<Category>, AppError> {
+    service(&db).get_all_categories()
+}
+
+#[tauri::command]
+pub fn get_category_tree(db: State<'_, Database>) -> Result<Vec<CategoryNode>, AppError> {
+    service(&db).get_category_tree()
+}
+
+#[tauri::command]
+pub fn get_category_by_id(db: State<'_, Database>, id: i64) -> Result<Category, AppError> {
+    service(&db).get_category_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_category(
+    db: State<'_, Database>,
+    id: i64,
+    name: String,
+    category_type: i16,
+    parent_id: Option<i64>,
+    icon: Option<String>,
+    color: Option<String>,
+) -> Result<(), AppError> {
+    service(&db).update_category(
+        id,
+        &name,
+        category_type,
+        parent_id,
+        icon.as_deref(),
+        color.as_deref(),
+    )
+}
+
+/// Kategori yang masih dipakai hanya bisa dihapus dengan `replacement_id`; transaksinya
+/// dipindahkan ke kategori tersebut. Mengembalikan jumlah transaksi yang dipindahkan.
+#[tauri::command]
+pub fn delete_category(
+    db: State<'_, Database>,
+    id: i64,
+    replacement_id: Option<i64>,
+) -> Result<usize, AppError> {
+    service(&db).delete_category(id, replacement_id)
+}
+
+/// Menggabungkan `source_id` ke `target_id`. Mengembalikan jumlah transaksi yang dipindahkan.
+#[tauri::command]
+pub fn merge_categories(
+    db: State<'_, Database>,
+    source_id: i64,
+    target_id: i64,
+) -> Result<usize, AppError> {
+    service(&db).merge_categories(source_id, target_id)
+}
+
+/// Kumpulan kategori bawaan per bahasa, untuk dipilih dan ditampilkan di UI.
+#[tauri::command]
+pub fn get_default_category_sets() -> &'static [DefaultCategorySet] {
+    DEFAULT_CATEGORY_SETS
+}
+
+/// Menambahkan kategori bawaan `locale` yang belum ada tanpa mengubah kategori yang sudah
+/// ada. Mengembalikan jumlah kategori yang ditambahkan.
+#[tauri::command]
+pub fn install_default_categories(
+    db: State<'_, Database>,
+    locale: String,
+) -> Result<usize, AppError> {
+    service(&db).install_default_categories(&locale)
+}
+
+fn service(db: &Database) -> CategoryService<SqliteCategoryRepository<'_>> {
+    CategoryService::new(SqliteCategoryRepository::new(db))
+}
@@ -0,0 +1,54 @@
+//! Command Tauri untuk pengelolaan kurs dan konversi ke mata uang pelaporan.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::exchange_rate::ExchangeRate;
+use crate::models::money::Money;
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::exchange_rate_repository::SqliteExchangeRateRepository;
+use crate::services::currency_service::CurrencyService;
+use chrono::NaiveDate;
+use tauri::State;
+
+#[tauri::command]
+pub fn add_exchange_rate(
+    db: State<'_, Database>,
+    date: NaiveDate,
+    base_currency: String,
+    quote_currency: String,
+    rate: f64,
+) -> Result<(), AppError> {
+    service(&db).add_exchange_rate(date, &base_currency, &quote_currency, rate)
+}
+
+#[tauri::command]
+pub fn get_all_exchange_rates(db: State<'_, Database>) -> Result<Vec<ExchangeRate>, AppError> {
+    service(&db).get_all_exchange_rates()
+}
+
+#[tauri::command]
+pub fn delete_exchange_rate(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_exchange_rate(id)
+}
+
+#[tauri::command]
+pub fn import_exchange_rates_csv(db: State<'_, Database>, path: String) -> Result<usize, AppError> {
+    service(&db).import_exchange_rates_csv(&path)
+}
+
+#[tauri::command]
+pub fn get_total_balance(
+    db: State<'_, Database>,
+    reporting_currency: String,
+) -> Result<Money, AppError> {
+    service(&db).get_total_balance(&reporting_currency)
+}
+
+fn service(
+    db: &Database,
+) -> CurrencyService<SqliteExchangeRateRepository<'_>, SqliteAccountRepository<'_>> {
+    CurrencyService::new(
+        SqliteExchangeRateRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
@@ -0,0 +1,97 @@
+//! Command Tauri untuk utang, piutang, dan pembayarannya.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::debt::{
+    CounterpartyBalance, Debt, DebtInput, DebtPayment, DebtPaymentInput, DebtStatus,
+};
+use crate::models::loan::{LoanInput, LoanSchedule};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::repositories::debt_repository::SqliteDebtRepository;
+use crate::services::debt_service::{self, DebtService};
+use chrono::NaiveDate;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_debt(db: State<'_, Database>, input: DebtInput) -> Result<(), AppError> {
+    service(&db).create_debt(&input)
+}
+
+#[tauri::command]
+pub fn get_all_debts(db: State<'_, Database>) -> Result<Vec<Debt>, AppError> {
+    service(&db).get_all_debts()
+}
+
+#[tauri::command]
+pub fn get_debt_by_id(db: State<'_, Database>, id: i64) -> Result<Debt, AppError> {
+    service(&db).get_debt_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_debt(db: State<'_, Database>, id: i64, input: DebtInput) -> Result<(), AppError> {
+    service(&db).update_debt(id, &input)
+}
+
+#[tauri::command]
+pub fn delete_debt(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_debt(id)
+}
+
+/// Mencatat pembayaran utang sebagai pengeluaran, atau pelunasan piutang sebagai pemasukan.
+#[tauri::command]
+pub fn add_debt_payment(
+    db: State<'_, Database>,
+    debt_id: i64,
+    input: DebtPaymentInput,
+) -> Result<(), AppError> {
+    service(&db).add_payment(debt_id, &input)
+}
+
+#[tauri::command]
+pub fn get_debt_payments(
+    db: State<'_, Database>,
+    debt_id: i64,
+) -> Result<Vec<DebtPayment>, AppError> {
+    service(&db).get_payments(debt_id)
+}
+
+#[tauri::command]
+pub fn delete_debt_payment(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_payment(id)
+}
+
+/// Sisa utang dan status setiap cicilan per `date` (default hari ini).
+#[tauri::command]
+pub fn get_debt_status(
+    db: State<'_, Database>,
+    id: i64,
+    date: Option<NaiveDate>,
+) -> Result<DebtStatus, AppError> {
+    service(&db).get_debt_status(id, date)
+}
+
+/// Sisa utang dan piutang yang belum lunas per pihak lawan.
+#[tauri::command]
+pub fn get_counterparty_balances(
+    db: State<'_, Database>,
+) -> Result<Vec<CounterpartyBalance>, AppError> {
+    service(&db).get_counterparty_balances()
+}
+
+/// Simulasi tabel angsuran (bunga flat atau anuitas) tanpa menyimpan utang.
+#[tauri::command]
+pub fn calculate_loan_schedule(input: LoanInput) -> Result<LoanSchedule, AppError> {
+    debt_service::calculate_loan_schedule(&input)
+}
+
+fn service(
+    db: &Database,
+) -> DebtService<SqliteDebtRepository<'_>, SqliteAccountRepository<'_>, SqliteCategoryRepository<'_>>
+{
+    DebtService::new(
+        SqliteDebtRepository::new(db),
+        SqliteAccountRepository::new(db),
+        SqliteCategoryRepository::new(db),
+    )
+}
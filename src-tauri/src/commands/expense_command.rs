@@ -1 +1,75 @@
+//! Command Tauri untuk pengeluaran.
 
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::expense::Expense;
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::repositories::expense_repository::SqliteExpenseRepository;
+use crate::services::expense_service::ExpenseService;
+use chrono::NaiveDateTime;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_expense(
+    db: State<'_, Database>,
+    description: String,
+    amount: f64,
+    date: NaiveDateTime,
+    category_id: i64,
+    account_id: i64,
+) -> Result<(), AppError> {
+    service(&db).create_expense(&description, amount, date, category_id, account_id)
+}
+
+#[tauri::command]
+pub fn get_all_expenses(db: State<'_, Database>) -> Result<Vec<Expense>, AppError> {
+    service(&db).get_all_expenses()
+}
+
+#[tauri::command]
+pub fn get_expense_by_id(db: State<'_, Database>, id: i64) -> Result<Expense, AppError> {
+    service(&db).get_expense_by_id(id)
+}
+
+/// Daftar pengeluaran per halaman; `total_count` adalah jumlah seluruh data yang cocok.
+#[tauri::command]
+pub fn query_expenses(
+    db: State<'_, Database>,
+    query: TransactionQuery,
+) -> Result<Page<Expense>, AppError> {
+    service(&db).query_expenses(&query)
+}
+
+#[tauri::command]
+pub fn update_expense(
+    db: State<'_, Database>,
+    id: i64,
+    description: String,
+    amount: f64,
+    date: NaiveDateTime,
+    category_id: i64,
+    account_id: i64,
+) -> Result<(), AppError> {
+    service(&db).update_expense(id, &description, amount, date, category_id, account_id)
+}
+
+#[tauri::command]
+pub fn delete_expense(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_expense(id)
+}
+
+fn service(
+    db: &Database,
+) -> ExpenseService<
+    SqliteExpenseRepository<'_>,
+    SqliteCategoryRepository<'_>,
+    SqliteAccountRepository<'_>,
+> {
+    ExpenseService::new(
+        SqliteExpenseRepository::new(db),
+        SqliteCategoryRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
@@ -0,0 +1,75 @@
+//! Command Tauri untuk target tabungan dan setorannya.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::goal::{ContributionInput, Goal, GoalContribution, GoalInput, GoalProgress};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::goal_repository::SqliteGoalRepository;
+use crate::services::goal_service::GoalService;
+use chrono::NaiveDate;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_goal(db: State<'_, Database>, input: GoalInput) -> Result<(), AppError> {
+    service(&db).create_goal(&input)
+}
+
+#[tauri::command]
+pub fn get_all_goals(db: State<'_, Database>) -> Result<Vec<Goal>, AppError> {
+    service(&db).get_all_goals()
+}
+
+#[tauri::command]
+pub fn get_goal_by_id(db: State<'_, Database>, id: i64) -> Result<Goal, AppError> {
+    service(&db).get_goal_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_goal(db: State<'_, Database>, id: i64, input: GoalInput) -> Result<(), AppError> {
+    service(&db).update_goal(id, &input)
+}
+
+#[tauri::command]
+pub fn delete_goal(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_goal(id)
+}
+
+/// Mencatat setoran ke target; jumlah negatif berarti penarikan.
+#[tauri::command]
+pub fn add_goal_contribution(
+    db: State<'_, Database>,
+    goal_id: i64,
+    input: ContributionInput,
+) -> Result<(), AppError> {
+    service(&db).add_contribution(goal_id, &input)
+}
+
+#[tauri::command]
+pub fn get_goal_contributions(
+    db: State<'_, Database>,
+    goal_id: i64,
+) -> Result<Vec<GoalContribution>, AppError> {
+    service(&db).get_contributions(goal_id)
+}
+
+#[tauri::command]
+pub fn delete_goal_contribution(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_contribution(id)
+}
+
+/// Tabungan terkumpul, perkiraan tanggal tercapai, dan kebutuhan per bulan untuk setiap
+/// target per `date` (default hari ini).
+#[tauri::command]
+pub fn get_goal_progress(
+    db: State<'_, Database>,
+    date: Option<NaiveDate>,
+) -> Result<Vec<GoalProgress>, AppError> {
+    service(&db).get_goal_progress(date)
+}
+
+fn service(db: &Database) -> GoalService<SqliteGoalRepository<'_>, SqliteAccountRepository<'_>> {
+    GoalService::new(
+        SqliteGoalRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
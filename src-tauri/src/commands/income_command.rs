@@ -0,0 +1,75 @@
+//! Command Tauri untuk pemasukan.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::income::Income;
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::repositories::income_repository::SqliteIncomeRepository;
+use crate::services::income_service::IncomeService;
+use chrono::NaiveDateTime;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_income(
+    db: State<'_, Database>,
+    description: String,
+    amount: f64,
+    date: NaiveDateTime,
+    category_id: i64,
+    account_id: i64,
+) -> Result<(), AppError> {
+    service(&db).create_income(&description, amount, date, category_id, account_id)
+}
+
+#[tauri::command]
+pub fn get_all_incomes(db: State<'_, Database>) -> Result<Vec<Income>, AppError> {
+    service(&db).get_all_incomes()
+}
+
+#[tauri::command]
+pub fn get_income_by_id(db: State<'_, Database>, id: i64) -> Result<Income, AppError> {
+    service(&db).get_income_by_id(id)
+}
+
+/// Daftar pemasukan per halaman; `total_count` adalah jumlah seluruh data yang cocok.
+#[tauri::command]
+pub fn query_incomes(
+    db: State<'_, Database>,
+    query: TransactionQuery,
+) -> Result<Page<Income>, AppError> {
+    service(&db).query_incomes(&query)
+}
+
+#[tauri::command]
+pub fn update_income(
+    db: State<'_, Database>,
+    id: i64,
+    description: String,
+    amount: f64,
+    date: NaiveDateTime,
+    category_id: i64,
+    account_id: i64,
+) -> Result<(), AppError> {
+    service(&db).update_income(id, &description, amount, date, category_id, account_id)
+}
+
+#[tauri::command]
+pub fn delete_income(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_income(id)
+}
+
+fn service(
+    db: &Database,
+) -> IncomeService<
+    SqliteIncomeRepository<'_>,
+    SqliteCategoryRepository<'_>,
+    SqliteAccountRepository<'_>,
+> {
+    IncomeService::new(
+        SqliteIncomeRepository::new(db),
+        SqliteCategoryRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
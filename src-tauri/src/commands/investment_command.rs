@@ -0,0 +1,125 @@
+//! Command Tauri untuk akun investasi: instrumen, transaksi, harga, dan valuasi portofolio.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::investment::{
+    Holding, Instrument, InstrumentInput, InstrumentPrice, InvestmentTransaction,
+    InvestmentTransactionInput, PortfolioValuation,
+};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::investment_repository::SqliteInvestmentRepository;
+use crate::services::investment_service::InvestmentService;
+use chrono::NaiveDate;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_instrument(db: State<'_, Database>, input: InstrumentInput) -> Result<(), AppError> {
+    service(&db).create_instrument(&input)
+}
+
+#[tauri::command]
+pub fn get_all_instruments(db: State<'_, Database>) -> Result<Vec<Instrument>, AppError> {
+    service(&db).get_all_instruments()
+}
+
+#[tauri::command]
+pub fn get_instrument_by_id(db: State<'_, Database>, id: i64) -> Result<Instrument, AppError> {
+    service(&db).get_instrument_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_instrument(
+    db: State<'_, Database>,
+    id: i64,
+    input: InstrumentInput,
+) -> Result<(), AppError> {
+    service(&db).update_instrument(id, &input)
+}
+
+#[tauri::command]
+pub fn delete_instrument(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_instrument(id)
+}
+
+#[tauri::command]
+pub fn add_investment_transaction(
+    db: State<'_, Database>,
+    input: InvestmentTransactionInput,
+) -> Result<(), AppError> {
+    service(&db).add_transaction(&input)
+}
+
+/// Transaksi investasi semua akun, atau hanya `account_id` bila diisi.
+#[tauri::command]
+pub fn get_investment_transactions(
+    db: State<'_, Database>,
+    account_id: Option<i64>,
+) -> Result<Vec<InvestmentTransaction>, AppError> {
+    service(&db).get_transactions(account_id)
+}
+
+#[tauri::command]
+pub fn delete_investment_transaction(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_transaction(id)
+}
+
+#[tauri::command]
+pub fn add_instrument_price(
+    db: State<'_, Database>,
+    instrument_id: i64,
+    date: NaiveDate,
+    price: f64,
+) -> Result<(), AppError> {
+    service(&db).add_price(instrument_id, date, price)
+}
+
+#[tauri::command]
+pub fn get_instrument_prices(
+    db: State<'_, Database>,
+    instrument_id: i64,
+) -> Result<Vec<InstrumentPrice>, AppError> {
+    service(&db).get_prices(instrument_id)
+}
+
+#[tauri::command]
+pub fn delete_instrument_price(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_price(id)
+}
+
+/// Mengimpor harga dari file CSV `date,symbol,price`; mengembalikan jumlah baris yang diimpor.
+#[tauri::command]
+pub fn import_instrument_prices_csv(
+    db: State<'_, Database>,
+    path: String,
+) -> Result<usize, AppError> {
+    service(&db).import_prices_csv(&path)
+}
+
+/// Kepemilikan per `date` (default hari ini), semua akun atau hanya `account_id`.
+#[tauri::command]
+pub fn get_holdings(
+    db: State<'_, Database>,
+    account_id: Option<i64>,
+    date: Option<NaiveDate>,
+) -> Result<Vec<Holding>, AppError> {
+    service(&db).get_holdings(account_id, date)
+}
+
+#[tauri::command]
+pub fn get_portfolio_valuation(
+    db: State<'_, Database>,
+    account_id: i64,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+) -> Result<PortfolioValuation, AppError> {
+    service(&db).get_portfolio_valuation(account_id, date_from, date_to)
+}
+
+fn service(
+    db: &Database,
+) -> InvestmentService<SqliteInvestmentRepository<'_>, SqliteAccountRepository<'_>> {
+    InvestmentService::new(
+        SqliteInvestmentRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
@@ -0,0 +1,22 @@
+//! Command Tauri untuk buku besar gabungan (pemasukan, pengeluaran, dan transfer).
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::ledger::{LedgerEntry, LedgerQuery};
+use crate::models::transaction_query::Page;
+use crate::repositories::ledger_repository::SqliteLedgerRepository;
+use crate::services::ledger_service::LedgerService;
+use tauri::State;
+
+/// Buku besar per halaman; `balance_after` adalah saldo akun setelah setiap entri.
+#[tauri::command]
+pub fn get_ledger(
+    db: State<'_, Database>,
+    query: LedgerQuery,
+) -> Result<Page<LedgerEntry>, AppError> {
+    service(&db).get_ledger(&query)
+}
+
+fn service(db: &Database) -> LedgerService<SqliteLedgerRepository<'_>> {
+    LedgerService::new(SqliteLedgerRepository::new(db))
+}
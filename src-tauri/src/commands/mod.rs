@@ -1,2 +1,15 @@
 pub mod account_command;
+pub mod budget_command;
+pub mod category_command;
+pub mod currency_command;
+pub mod debt_command;
 pub mod expense_command;
+pub mod goal_command;
+pub mod income_command;
+pub mod investment_command;
+pub mod ledger_command;
+pub mod net_worth_command;
+pub mod recurring_command;
+pub mod report_command;
+pub mod search_command;
+pub mod transfer_command;
@@ -0,0 +1,71 @@
+//! Command Tauri untuk kekayaan bersih dan riwayatnya.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::net_worth::{NetWorth, NetWorthPoint};
+use crate::models::report::ReportPeriod;
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::debt_repository::SqliteDebtRepository;
+use crate::repositories::exchange_rate_repository::SqliteExchangeRateRepository;
+use crate::repositories::investment_repository::SqliteInvestmentRepository;
+use crate::repositories::net_worth_repository::SqliteNetWorthRepository;
+use crate::services::currency_service::CurrencyService;
+use crate::services::net_worth_service::NetWorthService;
+use chrono::{Local, NaiveDate};
+use tauri::State;
+
+/// Kekayaan bersih hari ini dengan rincian aset dan kewajiban.
+#[tauri::command]
+pub fn get_net_worth(
+    db: State<'_, Database>,
+    reporting_currency: String,
+) -> Result<NetWorth, AppError> {
+    service(&db).get_net_worth(Local::now().date_naive(), &reporting_currency)
+}
+
+/// Riwayat kekayaan bersih untuk grafik, satu titik per `period` ("day", "week", "month",
+/// atau "year").
+#[tauri::command]
+pub fn get_net_worth_history(
+    db: State<'_, Database>,
+    period: ReportPeriod,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+    reporting_currency: String,
+) -> Result<Vec<NetWorthPoint>, AppError> {
+    service(&db).get_net_worth_history(period, date_from, date_to, &reporting_currency)
+}
+
+/// Mencatat cuplikan kekayaan bersih hari ini sekarang juga, tanpa menunggu pencatatan
+/// berkala.
+#[tauri::command]
+pub fn record_net_worth_snapshot(db: State<'_, Database>) -> Result<(), AppError> {
+    record_snapshot(&db)
+}
+
+/// Mencatat cuplikan kekayaan bersih hari ini. Dipakai juga oleh pencatatan berkala yang
+/// dijalankan saat aplikasi dimulai.
+pub(crate) fn record_snapshot(db: &Database) -> Result<(), AppError> {
+    service(db).record_snapshot(Local::now().date_naive())
+}
+
+fn service(
+    db: &Database,
+) -> NetWorthService<
+    SqliteNetWorthRepository<'_>,
+    SqliteAccountRepository<'_>,
+    SqliteInvestmentRepository<'_>,
+    SqliteDebtRepository<'_>,
+    SqliteExchangeRateRepository<'_>,
+> {
+    NetWorthService::new(
+        SqliteNetWorthRepository::new(db),
+        SqliteAccountRepository::new(db),
+        SqliteInvestmentRepository::new(db),
+        SqliteDebtRepository::new(db),
+        CurrencyService::new(
+            SqliteExchangeRateRepository::new(db),
+            SqliteAccountRepository::new(db),
+        ),
+    )
+}
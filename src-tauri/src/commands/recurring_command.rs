@@ -0,0 +1,106 @@
+//! Command Tauri untuk transaksi berulang.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::recurring::{
+    OccurrenceEditInput, RecurringOccurrence, RecurringRule, RecurringRuleInput,
+};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::category_repository::SqliteCategoryRepository;
+use crate::repositories::recurring_repository::SqliteRecurringRepository;
+use crate::services::recurring_service::RecurringService;
+use chrono::{Local, NaiveDate};
+use tauri::State;
+
+#[tauri::command]
+pub fn create_recurring_rule(
+    db: State<'_, Database>,
+    input: RecurringRuleInput,
+) -> Result<(), AppError> {
+    service(&db).create_rule(&input)
+}
+
+#[tauri::command]
+pub fn get_all_recurring_rules(db: State<'_, Database>) -> Result<Vec<RecurringRule>, AppError> {
+    service(&db).get_all_rules()
+}
+
+#[tauri::command]
+pub fn get_recurring_rule_by_id(
+    db: State<'_, Database>,
+    id: i64,
+) -> Result<RecurringRule, AppError> {
+    service(&db).get_rule_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_recurring_rule(
+    db: State<'_, Database>,
+    id: i64,
+    input: RecurringRuleInput,
+) -> Result<(), AppError> {
+    service(&db).update_rule(id, &input, Local::now().date_naive())
+}
+
+#[tauri::command]
+pub fn delete_recurring_rule(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_rule(id)
+}
+
+/// Kejadian berikutnya mulai `from` (default hari ini); `count` default 10, maksimal 100.
+#[tauri::command]
+pub fn preview_recurring_occurrences(
+    db: State<'_, Database>,
+    rule_id: i64,
+    from: Option<NaiveDate>,
+    count: Option<u32>,
+) -> Result<Vec<RecurringOccurrence>, AppError> {
+    let from = from.unwrap_or_else(|| Local::now().date_naive());
+    service(&db).preview_occurrences(rule_id, from, count)
+}
+
+#[tauri::command]
+pub fn skip_recurring_occurrence(
+    db: State<'_, Database>,
+    rule_id: i64,
+    scheduled_date: NaiveDate,
+) -> Result<(), AppError> {
+    service(&db).skip_occurrence(rule_id, scheduled_date)
+}
+
+#[tauri::command]
+pub fn edit_recurring_occurrence(
+    db: State<'_, Database>,
+    rule_id: i64,
+    scheduled_date: NaiveDate,
+    input: OccurrenceEditInput,
+) -> Result<(), AppError> {
+    service(&db).edit_occurrence(rule_id, scheduled_date, &input)
+}
+
+/// Membuat transaksi berulang yang jatuh tempo sekarang juga, tanpa menunggu pemeriksaan
+/// berkala. Mengembalikan jumlah transaksi yang dibuat.
+#[tauri::command]
+pub fn materialize_recurring_transactions(db: State<'_, Database>) -> Result<usize, AppError> {
+    materialize_due(&db)
+}
+
+/// Membuat transaksi berulang yang jatuh tempo sampai hari ini. Dipakai juga oleh
+/// pemeriksaan berkala yang dijalankan saat aplikasi dimulai.
+pub(crate) fn materialize_due(db: &Database) -> Result<usize, AppError> {
+    service(db).materialize_due(Local::now().date_naive())
+}
+
+fn service(
+    db: &Database,
+) -> RecurringService<
+    SqliteRecurringRepository<'_>,
+    SqliteCategoryRepository<'_>,
+    SqliteAccountRepository<'_>,
+> {
+    RecurringService::new(
+        SqliteRecurringRepository::new(db),
+        SqliteCategoryRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
@@ -0,0 +1,66 @@
+//! Command Tauri untuk laporan ringkasan dan tren keuangan di dashboard.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::report::{
+    AccountTotal, CategoryTotal, IncomeExpenseTotal, PeriodTotal, ReportPeriod, ReportQuery,
+};
+use crate::repositories::report_repository::SqliteReportRepository;
+use crate::services::report_service::ReportService;
+use tauri::State;
+
+/// `period`: "day", "week", "month", atau "year".
+#[tauri::command]
+pub fn get_totals_by_period(
+    db: State<'_, Database>,
+    period: ReportPeriod,
+    query: Option<ReportQuery>,
+) -> Result<Vec<PeriodTotal>, AppError> {
+    service(&db).get_totals_by_period(period, &query.unwrap_or_default())
+}
+
+/// `category_type`: 1 untuk Income, 2 untuk Expense, kosong untuk keduanya.
+/// `rollup`: jumlahkan total sub-kategori ke induknya.
+#[tauri::command]
+pub fn get_totals_by_category(
+    db: State<'_, Database>,
+    category_type: Option<i16>,
+    rollup: Option<bool>,
+    query: Option<ReportQuery>,
+) -> Result<Vec<CategoryTotal>, AppError> {
+    service(&db).get_totals_by_category(
+        category_type,
+        rollup.unwrap_or(false),
+        &query.unwrap_or_default(),
+    )
+}
+
+#[tauri::command]
+pub fn get_totals_by_account(
+    db: State<'_, Database>,
+    query: Option<ReportQuery>,
+) -> Result<Vec<AccountTotal>, AppError> {
+    service(&db).get_totals_by_account(&query.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn get_income_expense_summary(
+    db: State<'_, Database>,
+    query: Option<ReportQuery>,
+) -> Result<Vec<IncomeExpenseTotal>, AppError> {
+    service(&db).get_income_expense_summary(&query.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn get_top_categories(
+    db: State<'_, Database>,
+    category_type: Option<i16>,
+    limit: Option<u32>,
+    query: Option<ReportQuery>,
+) -> Result<Vec<CategoryTotal>, AppError> {
+    service(&db).get_top_categories(category_type, limit, &query.unwrap_or_default())
+}
+
+fn service(db: &Database) -> ReportService<SqliteReportRepository<'_>> {
+    ReportService::new(SqliteReportRepository::new(db))
+}
@@ -0,0 +1,22 @@
+//! Command Tauri untuk pencarian teks penuh atas pemasukan dan pengeluaran.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::search::SearchResult;
+use crate::repositories::search_repository::SqliteSearchRepository;
+use crate::services::search_service::SearchService;
+use tauri::State;
+
+/// Setiap kata dicari sebagai awalan; teks di antara tanda kutip dicari sebagai frasa.
+#[tauri::command]
+pub fn search_transactions(
+    db: State<'_, Database>,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<SearchResult>, AppError> {
+    service(&db).search_transactions(&query, limit)
+}
+
+fn service(db: &Database) -> SearchService<SqliteSearchRepository<'_>> {
+    SearchService::new(SqliteSearchRepository::new(db))
+}
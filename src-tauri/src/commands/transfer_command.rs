@@ -0,0 +1,47 @@
+//! Command Tauri untuk transfer antar akun.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::transfer::{Transfer, TransferInput};
+use crate::repositories::account_repository::SqliteAccountRepository;
+use crate::repositories::transfer_repository::SqliteTransferRepository;
+use crate::services::transfer_service::TransferService;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_transfer(db: State<'_, Database>, transfer: TransferInput) -> Result<(), AppError> {
+    service(&db).create_transfer(&transfer)
+}
+
+#[tauri::command]
+pub fn get_all_transfers(db: State<'_, Database>) -> Result<Vec<Transfer>, AppError> {
+    service(&db).get_all_transfers()
+}
+
+#[tauri::command]
+pub fn get_transfer_by_id(db: State<'_, Database>, id: i64) -> Result<Transfer, AppError> {
+    service(&db).get_transfer_by_id(id)
+}
+
+#[tauri::command]
+pub fn update_transfer(
+    db: State<'_, Database>,
+    id: i64,
+    transfer: TransferInput,
+) -> Result<(), AppError> {
+    service(&db).update_transfer(id, &transfer)
+}
+
+#[tauri::command]
+pub fn delete_transfer(db: State<'_, Database>, id: i64) -> Result<(), AppError> {
+    service(&db).delete_transfer(id)
+}
+
+fn service(
+    db: &Database,
+) -> TransferService<SqliteTransferRepository<'_>, SqliteAccountRepository<'_>> {
+    TransferService::new(
+        SqliteTransferRepository::new(db),
+        SqliteAccountRepository::new(db),
+    )
+}
@@ -0,0 +1,96 @@
+//! Pemeriksaan integritas skema saat aplikasi dimulai.
+//! Memastikan setiap tabel dan kolom yang dipakai oleh repository benar-benar ada,
+//! sehingga kerusakan skema terdeteksi sebelum UI ditampilkan, bukan saat query pertama gagal.
+
+use crate::db::seeds;
+use crate::error::AppError;
+use crate::repositories::{
+    account_repository, budget_repository, category_repository, debt_repository,
+    exchange_rate_repository, expense_repository, goal_repository, income_repository,
+    investment_repository, ledger_repository, net_worth_repository, recurring_repository,
+    search_repository, transfer_repository,
+};
+use rusqlite::{params, Connection};
+
+/// Tabel beserta kolom yang dibutuhkan oleh masing-masing repository.
+const EXPECTED_SCHEMA: &[(&str, &[&str])] = &[
+    (account_repository::TABLE, account_repository::COLUMNS),
+    (budget_repository::TABLE, budget_repository::COLUMNS),
+    (category_repository::TABLE, category_repository::COLUMNS),
+    (debt_repository::TABLE, debt_repository::COLUMNS),
+    (
+        debt_repository::PAYMENTS_TABLE,
+        debt_repository::PAYMENT_COLUMNS,
+    ),
+    (
+        debt_repository::INSTALLMENTS_TABLE,
+        debt_repository::INSTALLMENT_COLUMNS,
+    ),
+    (
+        exchange_rate_repository::TABLE,
+        exchange_rate_repository::COLUMNS,
+    ),
+    (expense_repository::TABLE, expense_repository::COLUMNS),
+    (goal_repository::TABLE, goal_repository::COLUMNS),
+    (
+        goal_repository::CONTRIBUTIONS_TABLE,
+        goal_repository::CONTRIBUTION_COLUMNS,
+    ),
+    (income_repository::TABLE, income_repository::COLUMNS),
+    (investment_repository::TABLE, investment_repository::COLUMNS),
+    (
+        investment_repository::INSTRUMENTS_TABLE,
+        investment_repository::INSTRUMENT_COLUMNS,
+    ),
+    (
+        investment_repository::PRICES_TABLE,
+        investment_repository::PRICE_COLUMNS,
+    ),
+    (ledger_repository::TABLE, ledger_repository::COLUMNS),
+    (net_worth_repository::TABLE, net_worth_repository::COLUMNS),
+    (recurring_repository::TABLE, recurring_repository::COLUMNS),
+    (
+        recurring_repository::OCCURRENCES_TABLE,
+        recurring_repository::OCCURRENCE_COLUMNS,
+    ),
+    (search_repository::TABLE, search_repository::COLUMNS),
+    (seeds::TABLE, seeds::COLUMNS),
+    (transfer_repository::TABLE, transfer_repository::COLUMNS),
+];
+
+/// Memverifikasi skema database terhadap kebutuhan repository.
+/// Mengembalikan error berisi daftar semua tabel/kolom yang hilang.
+pub fn verify_schema(conn: &Connection) -> Result<(), AppError> {
+    let mut problems = Vec::new();
+
+    for (table, columns) in EXPECTED_SCHEMA {
+        let existing = table_columns(conn, table)?;
+
+        if existing.is_empty() {
+            problems.push(format!("tabel '{}' tidak ditemukan", table));
+            continue;
+        }
+
+        for column in columns.iter() {
+            if !existing.iter().any(|c| c == column) {
+                problems.push(format!("kolom '{}.{}' tidak ditemukan", table, column));
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Schema(format!(
+            "Skema database tidak valid: {}",
+            problems.join(", ")
+        )))
+    }
+}
+
+/// Mengambil nama-nama kolom sebuah tabel (kosong jika tabel tidak ada).
+fn table_columns(conn: &Connection, table: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let columns = stmt.query_map(params![table], |row| row.get(0))?;
+    columns.collect()
+}
@@ -0,0 +1,218 @@
+//! Menentukan lokasi file database dan bahasa data awalnya.
+//!
+//! Urutan prioritas:
+//! 1. Variabel lingkungan `KEUANGANKU_DB_PATH` (berguna untuk pengujian).
+//! 2. Field `database_path` pada file `settings.json` di direktori konfigurasi aplikasi.
+//! 3. `finance.db` di direktori data aplikasi milik OS (misal `%APPDATA%` atau `~/.local/share`).
+//!
+//! Versi lama aplikasi membuka `finance.db` relatif terhadap direktori kerja. Jika file
+//! tersebut ditemukan dan lokasi default belum memiliki database, file lama dipindahkan.
+
+use crate::error::AppError;
+use crate::models::default_category::DEFAULT_LOCALE;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DB_FILE_NAME: &str = "finance.db";
+pub const DB_PATH_ENV: &str = "KEUANGANKU_DB_PATH";
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Isi `settings.json` yang relevan untuk database.
+#[derive(Deserialize, Debug, Default)]
+struct Settings {
+    database_path: Option<PathBuf>,
+    /// Bahasa kategori bawaan yang dipasang pada database baru, misal `id` atau `en`.
+    locale: Option<String>,
+}
+
+/// Menentukan path database sesuai urutan prioritas di atas.
+pub fn resolve_database_path(
+    app_data_dir: &Path,
+    app_config_dir: &Path,
+) -> Result<PathBuf, AppError> {
+    if let Some(path) = std::env::var_os(DB_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    if let Some(path) = read_settings(app_config_dir)?.database_path {
+        return Ok(path);
+    }
+
+    let path = app_data_dir.join(DB_FILE_NAME);
+    migrate_legacy_database(&path)?;
+    Ok(path)
+}
+
+/// Menentukan bahasa kategori bawaan dari field `locale` pada `settings.json`,
+/// atau `DEFAULT_LOCALE` bila tidak diisi.
+pub fn resolve_locale(app_config_dir: &Path) -> Result<String, AppError> {
+    Ok(read_settings(app_config_dir)?
+        .locale
+        .filter(|locale| !locale.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string()))
+}
+
+fn read_settings(app_config_dir: &Path) -> Result<Settings, AppError> {
+    let path = app_config_dir.join(SETTINGS_FILE_NAME);
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::Io(format!(
+            "File pengaturan '{}' tidak valid: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Memindahkan `./finance.db` peninggalan versi lama ke `target` jika `target` belum ada.
+/// File pendamping SQLite (`-wal`, `-shm`, `-journal`) ikut dipindahkan.
+/// Mengembalikan `true` jika ada database yang dipindahkan.
+pub fn migrate_legacy_database(target: &Path) -> Result<bool, AppError> {
+    move_legacy_database(&std::env::current_dir()?.join(DB_FILE_NAME), target)
+}
+
+fn move_legacy_database(legacy: &Path, target: &Path) -> Result<bool, AppError> {
+    if !legacy.is_file() || target.exists() || same_file(legacy, target) {
+        return Ok(false);
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    for suffix in ["", "-wal", "-shm", "-journal"] {
+        let from = sidecar(legacy, suffix);
+        if from.exists() {
+            move_file(&from, &sidecar(target, suffix))?;
+        }
+    }
+    Ok(true)
+}
+
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Memindahkan file; jika `rename` gagal (misal beda drive), salin lalu hapus file asal.
+fn move_file(from: &Path, to: &Path) -> Result<(), AppError> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Direktori sementara yang kosong dan unik untuk setiap pengujian.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "keuanganku-location-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn env_overrides_settings_which_override_app_data_dir() {
+        let root = temp_dir("resolve");
+        let data_dir = root.join("data");
+        let config_dir = root.join("config");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::create_dir_all(&config_dir).unwrap();
+        // Database default sudah ada, sehingga `./finance.db` milik direktori kerja tidak
+        // ikut dipindahkan oleh pengujian ini.
+        fs::write(data_dir.join(DB_FILE_NAME), b"").unwrap();
+        fs::write(
+            config_dir.join(SETTINGS_FILE_NAME),
+            r#"{ "database_path": "/data/keuangan.db", "locale": "en" }"#,
+        )
+        .unwrap();
+
+        std::env::set_var(DB_PATH_ENV, root.join("env.db"));
+        let from_env = resolve_database_path(&data_dir, &config_dir);
+        std::env::set_var(DB_PATH_ENV, "");
+        let empty_env = resolve_database_path(&data_dir, &config_dir);
+        std::env::remove_var(DB_PATH_ENV);
+        let from_settings = resolve_database_path(&data_dir, &config_dir);
+        assert_eq!(resolve_locale(&config_dir).unwrap(), "en");
+        fs::remove_file(config_dir.join(SETTINGS_FILE_NAME)).unwrap();
+        let default = resolve_database_path(&data_dir, &config_dir);
+
+        assert_eq!(from_env.unwrap(), root.join("env.db"));
+        assert_eq!(empty_env.unwrap(), PathBuf::from("/data/keuangan.db"));
+        assert_eq!(from_settings.unwrap(), PathBuf::from("/data/keuangan.db"));
+        assert_eq!(default.unwrap(), data_dir.join(DB_FILE_NAME));
+        assert_eq!(resolve_locale(&config_dir).unwrap(), DEFAULT_LOCALE);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn invalid_settings_file_is_reported() {
+        let dir = temp_dir("settings");
+        fs::write(dir.join(SETTINGS_FILE_NAME), "{ database_path: ").unwrap();
+
+        let err = resolve_locale(&dir).unwrap_err();
+
+        assert_eq!(err.code(), "IO_ERROR");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_database_and_sidecars_move_to_target() {
+        let dir = temp_dir("legacy");
+        let legacy = dir.join(DB_FILE_NAME);
+        fs::write(&legacy, b"lama").unwrap();
+        fs::write(sidecar(&legacy, "-wal"), b"wal").unwrap();
+        let target = dir.join("app-data").join(DB_FILE_NAME);
+
+        assert!(move_legacy_database(&legacy, &target).unwrap());
+
+        assert!(!legacy.exists());
+        assert!(!sidecar(&legacy, "-wal").exists());
+        assert_eq!(fs::read(&target).unwrap(), b"lama");
+        assert_eq!(fs::read(sidecar(&target, "-wal")).unwrap(), b"wal");
+        assert!(!sidecar(&target, "-shm").exists());
+        // Tidak ada lagi yang perlu dipindahkan.
+        assert!(!move_legacy_database(&legacy, &target).unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn existing_target_is_never_overwritten() {
+        let dir = temp_dir("existing");
+        let legacy = dir.join(DB_FILE_NAME);
+        fs::write(&legacy, b"lama").unwrap();
+        let target = dir.join("app-data").join(DB_FILE_NAME);
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, b"baru").unwrap();
+
+        assert!(!move_legacy_database(&legacy, &target).unwrap());
+        // Legacy dan target yang menunjuk file yang sama juga dibiarkan.
+        assert!(!move_legacy_database(&legacy, &legacy).unwrap());
+
+        assert_eq!(fs::read(&legacy).unwrap(), b"lama");
+        assert_eq!(fs::read(&target).unwrap(), b"baru");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
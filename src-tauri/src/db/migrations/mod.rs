@@ -0,0 +1,318 @@
+//! Sistem migrasi skema database berbasis nomor versi.
+//!
+//! Setiap migrasi memiliki nomor versi unik yang berurutan dan dicatat di tabel
+//! `schema_migrations` setelah berhasil diterapkan, sehingga tidak pernah dijalankan dua kali.
+//! Migrasi hanya bergerak maju (forward-only): perubahan skema berikutnya selalu
+//! ditambahkan sebagai migrasi baru, bukan dengan mengubah migrasi yang sudah ada.
+
+use crate::error::AppError;
+use rusqlite::{params, Connection};
+
+mod v001_initial_schema;
+mod v002_rename_income_to_incomes;
+mod v003_money_minor_units;
+mod v004_exchange_rates;
+mod v005_transaction_accounts;
+mod v006_transfers;
+mod v007_transaction_query_indexes;
+mod v008_ledger_view;
+mod v009_transaction_search;
+mod v010_category_parents;
+mod v011_category_defaults;
+mod v012_budgets;
+mod v013_recurring_rules;
+mod v014_goals;
+mod v015_debts;
+mod v016_loan_schedules;
+mod v017_investments;
+mod v018_net_worth_snapshots;
+mod v019_repair_orphaned_categories;
+mod v020_recurring_materialize_from;
+mod v021_debt_payment_auto_link;
+mod v022_debt_disbursements;
+
+/// Satu langkah migrasi skema.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+/// Daftar seluruh migrasi, harus terurut berdasarkan `version`.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        up: v001_initial_schema::up,
+    },
+    Migration {
+        version: 2,
+        name: "rename_income_to_incomes",
+        up: v002_rename_income_to_incomes::up,
+    },
+    Migration {
+        version: 3,
+        name: "money_minor_units",
+        up: v003_money_minor_units::up,
+    },
+    Migration {
+        version: 4,
+        name: "exchange_rates",
+        up: v004_exchange_rates::up,
+    },
+    Migration {
+        version: 5,
+        name: "transaction_accounts",
+        up: v005_transaction_accounts::up,
+    },
+    Migration {
+        version: 6,
+        name: "transfers",
+        up: v006_transfers::up,
+    },
+    Migration {
+        version: 7,
+        name: "transaction_query_indexes",
+        up: v007_transaction_query_indexes::up,
+    },
+    Migration {
+        version: 8,
+        name: "ledger_view",
+        up: v008_ledger_view::up,
+    },
+    Migration {
+        version: 9,
+        name: "transaction_search",
+        up: v009_transaction_search::up,
+    },
+    Migration {
+        version: 10,
+        name: "category_parents",
+        up: v010_category_parents::up,
+    },
+    Migration {
+        version: 11,
+        name: "category_defaults",
+        up: v011_category_defaults::up,
+    },
+    Migration {
+        version: 12,
+        name: "budgets",
+        up: v012_budgets::up,
+    },
+    Migration {
+        version: 13,
+        name: "recurring_rules",
+        up: v013_recurring_rules::up,
+    },
+    Migration {
+        version: 14,
+        name: "goals",
+        up: v014_goals::up,
+    },
+    Migration {
+        version: 15,
+        name: "debts",
+        up: v015_debts::up,
+    },
+    Migration {
+        version: 16,
+        name: "loan_schedules",
+        up: v016_loan_schedules::up,
+    },
+    Migration {
+        version: 17,
+        name: "investments",
+        up: v017_investments::up,
+    },
+    Migration {
+        version: 18,
+        name: "net_worth_snapshots",
+        up: v018_net_worth_snapshots::up,
+    },
+    Migration {
+        version: 19,
+        name: "repair_orphaned_categories",
+        up: v019_repair_orphaned_categories::up,
+    },
+    Migration {
+        version: 20,
+        name: "recurring_materialize_from",
+        up: v020_recurring_materialize_from::up,
+    },
+    Migration {
+        version: 21,
+        name: "debt_payment_auto_link",
+        up: v021_debt_payment_auto_link::up,
+    },
+    Migration {
+        version: 22,
+        name: "debt_disbursements",
+        up: v022_debt_disbursements::up,
+    },
+];
+
+const CREATE_SCHEMA_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        applied_at TEXT NOT NULL
+    )
+"#;
+
+/// Versi skema terbaru yang dikenal oleh aplikasi ini.
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Memeriksa apakah sebuah tabel ada di database.
+pub fn table_exists(conn: &Connection, table: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        params![table],
+        |row| row.get(0),
+    )
+}
+
+/// Mengambil versi skema yang sudah diterapkan pada database (0 jika belum ada).
+pub fn current_version(conn: &Connection) -> Result<i64, AppError> {
+    conn.query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+        row.get::<_, Option<i64>>(0)
+    })
+    .map(|v| v.unwrap_or(0))
+    .map_err(AppError::from)
+}
+
+/// Menjalankan semua migrasi yang belum diterapkan di dalam satu transaksi.
+/// Jika salah satu migrasi gagal, seluruh perubahan dibatalkan dan database tetap
+/// berada pada versi sebelumnya.
+pub fn run(conn: &mut Connection) -> Result<(), AppError> {
+    conn.execute_batch(CREATE_SCHEMA_MIGRATIONS_TABLE)?;
+
+    let current = current_version(conn)?;
+    let latest = latest_version();
+
+    // Database dibuat oleh versi aplikasi yang lebih baru; jangan disentuh.
+    if current > latest {
+        return Err(AppError::Schema(format!(
+            "Versi skema database ({}) lebih baru daripada yang didukung aplikasi ini ({}). \
+             Silakan perbarui aplikasi.",
+            current, latest
+        )));
+    }
+
+    // Foreign key dimatikan selama migrasi agar tabel bisa disalin atau dibangun ulang
+    // tanpa memicu constraint di tengah jalan. PRAGMA ini tidak berlaku di dalam
+    // transaksi, jadi harus diatur sebelum transaksi dimulai.
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let result = apply_pending(conn, current);
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+    result
+}
+
+/// Menerapkan migrasi dengan versi di atas `current`, lalu memastikan tidak ada
+/// pelanggaran foreign key sebelum transaksi di-commit.
+fn apply_pending(conn: &mut Connection, current: i64) -> Result<(), AppError> {
+    let tx = conn.transaction()?;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        (migration.up)(&tx).map_err(|e| {
+            AppError::Schema(format!(
+                "Migrasi {} ({}) gagal: {}",
+                migration.version, migration.name, e
+            ))
+        })?;
+
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, datetime('now'))",
+            params![migration.version, migration.name],
+        )?;
+    }
+
+    let violations: i64 =
+        tx.query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+            row.get(0)
+        })?;
+    if violations > 0 {
+        return Err(AppError::Schema(format!(
+            "Migrasi dibatalkan: ditemukan {} pelanggaran foreign key.",
+            violations
+        )));
+    }
+
+    tx.commit().map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Skema `db.rs` sebelum sistem migrasi ada: tanpa `schema_migrations` dan tanpa akun.
+    const BASELINE_SCHEMA: &str = r#"
+        CREATE TABLE categories (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            category_type INTEGER NOT NULL CHECK(category_type IN (1, 2))
+        );
+        CREATE TABLE income (
+            id INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL,
+            date TEXT NOT NULL,
+            category_id INTEGER NOT NULL,
+            FOREIGN KEY (category_id) REFERENCES categories (id)
+        );
+        CREATE TABLE expenses (
+            id INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL,
+            date TEXT NOT NULL,
+            category_id INTEGER NOT NULL,
+            FOREIGN KEY (category_id) REFERENCES categories (id)
+        );
+    "#;
+
+    #[test]
+    fn baseline_database_with_orphaned_transactions_migrates() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(BASELINE_SCHEMA).unwrap();
+        // Kategori 2 dan 3 sudah dihapus, tetapi transaksinya masih ada. Versi lama tidak
+        // pernah mengaktifkan foreign key, jadi data seperti ini bisa terjadi.
+        conn.execute_batch(
+            "PRAGMA foreign_keys = OFF;
+             INSERT INTO categories (id, name, category_type) VALUES (1, 'Makan', 2);
+             INSERT INTO expenses (description, amount, date, category_id)
+                 VALUES ('Makan siang', 25000, '2023-05-01 12:00:00', 1),
+                        ('Bensin', 50000, '2023-05-02 08:00:00', 2);
+             INSERT INTO income (description, amount, date, category_id)
+                 VALUES ('Gaji', 5000000, '2023-05-01 09:00:00', 3);",
+        )
+        .unwrap();
+
+        run(&mut conn).unwrap();
+
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        let moved: Vec<(String, String, i64)> = conn
+            .prepare(
+                "SELECT t.description, c.name, c.category_type
+                 FROM (SELECT description, category_id FROM expenses
+                       UNION ALL SELECT description, category_id FROM incomes) t
+                 JOIN categories c ON c.id = t.category_id
+                 ORDER BY t.description",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            moved,
+            vec![
+                ("Bensin".to_string(), "Tanpa Kategori".to_string(), 2),
+                ("Gaji".to_string(), "Tanpa Kategori".to_string(), 1),
+                ("Makan siang".to_string(), "Makan".to_string(), 2),
+            ]
+        );
+    }
+}
@@ -1,20 +1,24 @@
-use lazy_static::lazy_static;
-use rusqlite::{Connection, Result};
-use std::sync::Mutex;
+//! Skema awal: tabel kategori, akun, pemasukan, dan pengeluaran.
+//! Memakai `IF NOT EXISTS` agar database lama yang dibuat sebelum sistem migrasi
+//! ada tetap dapat diadopsi tanpa kehilangan data.
 
-// Mengganti nama database agar lebih relevan
-lazy_static! {
-    pub static ref DB_CONNECTION: Mutex<Connection> =
-        Mutex::new(Connection::open("finance.db").expect("failed to open database"));
-}
+use rusqlite::{Connection, Result};
 
-// 1. Variabel DDL (Data Definition Language) untuk setiap model
 const CREATE_CATEGORIES_TABLE: &str = r#"
     CREATE TABLE IF NOT EXISTS categories (
         id INTEGER PRIMARY KEY,
         name TEXT NOT NULL,
         category_type INTEGER NOT NULL CHECK(category_type IN (1, 2))
-    )
+    );
+"#;
+
+const CREATE_ACCOUNTS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS accounts (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        description TEXT,
+        balance REAL NOT NULL DEFAULT 0
+    );
 "#;
 
 const CREATE_INCOME_TABLE: &str = r#"
@@ -25,7 +29,7 @@ const CREATE_INCOME_TABLE: &str = r#"
         date TEXT NOT NULL,
         category_id INTEGER NOT NULL,
         FOREIGN KEY (category_id) REFERENCES categories (id)
-    )
+    );
 "#;
 
 const CREATE_EXPENSES_TABLE: &str = r#"
@@ -36,19 +40,15 @@ const CREATE_EXPENSES_TABLE: &str = r#"
         date TEXT NOT NULL,
         category_id INTEGER NOT NULL,
         FOREIGN KEY (category_id) REFERENCES categories (id)
-    )
+    );
 "#;
 
-// Fungsi untuk inisialisasi & migrasi database
-pub fn init_database() -> Result<()> {
-    let conn = DB_CONNECTION.lock().unwrap();
-
-    // 2. Menjalankan setiap query DDL untuk membuat tabel
-    //    Penting untuk membuat tabel 'categories' terlebih dahulu
-    //    karena tabel lain memiliki foreign key ke sana.
-    conn.execute(CREATE_CATEGORIES_TABLE, [])?;
-    conn.execute(CREATE_INCOME_TABLE, [])?;
-    conn.execute(CREATE_EXPENSES_TABLE, [])?;
-
+pub fn up(conn: &Connection) -> Result<()> {
+    // Tabel 'categories' dibuat terlebih dahulu karena tabel lain
+    // memiliki foreign key ke sana.
+    conn.execute_batch(CREATE_CATEGORIES_TABLE)?;
+    conn.execute_batch(CREATE_ACCOUNTS_TABLE)?;
+    conn.execute_batch(CREATE_INCOME_TABLE)?;
+    conn.execute_batch(CREATE_EXPENSES_TABLE)?;
     Ok(())
 }
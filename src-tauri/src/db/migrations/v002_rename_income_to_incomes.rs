@@ -0,0 +1,50 @@
+//! Menyeragamkan nama tabel pemasukan menjadi `incomes`.
+//!
+//! Skema lama membuat tabel `income`, sedangkan seluruh query di `income_repository`
+//! menggunakan `incomes`. Instalasi lama bisa memiliki salah satu atau kedua tabel,
+//! sehingga data dari `income` disalin ke `incomes` sebelum tabel lama dihapus.
+
+use super::table_exists;
+use rusqlite::{Connection, Result};
+
+const CREATE_INCOMES_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS incomes (
+        id INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        amount REAL NOT NULL,
+        date TEXT NOT NULL,
+        category_id INTEGER NOT NULL,
+        FOREIGN KEY (category_id) REFERENCES categories (id)
+    );
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_INCOMES_TABLE)?;
+
+    if !table_exists(conn, "income")? {
+        return Ok(());
+    }
+
+    let incomes_empty: bool =
+        conn.query_row("SELECT NOT EXISTS(SELECT 1 FROM incomes)", [], |row| {
+            row.get(0)
+        })?;
+
+    if incomes_empty {
+        // ID lama dipertahankan agar referensi dari luar tetap valid.
+        conn.execute(
+            "INSERT INTO incomes (id, description, amount, date, category_id)
+             SELECT id, description, amount, date, category_id FROM income",
+            [],
+        )?;
+    } else {
+        // Kedua tabel berisi data: ID bisa bentrok, jadi baris lama diberi ID baru.
+        conn.execute(
+            "INSERT INTO incomes (description, amount, date, category_id)
+             SELECT description, amount, date, category_id FROM income ORDER BY id",
+            [],
+        )?;
+    }
+
+    conn.execute_batch("DROP TABLE income;")
+}
@@ -0,0 +1,65 @@
+//! Mengubah kolom uang dari `REAL` menjadi `INTEGER` dalam satuan minor (sen)
+//! dan menambahkan kolom `currency`.
+//!
+//! SQLite tidak bisa mengubah tipe kolom secara langsung, jadi setiap tabel dibangun
+//! ulang: buat tabel baru, salin data dengan konversi `ROUND(nilai * 100)`, hapus tabel
+//! lama, lalu ganti nama. Semua data lama diasumsikan dalam Rupiah (IDR, 2 digit minor),
+//! sehingga nilai dengan paling banyak dua angka desimal dikonversi tanpa kehilangan presisi.
+
+use rusqlite::{Connection, Result};
+
+const REBUILD_ACCOUNTS_TABLE: &str = r#"
+    CREATE TABLE accounts_new (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        description TEXT,
+        balance INTEGER NOT NULL DEFAULT 0,
+        currency TEXT NOT NULL DEFAULT 'IDR'
+    );
+    INSERT INTO accounts_new (id, name, description, balance, currency)
+        SELECT id, name, description, CAST(ROUND(balance * 100) AS INTEGER), 'IDR'
+        FROM accounts;
+    DROP TABLE accounts;
+    ALTER TABLE accounts_new RENAME TO accounts;
+"#;
+
+const REBUILD_INCOMES_TABLE: &str = r#"
+    CREATE TABLE incomes_new (
+        id INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        currency TEXT NOT NULL DEFAULT 'IDR',
+        date TEXT NOT NULL,
+        category_id INTEGER NOT NULL,
+        FOREIGN KEY (category_id) REFERENCES categories (id)
+    );
+    INSERT INTO incomes_new (id, description, amount, currency, date, category_id)
+        SELECT id, description, CAST(ROUND(amount * 100) AS INTEGER), 'IDR', date, category_id
+        FROM incomes;
+    DROP TABLE incomes;
+    ALTER TABLE incomes_new RENAME TO incomes;
+"#;
+
+const REBUILD_EXPENSES_TABLE: &str = r#"
+    CREATE TABLE expenses_new (
+        id INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        currency TEXT NOT NULL DEFAULT 'IDR',
+        date TEXT NOT NULL,
+        category_id INTEGER NOT NULL,
+        FOREIGN KEY (category_id) REFERENCES categories (id)
+    );
+    INSERT INTO expenses_new (id, description, amount, currency, date, category_id)
+        SELECT id, description, CAST(ROUND(amount * 100) AS INTEGER), 'IDR', date, category_id
+        FROM expenses;
+    DROP TABLE expenses;
+    ALTER TABLE expenses_new RENAME TO expenses;
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(REBUILD_ACCOUNTS_TABLE)?;
+    conn.execute_batch(REBUILD_INCOMES_TABLE)?;
+    conn.execute_batch(REBUILD_EXPENSES_TABLE)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! Tabel kurs mata uang bertanggal untuk konversi ke mata uang pelaporan.
+//! Satu tanggal hanya boleh memiliki satu kurs untuk setiap pasangan mata uang.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_EXCHANGE_RATES_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS exchange_rates (
+        id INTEGER PRIMARY KEY,
+        date TEXT NOT NULL,
+        base_currency TEXT NOT NULL,
+        quote_currency TEXT NOT NULL,
+        rate REAL NOT NULL CHECK(rate > 0),
+        source INTEGER NOT NULL CHECK(source IN (1, 2)),
+        UNIQUE (date, base_currency, quote_currency)
+    );
+    CREATE INDEX IF NOT EXISTS idx_exchange_rates_pair_date
+        ON exchange_rates (base_currency, quote_currency, date);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_EXCHANGE_RATES_TABLE)
+}
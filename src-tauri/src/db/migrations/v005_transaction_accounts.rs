@@ -0,0 +1,22 @@
+//! Menghubungkan pemasukan dan pengeluaran ke akun.
+//!
+//! Akun mendapat kolom `opening_balance` agar saldo bisa dihitung ulang dari riwayat
+//! transaksi. Karena belum ada transaksi yang terhubung ke akun, saldo pembuka akun
+//! lama sama dengan saldonya saat ini. Transaksi lama dibiarkan tanpa akun (`NULL`).
+
+use rusqlite::{Connection, Result};
+
+const LINK_TRANSACTIONS_TO_ACCOUNTS: &str = r#"
+    ALTER TABLE accounts ADD COLUMN opening_balance INTEGER NOT NULL DEFAULT 0;
+    UPDATE accounts SET opening_balance = balance;
+
+    ALTER TABLE expenses ADD COLUMN account_id INTEGER REFERENCES accounts (id);
+    ALTER TABLE incomes ADD COLUMN account_id INTEGER REFERENCES accounts (id);
+
+    CREATE INDEX IF NOT EXISTS idx_expenses_account_id ON expenses (account_id);
+    CREATE INDEX IF NOT EXISTS idx_incomes_account_id ON incomes (account_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(LINK_TRANSACTIONS_TO_ACCOUNTS)
+}
@@ -0,0 +1,32 @@
+//! Tabel transfer antar akun.
+//!
+//! Akun asal didebit sebesar `amount + fee` dalam mata uangnya sendiri, akun tujuan
+//! dikredit sebesar `received_amount` dalam mata uangnya. Untuk transfer lintas mata uang,
+//! kurs yang dipakai disimpan di kolom `rate`.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_TRANSFERS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS transfers (
+        id INTEGER PRIMARY KEY,
+        description TEXT,
+        from_account_id INTEGER NOT NULL,
+        to_account_id INTEGER NOT NULL,
+        amount INTEGER NOT NULL CHECK(amount > 0),
+        currency TEXT NOT NULL,
+        fee INTEGER NOT NULL DEFAULT 0 CHECK(fee >= 0),
+        rate REAL NOT NULL DEFAULT 1 CHECK(rate > 0),
+        received_amount INTEGER NOT NULL,
+        received_currency TEXT NOT NULL,
+        date TEXT NOT NULL,
+        CHECK(from_account_id <> to_account_id),
+        FOREIGN KEY (from_account_id) REFERENCES accounts (id),
+        FOREIGN KEY (to_account_id) REFERENCES accounts (id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_transfers_from_account_id ON transfers (from_account_id);
+    CREATE INDEX IF NOT EXISTS idx_transfers_to_account_id ON transfers (to_account_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_TRANSFERS_TABLE)
+}
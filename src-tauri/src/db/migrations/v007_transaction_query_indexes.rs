@@ -0,0 +1,14 @@
+//! Indeks untuk query transaksi yang difilter dan diurutkan (tanggal dan kategori).
+
+use rusqlite::{Connection, Result};
+
+const CREATE_INDEXES: &str = r#"
+    CREATE INDEX IF NOT EXISTS idx_expenses_date ON expenses (date);
+    CREATE INDEX IF NOT EXISTS idx_expenses_category_id ON expenses (category_id);
+    CREATE INDEX IF NOT EXISTS idx_incomes_date ON incomes (date);
+    CREATE INDEX IF NOT EXISTS idx_incomes_category_id ON incomes (category_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_INDEXES)
+}
@@ -0,0 +1,31 @@
+//! View `ledger_entries`: pemasukan, pengeluaran, dan transfer dalam satu aliran.
+//!
+//! Setiap baris adalah satu mutasi pada satu akun, dengan `delta` bertanda dalam satuan
+//! minor mata uang akun. Transfer menghasilkan dua baris: keluar dari akun asal
+//! (`amount + fee`) dan masuk ke akun tujuan (`received_amount`).
+//! `entry_type`: 1 = pemasukan, 2 = pengeluaran, 3 = transfer keluar, 4 = transfer masuk.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_LEDGER_VIEW: &str = r#"
+    CREATE VIEW IF NOT EXISTS ledger_entries AS
+        SELECT 1 AS entry_type, id AS entry_id, account_id, NULL AS counterparty_account_id,
+               category_id, description, amount AS delta, currency, date
+        FROM incomes
+        UNION ALL
+        SELECT 2, id, account_id, NULL, category_id, description, -amount, currency, date
+        FROM expenses
+        UNION ALL
+        SELECT 3, id, from_account_id, to_account_id, NULL, description, -(amount + fee),
+               currency, date
+        FROM transfers
+        UNION ALL
+        SELECT 4, id, to_account_id, from_account_id, NULL, description, received_amount,
+               received_currency, date
+        FROM transfers;
+    CREATE INDEX IF NOT EXISTS idx_transfers_date ON transfers (date);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_LEDGER_VIEW)
+}
@@ -0,0 +1,51 @@
+//! Indeks pencarian teks penuh (FTS5) untuk deskripsi pemasukan dan pengeluaran.
+//!
+//! `transaction_search` berisi satu baris per transaksi dengan `entry_type` yang sama
+//! seperti view `ledger_entries` (1 = pemasukan, 2 = pengeluaran). Isinya dijaga oleh
+//! trigger pada `incomes` dan `expenses`, sehingga aplikasi tidak perlu memperbaruinya.
+//! Tokenizer `unicode61` dengan `remove_diacritics` membuat pencarian tidak peka huruf
+//! besar/kecil maupun tanda diakritik.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_SEARCH_INDEX: &str = r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS transaction_search USING fts5(
+        description,
+        entry_type UNINDEXED,
+        entry_id UNINDEXED,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+
+    INSERT INTO transaction_search (description, entry_type, entry_id)
+        SELECT description, 1, id FROM incomes;
+    INSERT INTO transaction_search (description, entry_type, entry_id)
+        SELECT description, 2, id FROM expenses;
+
+    CREATE TRIGGER IF NOT EXISTS incomes_search_insert AFTER INSERT ON incomes BEGIN
+        INSERT INTO transaction_search (description, entry_type, entry_id)
+        VALUES (new.description, 1, new.id);
+    END;
+    CREATE TRIGGER IF NOT EXISTS incomes_search_update AFTER UPDATE OF description ON incomes BEGIN
+        UPDATE transaction_search SET description = new.description
+        WHERE entry_type = 1 AND entry_id = old.id;
+    END;
+    CREATE TRIGGER IF NOT EXISTS incomes_search_delete AFTER DELETE ON incomes BEGIN
+        DELETE FROM transaction_search WHERE entry_type = 1 AND entry_id = old.id;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS expenses_search_insert AFTER INSERT ON expenses BEGIN
+        INSERT INTO transaction_search (description, entry_type, entry_id)
+        VALUES (new.description, 2, new.id);
+    END;
+    CREATE TRIGGER IF NOT EXISTS expenses_search_update AFTER UPDATE OF description ON expenses BEGIN
+        UPDATE transaction_search SET description = new.description
+        WHERE entry_type = 2 AND entry_id = old.id;
+    END;
+    CREATE TRIGGER IF NOT EXISTS expenses_search_delete AFTER DELETE ON expenses BEGIN
+        DELETE FROM transaction_search WHERE entry_type = 2 AND entry_id = old.id;
+    END;
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_SEARCH_INDEX)
+}
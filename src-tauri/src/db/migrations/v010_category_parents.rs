@@ -0,0 +1,13 @@
+//! Kategori bertingkat: `parent_id` opsional yang menunjuk ke kategori induk.
+//! Kategori lama tetap menjadi kategori akar (`parent_id` NULL).
+
+use rusqlite::{Connection, Result};
+
+const ADD_CATEGORY_PARENT: &str = r#"
+    ALTER TABLE categories ADD COLUMN parent_id INTEGER REFERENCES categories (id);
+    CREATE INDEX IF NOT EXISTS idx_categories_parent_id ON categories (parent_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ADD_CATEGORY_PARENT)
+}
@@ -0,0 +1,24 @@
+//! Ikon dan warna kategori, serta tabel `seed_history` yang mencatat data awal apa saja
+//! yang sudah dipasang. Database lama yang sudah berisi kategori langsung ditandai
+//! sudah di-seed agar kategori bawaan tidak pernah ditambahkan ke data milik pengguna.
+
+use rusqlite::{Connection, Result};
+
+const ADD_CATEGORY_DEFAULTS: &str = r#"
+    ALTER TABLE categories ADD COLUMN icon TEXT;
+    ALTER TABLE categories ADD COLUMN color TEXT;
+
+    CREATE TABLE IF NOT EXISTS seed_history (
+        name TEXT PRIMARY KEY,
+        locale TEXT,
+        applied_at TEXT NOT NULL
+    );
+
+    INSERT INTO seed_history (name, locale, applied_at)
+    SELECT 'default_categories', NULL, datetime('now')
+    WHERE EXISTS (SELECT 1 FROM categories);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ADD_CATEGORY_DEFAULTS)
+}
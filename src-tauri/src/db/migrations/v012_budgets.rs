@@ -0,0 +1,22 @@
+//! Anggaran per kategori pengeluaran. Batas disimpan dalam satuan minor `currency`.
+//! Anggaran ikut terhapus bersama kategorinya.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_BUDGETS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS budgets (
+        id INTEGER PRIMARY KEY,
+        category_id INTEGER NOT NULL,
+        period INTEGER NOT NULL CHECK(period IN (1, 2, 3)),
+        amount INTEGER NOT NULL CHECK(amount > 0),
+        currency TEXT NOT NULL,
+        rollover INTEGER NOT NULL DEFAULT 0 CHECK(rollover IN (0, 1)),
+        start_date TEXT NOT NULL,
+        UNIQUE (category_id, period, currency),
+        FOREIGN KEY (category_id) REFERENCES categories (id) ON DELETE CASCADE
+    );
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_BUDGETS_TABLE)
+}
@@ -0,0 +1,46 @@
+//! Jadwal transaksi berulang (`recurring_rules`) dan catatan per kejadiannya
+//! (`recurring_occurrences`).
+//!
+//! Setiap kejadian diidentifikasi oleh aturan dan tanggal jadwalnya. Baris kejadian
+//! mencatat transaksi yang sudah dibuat, kejadian yang dilewati, atau perubahan untuk satu
+//! kejadian yang belum dibuat, sehingga pembuatan transaksi tidak pernah berjalan dua kali.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_RECURRING_TABLES: &str = r#"
+    CREATE TABLE IF NOT EXISTS recurring_rules (
+        id INTEGER PRIMARY KEY,
+        transaction_type INTEGER NOT NULL CHECK(transaction_type IN (1, 2)),
+        description TEXT NOT NULL,
+        amount INTEGER NOT NULL CHECK(amount > 0),
+        currency TEXT NOT NULL,
+        category_id INTEGER NOT NULL,
+        account_id INTEGER NOT NULL,
+        frequency INTEGER NOT NULL CHECK(frequency IN (1, 2, 3, 4)),
+        interval INTEGER NOT NULL DEFAULT 1 CHECK(interval > 0),
+        weekday INTEGER CHECK(weekday BETWEEN 0 AND 6),
+        week_of_month INTEGER CHECK(week_of_month IN (-1, 1, 2, 3, 4)),
+        start_date TEXT NOT NULL,
+        end_date TEXT,
+        max_occurrences INTEGER CHECK(max_occurrences > 0),
+        FOREIGN KEY (category_id) REFERENCES categories (id),
+        FOREIGN KEY (account_id) REFERENCES accounts (id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_recurring_rules_category_id ON recurring_rules (category_id);
+
+    CREATE TABLE IF NOT EXISTS recurring_occurrences (
+        rule_id INTEGER NOT NULL,
+        scheduled_date TEXT NOT NULL,
+        status INTEGER NOT NULL CHECK(status IN (1, 2, 3)),
+        description TEXT,
+        amount INTEGER CHECK(amount > 0),
+        date TEXT,
+        entry_id INTEGER,
+        PRIMARY KEY (rule_id, scheduled_date),
+        FOREIGN KEY (rule_id) REFERENCES recurring_rules (id) ON DELETE CASCADE
+    );
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_RECURRING_TABLES)
+}
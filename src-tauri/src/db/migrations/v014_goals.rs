@@ -0,0 +1,34 @@
+//! Target tabungan (`goals`) beserta catatan setorannya (`goal_contributions`).
+//!
+//! Setoran bisa terhubung ke transfer yang memindahkan uangnya ke akun tabungan target.
+//! Bila transfer itu dihapus, setorannya tetap tercatat tanpa tautan.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_GOALS_TABLES: &str = r#"
+    CREATE TABLE IF NOT EXISTS goals (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        target_amount INTEGER NOT NULL CHECK(target_amount > 0),
+        currency TEXT NOT NULL,
+        target_date TEXT,
+        account_id INTEGER,
+        FOREIGN KEY (account_id) REFERENCES accounts (id) ON DELETE SET NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS goal_contributions (
+        id INTEGER PRIMARY KEY,
+        goal_id INTEGER NOT NULL,
+        amount INTEGER NOT NULL CHECK(amount <> 0),
+        date TEXT NOT NULL,
+        note TEXT,
+        transfer_id INTEGER,
+        FOREIGN KEY (goal_id) REFERENCES goals (id) ON DELETE CASCADE,
+        FOREIGN KEY (transfer_id) REFERENCES transfers (id) ON DELETE SET NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_goal_contributions_goal_id ON goal_contributions (goal_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_GOALS_TABLES)
+}
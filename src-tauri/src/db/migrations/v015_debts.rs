@@ -0,0 +1,39 @@
+//! Utang dan piutang (`debts`) beserta pembayarannya (`debt_payments`).
+//!
+//! Setiap pembayaran adalah pengeluaran (melunasi utang) atau pemasukan (menerima pelunasan
+//! piutang) biasa; `debt_payments` hanya menautkannya ke utang/piutang yang bersangkutan.
+//! Bila transaksinya dihapus, tautannya ikut terhapus.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_DEBTS_TABLES: &str = r#"
+    CREATE TABLE IF NOT EXISTS debts (
+        id INTEGER PRIMARY KEY,
+        counterparty TEXT NOT NULL,
+        direction INTEGER NOT NULL CHECK(direction IN (1, 2)),
+        principal INTEGER NOT NULL CHECK(principal > 0),
+        currency TEXT NOT NULL,
+        start_date TEXT NOT NULL,
+        due_date TEXT,
+        interest_rate REAL CHECK(interest_rate >= 0),
+        installment_count INTEGER CHECK(installment_count > 0),
+        description TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_debts_counterparty ON debts (counterparty COLLATE NOCASE);
+
+    CREATE TABLE IF NOT EXISTS debt_payments (
+        id INTEGER PRIMARY KEY,
+        debt_id INTEGER NOT NULL,
+        expense_id INTEGER UNIQUE,
+        income_id INTEGER UNIQUE,
+        CHECK((expense_id IS NULL) <> (income_id IS NULL)),
+        FOREIGN KEY (debt_id) REFERENCES debts (id) ON DELETE CASCADE,
+        FOREIGN KEY (expense_id) REFERENCES expenses (id) ON DELETE CASCADE,
+        FOREIGN KEY (income_id) REFERENCES incomes (id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_debt_payments_debt_id ON debt_payments (debt_id);
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_DEBTS_TABLES)
+}
@@ -0,0 +1,154 @@
+//! Tabel angsuran (`debt_installments`) untuk utang bercicilan, metode bunga (flat atau
+//! anuitas), dan kategori pengeluaran cicilan pada `debts`.
+//!
+//! Utang bercicilan yang sudah ada dihitung dengan bunga flat seperti sebelumnya, lalu
+//! tabel angsurannya disimpan.
+
+use chrono::{Months, NaiveDate};
+use rusqlite::{params, Connection, Result};
+
+const ALTER_DEBTS_AND_CREATE_INSTALLMENTS: &str = r#"
+    ALTER TABLE debts ADD COLUMN interest_method INTEGER NOT NULL DEFAULT 1
+        CHECK(interest_method IN (1, 2));
+    ALTER TABLE debts ADD COLUMN category_id INTEGER
+        REFERENCES categories (id) ON DELETE SET NULL;
+
+    CREATE TABLE IF NOT EXISTS debt_installments (
+        debt_id INTEGER NOT NULL,
+        number INTEGER NOT NULL CHECK(number > 0),
+        due_date TEXT NOT NULL,
+        principal INTEGER NOT NULL,
+        interest INTEGER NOT NULL,
+        remaining_principal INTEGER NOT NULL,
+        PRIMARY KEY (debt_id, number),
+        FOREIGN KEY (debt_id) REFERENCES debts (id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_debt_installments_due_date ON debt_installments (due_date);
+"#;
+
+/// Satu baris angsuran; nominal dalam satuan terkecil mata uang utang.
+struct Installment {
+    number: u32,
+    due_date: NaiveDate,
+    principal: i64,
+    interest: i64,
+    remaining_principal: i64,
+}
+
+/// Tabel angsuran bunga flat seperti yang dihitung aplikasi saat migrasi ini dibuat. Sengaja
+/// disalin di sini agar hasil migrasi tidak ikut berubah bila perhitungan aplikasi berubah.
+fn flat_schedule(
+    principal: i64,
+    annual_rate: f64,
+    installment_count: u32,
+    start_date: NaiveDate,
+) -> Vec<Installment> {
+    let principal = i128::from(principal);
+    let count = i128::from(installment_count);
+    let monthly_rate = annual_rate / 100.0 / 12.0;
+    let total_interest = (principal as f64 * monthly_rate * count as f64).round() as i128;
+    // Selisih pembulatan masuk ke cicilan terakhir.
+    let share = |total: i128, number: u32| {
+        if number == installment_count {
+            total - total / count * (count - 1)
+        } else {
+            total / count
+        }
+    };
+
+    let mut remaining = principal;
+    (1..=installment_count)
+        .map(|number| {
+            let principal_part = share(principal, number);
+            remaining -= principal_part;
+            Installment {
+                number,
+                due_date: start_date
+                    .checked_add_months(Months::new(number))
+                    .unwrap_or(NaiveDate::MAX),
+                principal: principal_part as i64,
+                interest: share(total_interest, number) as i64,
+                remaining_principal: remaining as i64,
+            }
+        })
+        .collect()
+}
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ALTER_DEBTS_AND_CREATE_INSTALLMENTS)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, principal, start_date, interest_rate, installment_count
+         FROM debts WHERE installment_count IS NOT NULL",
+    )?;
+    let debts = stmt
+        .query_map([], |row| {
+            let start_date: String = row.get(2)?;
+            let start_date = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d").map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    2,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?;
+            Ok((
+                row.get::<_, i64>(0)?,
+                flat_schedule(
+                    row.get(1)?,
+                    row.get::<_, Option<f64>>(3)?.unwrap_or(0.0),
+                    row.get(4)?,
+                    start_date,
+                ),
+            ))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    for (debt_id, schedule) in debts {
+        for installment in schedule {
+            conn.execute(
+                "INSERT INTO debt_installments
+                     (debt_id, number, due_date, principal, interest, remaining_principal)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    debt_id,
+                    installment.number,
+                    installment.due_date.format("%Y-%m-%d").to_string(),
+                    installment.principal,
+                    installment.interest,
+                    installment.remaining_principal
+                ],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_schedule_puts_rounding_in_last_installment() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+
+        // Pokok 1.000,00 dan bunga 10% setahun selama 3 bulan: bunga total 25,00.
+        let schedule = flat_schedule(100_000, 10.0, 3, start);
+
+        let rows: Vec<_> = schedule
+            .iter()
+            .map(|i| (i.number, i.principal, i.interest, i.remaining_principal))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (1, 33_333, 833, 66_667),
+                (2, 33_333, 833, 33_334),
+                (3, 33_334, 834, 0)
+            ]
+        );
+        assert_eq!(
+            schedule[0].due_date,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+    }
+}
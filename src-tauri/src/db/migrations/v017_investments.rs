@@ -0,0 +1,75 @@
+//! Akun investasi: jenis akun pada `accounts`, instrumen (`instruments`), transaksi beli/jual/
+//! dividen (`investment_transactions`), dan riwayat harga (`instrument_prices`).
+//!
+//! Transaksi investasi menyelesaikan kasnya pada saldo akun investasi: beli mengurangi
+//! `amount + fee`, jual dan dividen menambah `amount - fee`. View `ledger_entries` dibuat
+//! ulang dengan `entry_type` 5 untuk mutasi kas tersebut agar saldo berjalan tetap cocok.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_INVESTMENT_TABLES: &str = r#"
+    ALTER TABLE accounts ADD COLUMN account_type INTEGER NOT NULL DEFAULT 1
+        CHECK(account_type IN (1, 2));
+
+    CREATE TABLE IF NOT EXISTS instruments (
+        id INTEGER PRIMARY KEY,
+        symbol TEXT NOT NULL UNIQUE COLLATE NOCASE,
+        name TEXT NOT NULL,
+        kind INTEGER NOT NULL CHECK(kind IN (1, 2, 3, 4, 5)),
+        currency TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS investment_transactions (
+        id INTEGER PRIMARY KEY,
+        account_id INTEGER NOT NULL,
+        instrument_id INTEGER NOT NULL,
+        kind INTEGER NOT NULL CHECK(kind IN (1, 2, 3)),
+        units REAL NOT NULL CHECK(units >= 0),
+        amount INTEGER NOT NULL CHECK(amount > 0),
+        fee INTEGER NOT NULL DEFAULT 0 CHECK(fee >= 0),
+        currency TEXT NOT NULL,
+        date TEXT NOT NULL,
+        note TEXT,
+        FOREIGN KEY (account_id) REFERENCES accounts (id),
+        FOREIGN KEY (instrument_id) REFERENCES instruments (id)
+    );
+    CREATE INDEX IF NOT EXISTS idx_investment_transactions_account
+        ON investment_transactions (account_id, instrument_id, date);
+
+    CREATE TABLE IF NOT EXISTS instrument_prices (
+        id INTEGER PRIMARY KEY,
+        instrument_id INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        price REAL NOT NULL CHECK(price > 0),
+        source INTEGER NOT NULL CHECK(source IN (1, 2)),
+        UNIQUE (instrument_id, date),
+        FOREIGN KEY (instrument_id) REFERENCES instruments (id) ON DELETE CASCADE
+    );
+
+    DROP VIEW IF EXISTS ledger_entries;
+    CREATE VIEW ledger_entries AS
+        SELECT 1 AS entry_type, id AS entry_id, account_id, NULL AS counterparty_account_id,
+               category_id, description, amount AS delta, currency, date
+        FROM incomes
+        UNION ALL
+        SELECT 2, id, account_id, NULL, category_id, description, -amount, currency, date
+        FROM expenses
+        UNION ALL
+        SELECT 3, id, from_account_id, to_account_id, NULL, description, -(amount + fee),
+               currency, date
+        FROM transfers
+        UNION ALL
+        SELECT 4, id, to_account_id, from_account_id, NULL, description, received_amount,
+               received_currency, date
+        FROM transfers
+        UNION ALL
+        SELECT 5, t.id, t.account_id, NULL, NULL, COALESCE(t.note, i.name),
+               CASE t.kind WHEN 1 THEN -(t.amount + t.fee) ELSE t.amount - t.fee END,
+               t.currency, t.date
+        FROM investment_transactions t
+        JOIN instruments i ON i.id = t.instrument_id;
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_INVESTMENT_TABLES)
+}
@@ -0,0 +1,25 @@
+//! Cuplikan kekayaan bersih harian (`net_worth_snapshots`).
+//!
+//! Setiap baris menyimpan rincian aset dan kewajiban dalam satu mata uang tanpa konversi,
+//! sehingga riwayatnya bisa ditampilkan dalam mata uang pelaporan apa pun. Cuplikan hari
+//! yang sama diganti seluruhnya setiap kali dicatat ulang.
+
+use rusqlite::{Connection, Result};
+
+const CREATE_NET_WORTH_SNAPSHOTS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS net_worth_snapshots (
+        id INTEGER PRIMARY KEY,
+        date TEXT NOT NULL,
+        currency TEXT NOT NULL,
+        cash INTEGER NOT NULL,
+        investments INTEGER NOT NULL,
+        receivables INTEGER NOT NULL CHECK(receivables >= 0),
+        debts INTEGER NOT NULL CHECK(debts >= 0),
+        overdrafts INTEGER NOT NULL CHECK(overdrafts >= 0),
+        UNIQUE (date, currency)
+    );
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(CREATE_NET_WORTH_SNAPSHOTS_TABLE)
+}
@@ -0,0 +1,57 @@
+//! Memperbaiki pemasukan dan pengeluaran yang kategorinya sudah terhapus.
+//!
+//! Sebelum sistem migrasi ada, foreign key tidak pernah diaktifkan dan kategori dihapus
+//! dengan `DELETE` biasa, sehingga database lama bisa berisi transaksi dengan `category_id`
+//! yatim. Tanpa perbaikan ini pemeriksaan foreign key di akhir migrasi selalu gagal dan
+//! aplikasi tidak bisa dibuka. Transaksi yatim dipindahkan ke kategori "Tanpa Kategori"
+//! sesuai jenisnya; kategori itu hanya dibuat bila memang ada transaksi yang perlu dipindah.
+
+use rusqlite::{params, Connection, Result};
+
+/// Nama kategori penampung transaksi yang kategorinya hilang.
+const UNCATEGORIZED: &str = "Tanpa Kategori";
+
+pub fn up(conn: &Connection) -> Result<()> {
+    // Baris yatim dikumpulkan lebih dulu: kategori baru bisa mendapat ID milik kategori
+    // yang sudah terhapus, sehingga baris yatim lain tidak lagi terdeteksi setelahnya.
+    // (tabel, category_type): 1 untuk Income, 2 untuk Expense.
+    let mut orphans = Vec::new();
+    for (table, category_type) in [("incomes", 1), ("expenses", 2)] {
+        let ids = conn
+            .prepare(&format!(
+                "SELECT id FROM {} WHERE category_id NOT IN (SELECT id FROM categories)",
+                table
+            ))?
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<i64>>>()?;
+        orphans.push((table, category_type, ids));
+    }
+
+    for (table, category_type, ids) in orphans {
+        if ids.is_empty() {
+            continue;
+        }
+
+        conn.execute(
+            "INSERT INTO categories (name, category_type)
+             SELECT ?1, ?2
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM categories WHERE name = ?1 AND category_type = ?2
+             )",
+            params![UNCATEGORIZED, category_type],
+        )?;
+        let category_id: i64 = conn.query_row(
+            "SELECT MIN(id) FROM categories WHERE name = ?1 AND category_type = ?2",
+            params![UNCATEGORIZED, category_type],
+            |row| row.get(0),
+        )?;
+        let mut stmt = conn.prepare(&format!(
+            "UPDATE {} SET category_id = ?1 WHERE id = ?2",
+            table
+        ))?;
+        for id in ids {
+            stmt.execute(params![category_id, id])?;
+        }
+    }
+    Ok(())
+}
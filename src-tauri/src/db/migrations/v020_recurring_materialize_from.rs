@@ -0,0 +1,15 @@
+//! Batas awal pembuatan otomatis (`materialize_from`) pada aturan transaksi berulang.
+//!
+//! Saat jadwal aturan diubah, tanggal kejadian lama dan baru tidak lagi sama sehingga
+//! catatan per tanggal jadwal tidak cukup untuk mencegah transaksi ganda. Kejadian yang
+//! dijadwalkan sebelum tanggal ini tidak pernah dibuat. Aturan lama tetap NULL (tanpa batas).
+
+use rusqlite::{Connection, Result};
+
+const ADD_MATERIALIZE_FROM: &str = r#"
+    ALTER TABLE recurring_rules ADD COLUMN materialize_from TEXT;
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ADD_MATERIALIZE_FROM)
+}
@@ -0,0 +1,16 @@
+//! Penanda tautan pembayaran cicilan otomatis (`debt_payments.auto_linked`).
+//!
+//! Tautan yang dibuat dari pencocokan cicilan dinilai ulang setiap kali transaksinya
+//! diubah, sedangkan pembayaran yang dicatat langsung pada utang/piutang tetap tertaut.
+//! Tautan lama tidak bisa dibedakan sehingga diperlakukan sebagai pembayaran langsung.
+
+use rusqlite::{Connection, Result};
+
+const ADD_AUTO_LINKED: &str = r#"
+    ALTER TABLE debt_payments ADD COLUMN auto_linked INTEGER NOT NULL DEFAULT 0
+        CHECK(auto_linked IN (0, 1));
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ADD_AUTO_LINKED)
+}
@@ -0,0 +1,45 @@
+//! Akun pencairan utang/piutang (`debts.account_id`).
+//!
+//! Utang yang diterima menambah saldo akun sebesar pokoknya, piutang yang dipinjamkan
+//! menguranginya. Mutasi ini bukan pemasukan atau pengeluaran, sehingga view
+//! `ledger_entries` dibuat ulang dengan `entry_type` 6 untuknya agar saldo berjalan tetap
+//! cocok tanpa ikut masuk laporan.
+
+use rusqlite::{Connection, Result};
+
+const ADD_DEBT_ACCOUNT: &str = r#"
+    ALTER TABLE debts ADD COLUMN account_id INTEGER REFERENCES accounts (id);
+
+    DROP VIEW IF EXISTS ledger_entries;
+    CREATE VIEW ledger_entries AS
+        SELECT 1 AS entry_type, id AS entry_id, account_id, NULL AS counterparty_account_id,
+               category_id, description, amount AS delta, currency, date
+        FROM incomes
+        UNION ALL
+        SELECT 2, id, account_id, NULL, category_id, description, -amount, currency, date
+        FROM expenses
+        UNION ALL
+        SELECT 3, id, from_account_id, to_account_id, NULL, description, -(amount + fee),
+               currency, date
+        FROM transfers
+        UNION ALL
+        SELECT 4, id, to_account_id, from_account_id, NULL, description, received_amount,
+               received_currency, date
+        FROM transfers
+        UNION ALL
+        SELECT 5, t.id, t.account_id, NULL, NULL, COALESCE(t.note, i.name),
+               CASE t.kind WHEN 1 THEN -(t.amount + t.fee) ELSE t.amount - t.fee END,
+               t.currency, t.date
+        FROM investment_transactions t
+        JOIN instruments i ON i.id = t.instrument_id
+        UNION ALL
+        SELECT 6, id, account_id, NULL, NULL, counterparty,
+               CASE direction WHEN 1 THEN principal ELSE -principal END,
+               currency, start_date || ' 00:00:00'
+        FROM debts
+        WHERE account_id IS NOT NULL;
+"#;
+
+pub fn up(conn: &Connection) -> Result<()> {
+    conn.execute_batch(ADD_DEBT_ACCOUNT)
+}
@@ -0,0 +1,281 @@
+use crate::error::AppError;
+use rusqlite::{Connection, OpenFlags};
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+pub mod integrity;
+pub mod location;
+pub mod migrations;
+pub mod seeds;
+
+/// Jumlah koneksi baca yang dibuka untuk database file.
+const READER_COUNT: usize = 4;
+
+/// Berapa lama sebuah koneksi menunggu kunci database dilepas sebelum menyerah
+/// dengan `AppError::DatabaseBusy`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Handle database yang dibagikan ke seluruh aplikasi lewat managed state Tauri
+/// (`app.manage(database)`) dan diteruskan ke repository, bukan melalui variabel global.
+/// Dengan begitu repository dan service bisa diuji memakai database in-memory.
+///
+/// Database file dibuka dalam mode WAL dengan satu koneksi tulis dan beberapa koneksi
+/// baca, sehingga query laporan yang lama tidak menahan penyimpanan transaksi baru.
+pub struct Database {
+    writer: Mutex<Connection>,
+    /// Kosong untuk database in-memory; pembacaan lalu memakai koneksi tulis.
+    readers: Option<ReaderPool>,
+}
+
+impl Database {
+    /// Membuka database di `path` (direktorinya dibuat bila perlu), menjalankan semua
+    /// migrasi yang belum diterapkan, lalu memverifikasi bahwa skema sesuai dengan
+    /// kebutuhan repository. Dipanggil sekali saat aplikasi dimulai.
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = Connection::open(path)?;
+        configure(&writer)?;
+        writer
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        writer.pragma_update(None, "synchronous", "NORMAL")?;
+        prepare_schema(&mut writer)?;
+
+        // Koneksi baca dibuka setelah migrasi agar tidak pernah melihat skema lama.
+        let readers = (0..READER_COUNT)
+            .map(|_| open_reader(path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Database {
+            writer: Mutex::new(writer),
+            readers: Some(ReaderPool::new(readers)),
+        })
+    }
+
+    /// Membuka database in-memory yang sudah dimigrasi, terutama untuk pengujian.
+    pub fn open_in_memory() -> Result<Self, AppError> {
+        let mut writer = Connection::open_in_memory()?;
+        configure(&writer)?;
+        prepare_schema(&mut writer)?;
+
+        Ok(Database {
+            writer: Mutex::new(writer),
+            readers: None,
+        })
+    }
+
+    /// Memasang data awal (kategori bawaan dalam bahasa `locale`) pada database yang
+    /// belum pernah di-seed. Dipanggil sekali saat startup, setelah `open`.
+    pub fn seed_defaults(&self, locale: &str) -> Result<(), AppError> {
+        self.write(|conn| seeds::seed_default_categories(conn, locale))?;
+        Ok(())
+    }
+
+    /// Menjalankan `f` dengan akses eksklusif ke koneksi tulis. Semua perubahan data
+    /// harus lewat sini; SQLite hanya mengizinkan satu penulis dalam satu waktu.
+    pub fn write<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let mut conn = self.lock_writer();
+        f(&mut conn)
+    }
+
+    /// Menjalankan `f` dengan salah satu koneksi baca. Pembacaan berjalan paralel dengan
+    /// pembacaan lain maupun dengan penulisan, dan selalu melihat data yang sudah di-commit.
+    pub fn read<T>(
+        &self,
+        f: impl FnOnce(&Connection) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        match &self.readers {
+            Some(pool) => f(&pool.acquire()),
+            None => f(&self.lock_writer()),
+        }
+    }
+
+    /// Mengunci koneksi tulis. Jika kunci ter-poison karena sebuah operasi panic,
+    /// koneksinya tetap dipakai kembali setelah transaksi yang tertinggal dibatalkan,
+    /// sehingga satu panic tidak membuat database tidak bisa dipakai sampai aplikasi ditutup.
+    fn lock_writer(&self) -> MutexGuard<'_, Connection> {
+        self.writer.lock().unwrap_or_else(|poisoned| {
+            let conn = poisoned.into_inner();
+            if !conn.is_autocommit() {
+                // Gagal rollback berarti memang tidak ada transaksi yang bisa dibatalkan.
+                let _ = conn.execute_batch("ROLLBACK");
+            }
+            conn
+        })
+    }
+}
+
+/// Pengaturan yang berlaku untuk setiap koneksi, baik tulis maupun baca.
+fn configure(conn: &Connection) -> Result<(), AppError> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+    Ok(())
+}
+
+fn prepare_schema(conn: &mut Connection) -> Result<(), AppError> {
+    migrations::run(conn)?;
+    integrity::verify_schema(conn)?;
+    Ok(())
+}
+
+fn open_reader(path: &Path) -> Result<Connection, AppError> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    configure(&conn)?;
+    Ok(conn)
+}
+
+/// Kumpulan koneksi baca. Pemanggil menunggu bila semua koneksi sedang dipakai.
+struct ReaderPool {
+    idle: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+impl ReaderPool {
+    fn new(connections: Vec<Connection>) -> Self {
+        ReaderPool {
+            idle: Mutex::new(connections),
+            available: Condvar::new(),
+        }
+    }
+
+    fn acquire(&self) -> PooledConnection<'_> {
+        // Daftar koneksi menganggur tetap valid walau kuncinya ter-poison,
+        // jadi isinya dipakai apa adanya.
+        let mut idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(conn) = idle.pop() {
+                return PooledConnection {
+                    pool: self,
+                    conn: Some(conn),
+                };
+            }
+            idle = self
+                .available
+                .wait(idle)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// Koneksi baca yang dipinjam dari `ReaderPool` dan dikembalikan saat di-drop,
+/// termasuk ketika pemakainya panic.
+struct PooledConnection<'a> {
+    pool: &'a ReaderPool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("koneksi sudah dikembalikan ke pool")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool
+                .idle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(conn);
+            self.pool.available.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::path::PathBuf;
+
+    /// Lokasi database file sementara yang unik untuk setiap pengujian.
+    fn temp_db_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("keuanganku-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("finance.db")
+    }
+
+    #[test]
+    fn file_database_uses_wal_and_foreign_keys() {
+        let path = temp_db_path("wal");
+        let db = Database::open(&path).unwrap();
+
+        let (journal_mode, foreign_keys) = db
+            .read(|conn| {
+                let journal_mode: String =
+                    conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+                let foreign_keys: i64 =
+                    conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+                Ok((journal_mode, foreign_keys))
+            })
+            .unwrap();
+
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(foreign_keys, 1);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn readers_see_committed_writes() {
+        let path = temp_db_path("readers");
+        let db = Database::open(&path).unwrap();
+
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO categories (name, category_type) VALUES ('Makan', 2)",
+                [],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        // Koneksi baca bersifat read-only dan tetap melihat data terbaru.
+        let count: i64 = db
+            .read(|conn| {
+                assert!(conn.execute("DELETE FROM categories", []).is_err());
+                conn.query_row("SELECT COUNT(*) FROM categories", [], |row| row.get(0))
+                    .map_err(AppError::from)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn panic_inside_write_rolls_back_and_keeps_database_usable() {
+        let db = Database::open_in_memory().unwrap();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            db.write::<()>(|conn| {
+                conn.execute_batch(
+                    "BEGIN; INSERT INTO categories (name, category_type) VALUES ('Makan', 2);",
+                )?;
+                panic!("operasi gagal di tengah transaksi");
+            })
+        }));
+        assert!(result.is_err());
+
+        let count: i64 = db
+            .read(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM categories", [], |row| row.get(0))
+                    .map_err(AppError::from)
+            })
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+}
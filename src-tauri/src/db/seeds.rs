@@ -0,0 +1,127 @@
+//! Data awal untuk database baru, dijalankan saat startup setelah migrasi.
+//!
+//! Setiap seed dicatat di tabel `seed_history` (dibuat oleh migrasi v011) dan hanya
+//! dijalankan sekali seumur database. Seed juga dilewati bila tabel tujuannya sudah
+//! berisi data, sehingga data buatan pengguna tidak pernah ditimpa atau ditambah diam-diam.
+
+use crate::error::AppError;
+use crate::models::default_category::{self, DEFAULT_LOCALE};
+use crate::repositories::category_repository;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Tabel dan kolom yang dibutuhkan modul ini (diperiksa saat startup).
+pub const TABLE: &str = "seed_history";
+pub const COLUMNS: &[&str] = &["name", "locale", "applied_at"];
+
+const DEFAULT_CATEGORIES: &str = "default_categories";
+
+/// Memasang kategori bawaan untuk `locale` (atau `DEFAULT_LOCALE` bila tidak dikenal)
+/// jika database belum pernah di-seed dan belum memiliki kategori.
+/// Mengembalikan `true` jika kategori bawaan dipasang.
+pub fn seed_default_categories(conn: &mut Connection, locale: &str) -> Result<bool, AppError> {
+    let tx = conn.transaction()?;
+
+    let seeded = tx
+        .query_row(
+            "SELECT 1 FROM seed_history WHERE name = ?1",
+            params![DEFAULT_CATEGORIES],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if seeded {
+        return Ok(false);
+    }
+
+    let has_categories: bool =
+        tx.query_row("SELECT EXISTS(SELECT 1 FROM categories)", [], |row| {
+            row.get(0)
+        })?;
+    let applied_locale = if has_categories {
+        None
+    } else {
+        let set = default_category::find_set(locale)
+            .or_else(|| default_category::find_set(DEFAULT_LOCALE))
+            .expect("kategori bawaan untuk DEFAULT_LOCALE harus tersedia");
+        category_repository::insert_defaults(&tx, set)?;
+        Some(set.locale)
+    };
+
+    tx.execute(
+        "INSERT INTO seed_history (name, locale, applied_at) VALUES (?1, ?2, datetime('now'))",
+        params![DEFAULT_CATEGORIES, applied_locale],
+    )?;
+    tx.commit()?;
+    Ok(applied_locale.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn category_names(db: &Database) -> Vec<String> {
+        db.read(|conn| {
+            let mut stmt = conn.prepare("SELECT name FROM categories ORDER BY id")?;
+            let names = stmt.query_map([], |row| row.get(0))?;
+            names
+                .collect::<rusqlite::Result<Vec<String>>>()
+                .map_err(AppError::from)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn seeds_once_for_selected_locale() {
+        let db = Database::open_in_memory().unwrap();
+
+        assert!(db
+            .write(|conn| seed_default_categories(conn, "en-US"))
+            .unwrap());
+        let names = category_names(&db);
+        assert!(names.contains(&"Salary".to_string()));
+        assert!(names.contains(&"Groceries".to_string()));
+
+        // Kategori yang dihapus pengguna tidak dipasang lagi pada startup berikutnya.
+        db.write(|conn| {
+            conn.execute("DELETE FROM categories", [])?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!db
+            .write(|conn| seed_default_categories(conn, "id"))
+            .unwrap());
+        assert!(category_names(&db).is_empty());
+    }
+
+    #[test]
+    fn existing_categories_are_never_seeded_over() {
+        let db = Database::open_in_memory().unwrap();
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO categories (name, category_type) VALUES ('Jajan', 2)",
+                [],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(!db
+            .write(|conn| seed_default_categories(conn, "id"))
+            .unwrap());
+        assert_eq!(category_names(&db), vec!["Jajan".to_string()]);
+    }
+
+    #[test]
+    fn unknown_locale_falls_back_to_indonesian() {
+        let db = Database::open_in_memory().unwrap();
+
+        assert!(db
+            .write(|conn| seed_default_categories(conn, "fr"))
+            .unwrap());
+        let names = category_names(&db);
+        for name in ["Makan", "Transportasi", "Gaji", "Belanja", "Tagihan"] {
+            assert!(names.contains(&name.to_string()), "{} tidak ada", name);
+        }
+    }
+}
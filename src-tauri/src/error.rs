@@ -0,0 +1,124 @@
+//! Tipe error aplikasi yang dipakai oleh repository, service, dan command.
+//!
+//! Setiap varian memiliki kode yang stabil (misal `NOT_FOUND`) sehingga frontend bisa
+//! membedakan jenis error dan menampilkan pesan yang sudah dilokalisasi, tanpa harus
+//! mem-parsing teks pesan. Di sisi frontend, error diterima sebagai objek JSON:
+//! `{ "code": "VALIDATION_ERROR", "message": "...", "field": "amount" }`.
+
+use rusqlite::ErrorCode;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AppError {
+    /// Data yang diminta tidak ada.
+    NotFound(String),
+    /// Input tidak valid; `field` adalah nama parameter yang bermasalah.
+    Validation { field: String, message: String },
+    /// Operasi bertentangan dengan data yang ada (constraint, data masih dipakai, dll).
+    Conflict(String),
+    /// Database sedang dikunci oleh operasi lain; aman untuk dicoba lagi.
+    DatabaseBusy,
+    /// Error database lain yang tidak terduga.
+    Database(String),
+    /// Versi atau struktur skema database tidak sesuai dengan aplikasi.
+    Schema(String),
+    /// Gagal membaca atau menulis file.
+    Io(String),
+}
+
+impl AppError {
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        AppError::Validation {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(entity: &str, id: i64) -> Self {
+        AppError::NotFound(format!("{} dengan ID {} tidak ditemukan.", entity, id))
+    }
+
+    /// Kode error yang stabil untuk lokalisasi di frontend.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Validation { .. } => "VALIDATION_ERROR",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::DatabaseBusy => "DATABASE_BUSY",
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Schema(_) => "SCHEMA_ERROR",
+            AppError::Io(_) => "IO_ERROR",
+        }
+    }
+
+    /// Nama field yang tidak valid, hanya untuk `Validation`.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AppError::Validation { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+}
+
+/// Membuat pemeta error untuk query satu baris: `QueryReturnedNoRows` menjadi
+/// `NotFound` yang menyebut nama entitas dan ID-nya, error lain dikonversi seperti biasa.
+pub fn not_found_as(entity: &'static str, id: i64) -> impl FnOnce(rusqlite::Error) -> AppError {
+    move |e| match e {
+        rusqlite::Error::QueryReturnedNoRows => AppError::not_found(entity, id),
+        e => AppError::from(e),
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::NotFound(message)
+            | AppError::Conflict(message)
+            | AppError::Database(message)
+            | AppError::Schema(message)
+            | AppError::Io(message) => f.write_str(message),
+            AppError::Validation { message, .. } => f.write_str(message),
+            AppError::DatabaseBusy => {
+                f.write_str("Database sedang digunakan. Silakan coba beberapa saat lagi.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("field", &self.field())?;
+        state.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match &e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                AppError::NotFound("Data tidak ditemukan.".to_string())
+            }
+            rusqlite::Error::SqliteFailure(err, _) => match err.code {
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => AppError::DatabaseBusy,
+                ErrorCode::ConstraintViolation => {
+                    AppError::Conflict(format!("Data bertentangan dengan data lain: {}", e))
+                }
+                _ => AppError::Database(e.to_string()),
+            },
+            _ => AppError::Database(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(e.to_string())
+    }
+}
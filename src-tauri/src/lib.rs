@@ -1,27 +1,181 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 mod commands;
 pub mod db;
+pub mod error;
 pub mod models;
 pub mod repositories;
 pub mod services;
 
+use tauri::{Emitter, Manager};
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Event yang dikirim ke frontend saat tugas latar belakang gagal.
+const BACKGROUND_TASK_FAILED_EVENT: &str = "background-task-failed";
+
+/// Isi event `background-task-failed`: nama tugas dan error dalam bentuk yang sama dengan
+/// error command.
+#[derive(Clone, serde::Serialize)]
+struct BackgroundTaskFailed<'a> {
+    task: &'static str,
+    error: &'a error::AppError,
+}
+
+/// Meneruskan error tugas latar belakang ke frontend agar bisa ditampilkan kepada pengguna;
+/// aplikasi desktop tidak memiliki konsol untuk stderr.
+fn report_background_error(app: &tauri::AppHandle, task: &'static str, error: &error::AppError) {
+    // Gagal mengirim hanya berarti tidak ada jendela yang bisa diberi tahu.
+    let _ = app.emit(
+        BACKGROUND_TASK_FAILED_EVENT,
+        BackgroundTaskFailed { task, error },
+    );
+}
+
+/// Membuat transaksi berulang yang jatuh tempo segera setelah aplikasi dimulai, lalu
+/// memeriksanya lagi secara berkala. Berjalan di thread terpisah agar tidak menahan UI.
+fn spawn_recurring_materializer(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        let database = app.state::<db::Database>();
+        if let Err(e) = commands::recurring_command::materialize_due(&database) {
+            report_background_error(&app, "recurring_transactions", &e);
+        }
+        std::thread::sleep(services::recurring_service::MATERIALIZE_INTERVAL);
+    });
+}
+
+/// Mencatat cuplikan kekayaan bersih hari ini saat aplikasi dimulai, lalu memperbaruinya
+/// secara berkala agar riwayatnya terisi tanpa tindakan pengguna.
+fn spawn_net_worth_snapshots(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        let database = app.state::<db::Database>();
+        if let Err(e) = commands::net_worth_command::record_snapshot(&database) {
+            report_background_error(&app, "net_worth_snapshot", &e);
+        }
+        std::thread::sleep(services::net_worth_service::SNAPSHOT_INTERVAL);
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // Database disimpan di direktori data aplikasi milik OS, bukan di direktori kerja.
+            let config_dir = app.path().app_config_dir()?;
+            let db_path =
+                db::location::resolve_database_path(&app.path().app_data_dir()?, &config_dir)?;
+            // Skema database harus mutakhir sebelum UI ditampilkan. Handle-nya disimpan
+            // sebagai managed state dan diambil oleh setiap command lewat `State<Database>`.
+            let database = db::Database::open(&db_path)?;
+            // Database baru langsung berisi kategori bawaan agar transaksi pertama bisa dicatat.
+            database.seed_defaults(&db::location::resolve_locale(&config_dir)?)?;
+            app.manage(database);
+            spawn_recurring_materializer(app.handle().clone());
+            spawn_net_worth_snapshots(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             commands::account_command::create_account,
             commands::account_command::get_all_accounts,
             commands::account_command::get_account_by_id,
             commands::account_command::update_account,
-            commands::account_command::delete_account
+            commands::account_command::delete_account,
+            commands::account_command::recalculate_account_balance,
+            commands::budget_command::create_budget,
+            commands::budget_command::get_all_budgets,
+            commands::budget_command::get_budget_by_id,
+            commands::budget_command::update_budget,
+            commands::budget_command::delete_budget,
+            commands::budget_command::get_budget_progress,
+            commands::category_command::create_category,
+            commands::category_command::get_all_categories,
+            commands::category_command::get_category_tree,
+            commands::category_command::get_category_by_id,
+            commands::category_command::update_category,
+            commands::category_command::delete_category,
+            commands::category_command::merge_categories,
+            commands::category_command::get_default_category_sets,
+            commands::category_command::install_default_categories,
+            commands::debt_command::create_debt,
+            commands::debt_command::get_all_debts,
+            commands::debt_command::get_debt_by_id,
+            commands::debt_command::update_debt,
+            commands::debt_command::delete_debt,
+            commands::debt_command::add_debt_payment,
+            commands::debt_command::get_debt_payments,
+            commands::debt_command::delete_debt_payment,
+            commands::debt_command::get_debt_status,
+            commands::debt_command::get_counterparty_balances,
+            commands::debt_command::calculate_loan_schedule,
+            commands::currency_command::add_exchange_rate,
+            commands::currency_command::get_all_exchange_rates,
+            commands::currency_command::delete_exchange_rate,
+            commands::currency_command::import_exchange_rates_csv,
+            commands::currency_command::get_total_balance,
+            commands::expense_command::create_expense,
+            commands::expense_command::get_all_expenses,
+            commands::expense_command::get_expense_by_id,
+            commands::expense_command::query_expenses,
+            commands::expense_command::update_expense,
+            commands::expense_command::delete_expense,
+            commands::goal_command::create_goal,
+            commands::goal_command::get_all_goals,
+            commands::goal_command::get_goal_by_id,
+            commands::goal_command::update_goal,
+            commands::goal_command::delete_goal,
+            commands::goal_command::add_goal_contribution,
+            commands::goal_command::get_goal_contributions,
+            commands::goal_command::delete_goal_contribution,
+            commands::goal_command::get_goal_progress,
+            commands::income_command::create_income,
+            commands::income_command::get_all_incomes,
+            commands::income_command::get_income_by_id,
+            commands::income_command::query_incomes,
+            commands::income_command::update_income,
+            commands::income_command::delete_income,
+            commands::investment_command::create_instrument,
+            commands::investment_command::get_all_instruments,
+            commands::investment_command::get_instrument_by_id,
+            commands::investment_command::update_instrument,
+            commands::investment_command::delete_instrument,
+            commands::investment_command::add_investment_transaction,
+            commands::investment_command::get_investment_transactions,
+            commands::investment_command::delete_investment_transaction,
+            commands::investment_command::add_instrument_price,
+            commands::investment_command::get_instrument_prices,
+            commands::investment_command::delete_instrument_price,
+            commands::investment_command::import_instrument_prices_csv,
+            commands::investment_command::get_holdings,
+            commands::investment_command::get_portfolio_valuation,
+            commands::ledger_command::get_ledger,
+            commands::net_worth_command::get_net_worth,
+            commands::net_worth_command::get_net_worth_history,
+            commands::net_worth_command::record_net_worth_snapshot,
+            commands::recurring_command::create_recurring_rule,
+            commands::recurring_command::get_all_recurring_rules,
+            commands::recurring_command::get_recurring_rule_by_id,
+            commands::recurring_command::update_recurring_rule,
+            commands::recurring_command::delete_recurring_rule,
+            commands::recurring_command::preview_recurring_occurrences,
+            commands::recurring_command::skip_recurring_occurrence,
+            commands::recurring_command::edit_recurring_occurrence,
+            commands::recurring_command::materialize_recurring_transactions,
+            commands::report_command::get_totals_by_period,
+            commands::report_command::get_totals_by_category,
+            commands::report_command::get_totals_by_account,
+            commands::report_command::get_income_expense_summary,
+            commands::report_command::get_top_categories,
+            commands::search_command::search_transactions,
+            commands::transfer_command::create_transfer,
+            commands::transfer_command::get_all_transfers,
+            commands::transfer_command::get_transfer_by_id,
+            commands::transfer_command::update_transfer,
+            commands::transfer_command::delete_transfer
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
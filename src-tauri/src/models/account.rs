@@ -1,4 +1,18 @@
-use serde::Serialize;
+use crate::models::money::{Currency, Money};
+use serde::{Deserialize, Serialize};
+
+/// Jenis akun. Disimpan sebagai integer 1–2.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum AccountType {
+    /// Dompet, rekening bank, atau e-wallet.
+    #[default]
+    Cash = 1,
+    /// Rekening efek atau reksa dana. Saldonya adalah kas yang belum diinvestasikan;
+    /// nilai kepemilikan instrumennya dihitung terpisah oleh layanan investasi.
+    Investment = 2,
+}
 
 /// Merepresentasikan sebuah akun (misal: dompet, rekening bank).
 #[derive(Serialize, Debug)]
@@ -6,5 +20,10 @@ pub struct Account {
     pub id: i64,
     pub name: String,
     pub description: Option<String>, // Deskripsi bisa jadi tidak ada (NULL)
-    pub balance: f64,
+    pub balance: Money,
+    /// Mata uang akun (ISO 4217). Diekspos terpisah karena `Money` di-serialize sebagai angka.
+    pub currency: Currency,
+    /// Saldo saat akun dibuat, titik awal untuk menghitung ulang saldo dari riwayat transaksi.
+    pub opening_balance: Money,
+    pub account_type: AccountType,
 }
@@ -0,0 +1,109 @@
+use crate::models::money::{Currency, Money};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Periode anggaran. Disimpan sebagai integer 1–3.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum BudgetPeriod {
+    /// Senin sampai Minggu.
+    Weekly = 1,
+    Monthly = 2,
+    Yearly = 3,
+}
+
+impl BudgetPeriod {
+    /// Tanggal awal periode yang memuat `date`.
+    pub fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            BudgetPeriod::Weekly => date - Days::new(date.weekday().num_days_from_monday().into()),
+            BudgetPeriod::Monthly => date.with_day(1).expect("tanggal 1 selalu valid"),
+            BudgetPeriod::Yearly => {
+                NaiveDate::from_ymd_opt(date.year(), 1, 1).expect("1 Januari selalu valid")
+            }
+        }
+    }
+
+    /// Tanggal awal periode berikutnya setelah periode yang dimulai pada `start`.
+    pub fn next_start(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            BudgetPeriod::Weekly => start + Days::new(7),
+            BudgetPeriod::Monthly => start + Months::new(1),
+            BudgetPeriod::Yearly => start + Months::new(12),
+        }
+    }
+}
+
+/// Anggaran pengeluaran untuk satu kategori, termasuk seluruh sub-kategorinya.
+#[derive(Serialize, Debug)]
+pub struct Budget {
+    pub id: i64,
+    pub category_id: i64,
+    pub period: BudgetPeriod,
+    /// Batas pengeluaran per periode.
+    pub amount: Money,
+    pub currency: Currency,
+    /// Sisa anggaran yang tidak terpakai ditambahkan ke periode berikutnya.
+    pub rollover: bool,
+    /// Awal periode pertama anggaran; sisa anggaran dihitung sejak tanggal ini.
+    pub start_date: NaiveDate,
+}
+
+/// Data anggaran yang sudah divalidasi oleh service.
+#[derive(Debug)]
+pub struct NewBudget {
+    pub category_id: i64,
+    pub period: BudgetPeriod,
+    pub amount: Money,
+    pub rollover: bool,
+    pub start_date: NaiveDate,
+}
+
+/// Input anggaran dari frontend. `amount` dalam satuan mayor `currency` (default IDR);
+/// `start_date` kosong berarti mulai periode ini.
+#[derive(Deserialize, Debug)]
+pub struct BudgetInput {
+    pub category_id: i64,
+    pub period: BudgetPeriod,
+    pub amount: f64,
+    #[serde(default)]
+    pub currency: Currency,
+    #[serde(default)]
+    pub rollover: bool,
+    pub start_date: Option<NaiveDate>,
+}
+
+/// Status pemakaian anggaran pada periode berjalan.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    OnTrack,
+    /// Belum melewati batas, tetapi laju pengeluaran saat ini akan melewatinya
+    /// sebelum periode berakhir.
+    TrendingOver,
+    OverBudget,
+}
+
+/// Realisasi satu anggaran pada periode yang memuat tanggal acuan.
+#[derive(Serialize, Debug)]
+pub struct BudgetProgress {
+    #[serde(flatten)]
+    pub budget: Budget,
+    pub category_name: String,
+    pub period_start: NaiveDate,
+    /// Hari terakhir periode (inklusif).
+    pub period_end: NaiveDate,
+    /// Sisa anggaran dari periode-periode sebelumnya (hanya bila `rollover`).
+    pub carried_over: Money,
+    /// `amount + carried_over`.
+    pub available: Money,
+    pub spent: Money,
+    /// `available - spent`; negatif bila anggaran terlampaui.
+    pub remaining: Money,
+    /// Persentase `spent` terhadap `available`.
+    pub percent_used: f64,
+    /// Perkiraan pengeluaran sampai akhir periode dengan laju harian saat ini.
+    pub projected_spent: Money,
+    pub status: BudgetStatus,
+}
@@ -2,7 +2,7 @@ use serde::Serialize;
 
 /// Enum untuk merepresentasikan tipe kategori.
 /// Akan di-serialize sebagai integer (1 untuk Income, 2 untuk Expense).
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i16)]
 pub enum CategoryType {
     Income = 1,
@@ -15,4 +15,18 @@ pub struct Category {
     pub id: i64,
     pub name: String,
     pub category_type: CategoryType,
+    /// Kategori induk; `None` untuk kategori akar. Induk selalu bertipe sama.
+    pub parent_id: Option<i64>,
+    /// Nama ikon lucide, misal `utensils`.
+    pub icon: Option<String>,
+    /// Warna dalam format `#RRGGBB`.
+    pub color: Option<String>,
+}
+
+/// Satu kategori beserta seluruh sub-kategorinya, untuk ditampilkan sebagai pohon.
+#[derive(Serialize, Debug)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    pub children: Vec<CategoryNode>,
 }
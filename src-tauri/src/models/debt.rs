@@ -0,0 +1,172 @@
+use crate::models::loan::{InterestMethod, ScheduledInstallment};
+use crate::models::money::{Currency, Money};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Arah utang. Disimpan sebagai integer 1–2.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum DebtDirection {
+    /// Saya berutang kepada pihak lain; pembayarannya dicatat sebagai pengeluaran.
+    Payable = 1,
+    /// Pihak lain berutang kepada saya; pelunasannya dicatat sebagai pemasukan.
+    Receivable = 2,
+}
+
+/// Utang atau piutang kepada satu pihak, misal pinjaman keluarga atau kredit motor.
+#[derive(Serialize, Debug)]
+pub struct Debt {
+    pub id: i64,
+    /// Nama pihak lawan (orang, bank, leasing).
+    pub counterparty: String,
+    pub direction: DebtDirection,
+    /// Pokok pinjaman.
+    pub principal: Money,
+    pub currency: Currency,
+    pub start_date: NaiveDate,
+    /// Jatuh tempo pelunasan sekaligus; untuk utang bercicilan mengikuti cicilan terakhir.
+    pub due_date: Option<NaiveDate>,
+    /// Bunga per tahun dalam persen, dihitung dengan `interest_method` selama masa cicilan.
+    pub interest_rate: Option<f64>,
+    /// Jumlah cicilan bulanan, dimulai sebulan setelah `start_date`.
+    pub installment_count: Option<u32>,
+    pub interest_method: InterestMethod,
+    /// Kategori cicilan: kategori pengeluaran untuk utang, kategori pemasukan untuk piutang.
+    /// Transaksi dalam kategori ini yang jumlahnya sama dengan cicilan dan tanggalnya dekat
+    /// jatuh tempo otomatis dicatat sebagai pembayaran.
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
+    /// Akun tempat pokok pinjaman dicairkan: bertambah untuk utang, berkurang untuk piutang.
+    /// Kosong bila pencairannya tidak dicatat pada akun mana pun.
+    pub account_id: Option<i64>,
+}
+
+/// Data utang yang sudah divalidasi oleh service.
+#[derive(Debug)]
+pub struct NewDebt<'a> {
+    pub counterparty: &'a str,
+    pub direction: DebtDirection,
+    pub principal: Money,
+    pub start_date: NaiveDate,
+    pub due_date: Option<NaiveDate>,
+    pub interest_rate: Option<f64>,
+    pub installment_count: Option<u32>,
+    pub interest_method: InterestMethod,
+    pub category_id: Option<i64>,
+    pub description: Option<&'a str>,
+    pub account_id: Option<i64>,
+    /// Tabel angsuran yang disimpan bersama utang; kosong untuk utang tanpa cicilan.
+    pub schedule: Vec<ScheduledInstallment>,
+}
+
+/// Input utang dari frontend. `principal` dalam satuan mayor `currency` (default IDR).
+/// `interest_rate` hanya berlaku bersama `installment_count`; `due_date` hanya untuk utang
+/// tanpa cicilan.
+#[derive(Deserialize, Debug)]
+pub struct DebtInput {
+    pub counterparty: String,
+    pub direction: DebtDirection,
+    pub principal: f64,
+    #[serde(default)]
+    pub currency: Currency,
+    pub start_date: NaiveDate,
+    pub due_date: Option<NaiveDate>,
+    pub interest_rate: Option<f64>,
+    pub installment_count: Option<u32>,
+    #[serde(default)]
+    pub interest_method: InterestMethod,
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
+    /// Akun pencairan; mata uangnya harus sama dengan `currency`.
+    pub account_id: Option<i64>,
+}
+
+/// Satu pembayaran utang atau pelunasan piutang. Jumlah, tanggal, dan akunnya diambil dari
+/// transaksi yang ditautkan.
+#[derive(Serialize, Debug)]
+pub struct DebtPayment {
+    pub id: i64,
+    pub debt_id: i64,
+    pub amount: Money,
+    pub date: NaiveDateTime,
+    pub account_id: Option<i64>,
+    /// Pengeluaran yang mencatat pembayaran utang.
+    pub expense_id: Option<i64>,
+    /// Pemasukan yang mencatat pelunasan piutang.
+    pub income_id: Option<i64>,
+}
+
+/// Data pembayaran yang sudah divalidasi; dicatat sebagai pengeluaran atau pemasukan
+/// sesuai arah utangnya.
+#[derive(Debug)]
+pub struct NewDebtPayment<'a> {
+    pub description: &'a str,
+    pub amount: Money,
+    pub date: NaiveDateTime,
+    pub category_id: i64,
+    pub account_id: i64,
+}
+
+/// Input pembayaran dari frontend. `amount` dalam satuan mayor mata uang utang.
+/// `category_id` harus kategori pengeluaran untuk utang dan kategori pemasukan untuk piutang.
+#[derive(Deserialize, Debug)]
+pub struct DebtPaymentInput {
+    pub amount: f64,
+    pub date: NaiveDateTime,
+    pub account_id: i64,
+    pub category_id: i64,
+    pub description: Option<String>,
+}
+
+/// Status satu cicilan per tanggal acuan.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallmentStatus {
+    Paid,
+    /// Sudah dibayar sebagian dan belum jatuh tempo.
+    Partial,
+    Upcoming,
+    /// Lewat jatuh tempo dan belum lunas.
+    Overdue,
+}
+
+/// Satu cicilan dalam jadwal, dengan pembayaran yang dialokasikan berurutan dari cicilan
+/// pertama.
+#[derive(Serialize, Debug)]
+pub struct Installment {
+    #[serde(flatten)]
+    pub scheduled: ScheduledInstallment,
+    pub paid: Money,
+    pub status: InstallmentStatus,
+}
+
+/// Ringkasan satu utang per tanggal acuan.
+#[derive(Serialize, Debug)]
+pub struct DebtStatus {
+    #[serde(flatten)]
+    pub debt: Debt,
+    pub total_interest: Money,
+    /// Pokok ditambah bunga.
+    pub total_due: Money,
+    pub paid: Money,
+    pub outstanding: Money,
+    /// Sisa pokok; dalam setiap cicilan, pembayaran menutup bunga lebih dulu.
+    pub remaining_principal: Money,
+    /// Masih ada sisa setelah jatuh tempo (atau ada cicilan yang terlambat).
+    pub overdue: bool,
+    pub installments: Vec<Installment>,
+}
+
+/// Sisa utang dan piutang dengan satu pihak dalam satu mata uang.
+#[derive(Serialize, Debug)]
+pub struct CounterpartyBalance {
+    pub counterparty: String,
+    pub currency: Currency,
+    /// Sisa yang masih harus diterima dari pihak ini.
+    pub receivable: Money,
+    /// Sisa yang masih harus dibayar kepada pihak ini.
+    pub payable: Money,
+    /// `receivable - payable`; positif berarti pihak ini masih berutang kepada saya.
+    pub net: Money,
+}
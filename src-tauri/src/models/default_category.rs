@@ -0,0 +1,150 @@
+//! Kumpulan kategori bawaan per bahasa yang dipasang pada database baru.
+//! Ikon memakai nama ikon lucide (kebab-case) yang juga dipakai frontend.
+
+use serde::Serialize;
+
+/// Bahasa yang dipakai bila pengaturan `locale` tidak diisi atau tidak dikenal.
+pub const DEFAULT_LOCALE: &str = "id";
+
+/// Satu kategori bawaan beserta sub-kategorinya. Sub-kategori mengikuti tipe induknya,
+/// dan memakai warna induknya bila `color` tidak diisi.
+#[derive(Serialize, Debug)]
+pub struct DefaultCategory {
+    pub name: &'static str,
+    pub icon: &'static str,
+    pub color: Option<&'static str>,
+    pub children: &'static [DefaultCategory],
+}
+
+/// Kategori bawaan untuk satu bahasa.
+#[derive(Serialize, Debug)]
+pub struct DefaultCategorySet {
+    /// Kode bahasa, misal `id` atau `en`.
+    pub locale: &'static str,
+    /// Nama bahasa untuk ditampilkan di UI.
+    pub label: &'static str,
+    pub income: &'static [DefaultCategory],
+    pub expense: &'static [DefaultCategory],
+}
+
+const fn root(
+    name: &'static str,
+    icon: &'static str,
+    color: &'static str,
+    children: &'static [DefaultCategory],
+) -> DefaultCategory {
+    DefaultCategory {
+        name,
+        icon,
+        color: Some(color),
+        children,
+    }
+}
+
+const fn child(name: &'static str, icon: &'static str) -> DefaultCategory {
+    DefaultCategory {
+        name,
+        icon,
+        color: None,
+        children: &[],
+    }
+}
+
+/// Semua kumpulan kategori bawaan yang tersedia.
+pub const DEFAULT_CATEGORY_SETS: &[DefaultCategorySet] = &[
+    DefaultCategorySet {
+        locale: "id",
+        label: "Bahasa Indonesia",
+        income: &[
+            root("Gaji", "briefcase", "#22C55E", &[]),
+            root("Bonus", "gift", "#84CC16", &[]),
+            root("Usaha", "store", "#F59E0B", &[]),
+            root("Investasi", "trending-up", "#06B6D4", &[]),
+            root("Pemasukan Lain", "coins", "#64748B", &[]),
+        ],
+        expense: &[
+            root(
+                "Makan",
+                "utensils",
+                "#F97316",
+                &[
+                    child("Restoran", "chef-hat"),
+                    child("Belanja Dapur", "shopping-basket"),
+                ],
+            ),
+            root(
+                "Transportasi",
+                "train",
+                "#3B82F6",
+                &[child("Bensin", "fuel"), child("Ojek Online", "bike")],
+            ),
+            root("Belanja", "shopping-cart", "#EC4899", &[]),
+            root(
+                "Tagihan",
+                "receipt",
+                "#EF4444",
+                &[
+                    child("Listrik", "zap"),
+                    child("Internet", "wifi"),
+                    child("Air", "droplet"),
+                ],
+            ),
+            root("Kesehatan", "heart-pulse", "#10B981", &[]),
+            root("Hiburan", "film", "#8B5CF6", &[]),
+            root("Pendidikan", "graduation-cap", "#0EA5E9", &[]),
+            root("Sedekah", "handshake", "#14B8A6", &[]),
+        ],
+    },
+    DefaultCategorySet {
+        locale: "en",
+        label: "English",
+        income: &[
+            root("Salary", "briefcase", "#22C55E", &[]),
+            root("Bonus", "gift", "#84CC16", &[]),
+            root("Business", "store", "#F59E0B", &[]),
+            root("Investments", "trending-up", "#06B6D4", &[]),
+            root("Other Income", "coins", "#64748B", &[]),
+        ],
+        expense: &[
+            root(
+                "Food",
+                "utensils",
+                "#F97316",
+                &[
+                    child("Restaurants", "chef-hat"),
+                    child("Groceries", "shopping-basket"),
+                ],
+            ),
+            root(
+                "Transportation",
+                "train",
+                "#3B82F6",
+                &[child("Fuel", "fuel"), child("Ride Hailing", "bike")],
+            ),
+            root("Shopping", "shopping-cart", "#EC4899", &[]),
+            root(
+                "Bills",
+                "receipt",
+                "#EF4444",
+                &[
+                    child("Electricity", "zap"),
+                    child("Internet", "wifi"),
+                    child("Water", "droplet"),
+                ],
+            ),
+            root("Health", "heart-pulse", "#10B981", &[]),
+            root("Entertainment", "film", "#8B5CF6", &[]),
+            root("Education", "graduation-cap", "#0EA5E9", &[]),
+            root("Charity", "handshake", "#14B8A6", &[]),
+        ],
+    },
+];
+
+/// Mencari kumpulan kategori bawaan untuk `locale` (tidak peka huruf besar/kecil).
+/// Bagian wilayah diabaikan, sehingga `id-ID` memakai kumpulan `id`.
+pub fn find_set(locale: &str) -> Option<&'static DefaultCategorySet> {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    DEFAULT_CATEGORY_SETS
+        .iter()
+        .find(|set| set.locale.eq_ignore_ascii_case(language.trim()))
+}
@@ -0,0 +1,23 @@
+use crate::models::money::Currency;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Asal data kurs: dimasukkan manual oleh pengguna atau diimpor dari file CSV.
+/// Disimpan sebagai integer (1 untuk Manual, 2 untuk Csv).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i16)]
+pub enum RateSource {
+    Manual = 1,
+    Csv = 2,
+}
+
+/// Kurs pada tanggal tertentu: `1 base_currency = rate quote_currency`.
+#[derive(Serialize, Debug)]
+pub struct ExchangeRate {
+    pub id: i64,
+    pub date: NaiveDate,
+    pub base_currency: Currency,
+    pub quote_currency: Currency,
+    pub rate: f64,
+    pub source: RateSource,
+}
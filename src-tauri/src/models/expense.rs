@@ -1,3 +1,4 @@
+use crate::models::money::{Currency, Money};
 use chrono::NaiveDateTime;
 use serde::Serialize;
 
@@ -6,7 +7,10 @@ use serde::Serialize;
 pub struct Expense {
     pub id: i64,
     pub description: String,
-    pub amount: f64,
+    pub amount: Money,
+    /// Mata uang transaksi (ISO 4217), sama dengan mata uang pada `amount`.
+    pub currency: Currency,
     pub date: NaiveDateTime,
-    pub category_id: i64, // Foreign key yang terhubung ke Category
+    pub category_id: i64,        // Foreign key yang terhubung ke Category
+    pub account_id: Option<i64>, // NULL untuk data lama yang dibuat sebelum transaksi terhubung ke akun
 }
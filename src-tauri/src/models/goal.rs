@@ -0,0 +1,81 @@
+use crate::models::money::{Currency, Money};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Target tabungan, misal motor, Lebaran, atau dana darurat.
+#[derive(Serialize, Debug)]
+pub struct Goal {
+    pub id: i64,
+    pub name: String,
+    pub target_amount: Money,
+    pub currency: Currency,
+    /// Tanggal target tercapai; kosong untuk target tanpa tenggat.
+    pub target_date: Option<NaiveDate>,
+    /// Akun tempat tabungan ini disimpan. Mata uangnya sama dengan `currency`.
+    pub account_id: Option<i64>,
+}
+
+/// Data target yang sudah divalidasi oleh service.
+#[derive(Debug)]
+pub struct NewGoal<'a> {
+    pub name: &'a str,
+    pub target_amount: Money,
+    pub target_date: Option<NaiveDate>,
+    pub account_id: Option<i64>,
+}
+
+/// Input target dari frontend. Bila `account_id` diisi, mata uang target mengikuti akun;
+/// selain itu memakai `currency` (default IDR). `target_amount` dalam satuan mayor.
+#[derive(Deserialize, Debug)]
+pub struct GoalInput {
+    pub name: String,
+    pub target_amount: f64,
+    #[serde(default)]
+    pub currency: Currency,
+    pub target_date: Option<NaiveDate>,
+    pub account_id: Option<i64>,
+}
+
+/// Satu setoran ke target. Jumlah negatif berarti penarikan.
+#[derive(Serialize, Debug)]
+pub struct GoalContribution {
+    pub id: i64,
+    pub goal_id: i64,
+    pub amount: Money,
+    pub date: NaiveDate,
+    pub note: Option<String>,
+    /// Transfer yang memindahkan uang setoran ini ke akun target, jika ada.
+    pub transfer_id: Option<i64>,
+}
+
+/// Input setoran dari frontend. `amount` dalam satuan mayor mata uang target.
+/// Bila `from_account_id` diisi, setoran juga dicatat sebagai transfer dari akun tersebut
+/// ke akun target.
+#[derive(Deserialize, Debug)]
+pub struct ContributionInput {
+    pub amount: f64,
+    pub date: NaiveDate,
+    pub note: Option<String>,
+    pub from_account_id: Option<i64>,
+}
+
+/// Perkembangan satu target per tanggal acuan.
+#[derive(Serialize, Debug)]
+pub struct GoalProgress {
+    #[serde(flatten)]
+    pub goal: Goal,
+    /// Jumlah seluruh setoran dikurangi penarikan.
+    pub saved: Money,
+    /// Kekurangan menuju target; nol bila sudah tercapai.
+    pub remaining: Money,
+    pub percent_complete: f64,
+    /// Rata-rata tabungan per bulan sejak setoran pertama.
+    pub average_monthly_saving: Option<Money>,
+    /// Tanggal target tercapai (bila sudah) atau perkiraannya dengan laju rata-rata saat ini.
+    /// Kosong bila belum ada tabungan bersih.
+    pub projected_completion_date: Option<NaiveDate>,
+    /// Tabungan per bulan yang dibutuhkan agar target tercapai tepat pada `target_date`.
+    pub required_monthly_saving: Option<Money>,
+    /// Apakah perkiraan tanggal tercapai tidak melewati `target_date`.
+    pub on_track: Option<bool>,
+}
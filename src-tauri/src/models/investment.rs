@@ -0,0 +1,160 @@
+use crate::models::money::{Currency, Money};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Jenis instrumen investasi. Disimpan sebagai integer 1–5.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum InstrumentKind {
+    /// Reksa dana; unit penyertaan dengan NAB harian.
+    MutualFund = 1,
+    Stock = 2,
+    /// Emas; unit dalam gram.
+    Gold = 3,
+    Bond = 4,
+    Other = 5,
+}
+
+/// Instrumen yang bisa dimiliki di akun investasi, misal saham BBCA atau reksa dana pasar uang.
+#[derive(Serialize, Debug, Clone)]
+pub struct Instrument {
+    pub id: i64,
+    /// Kode unik (tidak membedakan huruf besar/kecil), dipakai juga saat impor harga CSV.
+    pub symbol: String,
+    pub name: String,
+    pub kind: InstrumentKind,
+    /// Mata uang harga dan transaksi instrumen ini.
+    pub currency: Currency,
+}
+
+/// Input instrumen dari frontend; `currency` default IDR.
+#[derive(Deserialize, Debug)]
+pub struct InstrumentInput {
+    pub symbol: String,
+    pub name: String,
+    pub kind: InstrumentKind,
+    #[serde(default)]
+    pub currency: Currency,
+}
+
+/// Jenis transaksi investasi. Disimpan sebagai integer 1–3.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum InvestmentTransactionKind {
+    /// Membeli unit; kas akun berkurang `amount + fee`.
+    Buy = 1,
+    /// Menjual unit; kas akun bertambah `amount - fee`.
+    Sell = 2,
+    /// Dividen atau kupon tunai; kas akun bertambah `amount - fee` (misal potongan pajak).
+    Dividend = 3,
+}
+
+/// Satu transaksi beli, jual, atau dividen pada akun investasi.
+#[derive(Serialize, Debug)]
+pub struct InvestmentTransaction {
+    pub id: i64,
+    pub account_id: i64,
+    pub instrument_id: i64,
+    pub kind: InvestmentTransactionKind,
+    /// Jumlah unit yang dibeli atau dijual; nol untuk dividen.
+    pub units: f64,
+    /// Nilai transaksi sebelum biaya.
+    pub amount: Money,
+    /// Biaya transaksi atau pajak.
+    pub fee: Money,
+    pub currency: Currency,
+    pub date: NaiveDateTime,
+    pub note: Option<String>,
+}
+
+/// Data transaksi investasi yang sudah divalidasi oleh service.
+#[derive(Debug)]
+pub struct NewInvestmentTransaction<'a> {
+    pub account_id: i64,
+    pub instrument_id: i64,
+    pub kind: InvestmentTransactionKind,
+    pub units: f64,
+    pub amount: Money,
+    pub fee: Money,
+    pub date: NaiveDateTime,
+    pub note: Option<&'a str>,
+}
+
+/// Input transaksi investasi dari frontend. Nilai dalam satuan mayor mata uang instrumen;
+/// `units` wajib untuk beli/jual dan diabaikan untuk dividen.
+#[derive(Deserialize, Debug)]
+pub struct InvestmentTransactionInput {
+    pub account_id: i64,
+    pub instrument_id: i64,
+    pub kind: InvestmentTransactionKind,
+    #[serde(default)]
+    pub units: f64,
+    pub amount: f64,
+    pub fee: Option<f64>,
+    pub date: NaiveDateTime,
+    pub note: Option<String>,
+}
+
+/// Asal data harga: dimasukkan manual atau diimpor dari CSV.
+/// Disimpan sebagai integer (1 untuk Manual, 2 untuk Csv).
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum PriceSource {
+    Manual = 1,
+    Csv = 2,
+}
+
+/// Harga satu unit instrumen pada tanggal tertentu (NAB, harga penutupan, atau harga emas).
+#[derive(Serialize, Debug)]
+pub struct InstrumentPrice {
+    pub id: i64,
+    pub instrument_id: i64,
+    pub date: NaiveDate,
+    /// Harga per unit dalam satuan mayor mata uang instrumen.
+    pub price: f64,
+    pub source: PriceSource,
+}
+
+/// Kepemilikan satu instrumen di satu akun per tanggal acuan, dengan metode biaya rata-rata.
+#[derive(Serialize, Debug)]
+pub struct Holding {
+    pub account_id: i64,
+    pub instrument: Instrument,
+    pub units: f64,
+    /// Total biaya perolehan unit yang masih dimiliki, termasuk biaya beli.
+    pub cost_basis: Money,
+    /// Harga terakhir yang diketahui: harga tercatat atau harga transaksi terakhir.
+    pub price: Option<f64>,
+    pub price_date: Option<NaiveDate>,
+    pub market_value: Option<Money>,
+    /// `market_value - cost_basis`.
+    pub unrealized_gain: Option<Money>,
+    /// Laba dari penjualan (hasil bersih dikurangi biaya perolehan unit yang dijual).
+    pub realized_gain: Money,
+    /// Dividen bersih yang sudah diterima.
+    pub dividends: Money,
+}
+
+/// Nilai portofolio satu akun investasi dalam mata uang akunnya.
+#[derive(Serialize, Debug)]
+pub struct PortfolioValuation {
+    pub account_id: i64,
+    pub currency: Currency,
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub holdings: Vec<Holding>,
+    /// Kas yang belum diinvestasikan pada akun (saldo saat ini).
+    pub cash_balance: Money,
+    pub cost_basis: Money,
+    /// Nilai pasar kepemilikan (tanpa kas); instrumen tanpa harga dinilai sebesar biayanya.
+    pub market_value: Money,
+    pub unrealized_gain: Money,
+    pub realized_gain: Money,
+    pub dividends: Money,
+    /// Imbal hasil tertimbang waktu (time-weighted return) kepemilikan selama periode,
+    /// sebagai pecahan (0,05 = 5%). Kosong bila tidak ada kepemilikan selama periode.
+    pub time_weighted_return: Option<f64>,
+}
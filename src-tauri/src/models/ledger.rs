@@ -0,0 +1,74 @@
+use crate::error::AppError;
+use crate::models::money::Money;
+use crate::models::transaction_query::{
+    validate_date_range, validate_limit, SortDirection, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Jenis entri buku besar. Disimpan di view `ledger_entries` sebagai integer 1–6.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum LedgerEntryType {
+    Income = 1,
+    Expense = 2,
+    TransferOut = 3,
+    TransferIn = 4,
+    /// Mutasi kas dari beli, jual, atau dividen pada akun investasi.
+    Investment = 5,
+    /// Pencairan utang (uang masuk) atau piutang (uang keluar) pada akun pencairannya.
+    DebtDisbursement = 6,
+}
+
+/// Satu mutasi pada satu akun dalam buku besar gabungan.
+#[derive(Serialize, Debug)]
+pub struct LedgerEntry {
+    pub entry_type: LedgerEntryType,
+    /// ID di tabel asalnya (`incomes`, `expenses`, `transfers`, `investment_transactions`,
+    /// atau `debts`).
+    pub entry_id: i64,
+    /// NULL untuk pemasukan/pengeluaran lama yang belum terhubung ke akun.
+    pub account_id: Option<i64>,
+    /// Akun lawan, hanya untuk transfer.
+    pub counterparty_account_id: Option<i64>,
+    /// Kategori, hanya untuk pemasukan dan pengeluaran.
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
+    /// Perubahan saldo akun: positif untuk uang masuk, negatif untuk uang keluar.
+    pub amount: Money,
+    pub date: NaiveDateTime,
+    /// Saldo akun setelah entri ini diterapkan; kosong bila entri tidak terhubung ke akun.
+    pub balance_after: Option<Money>,
+}
+
+/// Filter dan halaman untuk buku besar.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct LedgerQuery {
+    /// Hanya entri milik akun ini. Kosong berarti semua akun.
+    pub account_id: Option<i64>,
+    /// Batas awal tanggal (inklusif).
+    pub date_from: Option<NaiveDateTime>,
+    /// Batas akhir tanggal (inklusif).
+    pub date_to: Option<NaiveDateTime>,
+    /// Default terbaru lebih dulu.
+    pub sort_direction: SortDirection,
+    /// Jumlah data per halaman; default 50, maksimal 500.
+    pub limit: Option<u32>,
+    /// Jumlah data yang dilewati dari awal hasil.
+    pub offset: Option<u32>,
+}
+
+impl LedgerQuery {
+    /// Memeriksa rentang tanggal dan ukuran halaman.
+    pub fn validate(&self) -> Result<(), AppError> {
+        validate_date_range(self.date_from, self.date_to)?;
+        validate_limit(self.limit)
+    }
+
+    /// Ukuran halaman yang dipakai untuk query ini.
+    pub fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE)
+    }
+}
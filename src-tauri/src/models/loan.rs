@@ -0,0 +1,129 @@
+use crate::models::money::{Currency, Money};
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Metode perhitungan bunga cicilan. Disimpan sebagai integer 1–2.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum InterestMethod {
+    /// Bunga dihitung dari pokok awal, sama besar setiap bulan (umum untuk kredit motor
+    /// dan paylater).
+    #[default]
+    Flat = 1,
+    /// Bunga efektif dari sisa pokok dengan cicilan tetap (anuitas, umum untuk KPR).
+    Annuity = 2,
+}
+
+/// Syarat pinjaman yang sudah divalidasi, dasar perhitungan tabel angsuran.
+#[derive(Debug, Clone, Copy)]
+pub struct LoanTerms {
+    pub principal: Money,
+    /// Bunga per tahun dalam persen.
+    pub annual_rate: f64,
+    pub installment_count: u32,
+    pub method: InterestMethod,
+    /// Cicilan pertama jatuh tempo sebulan setelah tanggal ini.
+    pub start_date: NaiveDate,
+}
+
+impl LoanTerms {
+    /// Tabel angsuran bulanan. Jatuh tempo mengikuti tanggal `start_date` (digeser ke hari
+    /// terakhir pada bulan yang lebih pendek); selisih pembulatan masuk ke cicilan terakhir
+    /// sehingga total pokok selalu sama dengan `principal`.
+    pub fn amortize(&self) -> Vec<ScheduledInstallment> {
+        let currency = self.principal.currency();
+        let principal = i128::from(self.principal.minor_units());
+        let count = i128::from(self.installment_count);
+        let monthly_rate = self.annual_rate / 100.0 / 12.0;
+
+        // Anuitas: cicilan tetap P·r / (1 − (1 + r)^−n).
+        let payment = (monthly_rate > 0.0).then(|| {
+            let factor = (1.0 + monthly_rate).powi(-(self.installment_count as i32));
+            (principal as f64 * monthly_rate / (1.0 - factor)).round() as i128
+        });
+        // Flat: total bunga P·r·n dibagi rata ke setiap cicilan.
+        let total_flat_interest = (principal as f64 * monthly_rate * count as f64).round() as i128;
+
+        let mut remaining = principal;
+        (1..=self.installment_count)
+            .map(|number| {
+                let (principal_part, interest) = match (self.method, payment) {
+                    (InterestMethod::Annuity, Some(payment)) => {
+                        let interest = (remaining as f64 * monthly_rate).round() as i128;
+                        let principal_part = if number == self.installment_count {
+                            remaining
+                        } else {
+                            (payment - interest).clamp(0, remaining)
+                        };
+                        (principal_part, interest)
+                    }
+                    _ => {
+                        let share = |total: i128| {
+                            if number == self.installment_count {
+                                total - total / count * (count - 1)
+                            } else {
+                                total / count
+                            }
+                        };
+                        (share(principal), share(total_flat_interest))
+                    }
+                };
+                remaining -= principal_part;
+
+                let money = |value: i128| Money::new(value as i64, currency);
+                ScheduledInstallment {
+                    number,
+                    due_date: self
+                        .start_date
+                        .checked_add_months(Months::new(number))
+                        .unwrap_or(NaiveDate::MAX),
+                    principal: money(principal_part),
+                    interest: money(interest),
+                    amount: money(principal_part + interest),
+                    remaining_principal: money(remaining),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Satu baris tabel angsuran.
+#[derive(Serialize, Debug, Clone)]
+pub struct ScheduledInstallment {
+    /// Nomor cicilan, mulai dari 1.
+    pub number: u32,
+    pub due_date: NaiveDate,
+    /// Bagian cicilan yang mengurangi pokok.
+    pub principal: Money,
+    pub interest: Money,
+    /// `principal + interest`.
+    pub amount: Money,
+    /// Sisa pokok setelah cicilan ini dibayar.
+    pub remaining_principal: Money,
+}
+
+/// Input kalkulator simulasi cicilan. `principal` dalam satuan mayor `currency` (default IDR);
+/// `start_date` kosong berarti hari ini.
+#[derive(Deserialize, Debug)]
+pub struct LoanInput {
+    pub principal: f64,
+    #[serde(default)]
+    pub currency: Currency,
+    /// Bunga per tahun dalam persen.
+    pub interest_rate: f64,
+    pub installment_count: u32,
+    #[serde(default)]
+    pub method: InterestMethod,
+    pub start_date: Option<NaiveDate>,
+}
+
+/// Hasil kalkulator: tabel angsuran beserta totalnya.
+#[derive(Serialize, Debug)]
+pub struct LoanSchedule {
+    pub method: InterestMethod,
+    pub installments: Vec<ScheduledInstallment>,
+    pub total_principal: Money,
+    pub total_interest: Money,
+    pub total_amount: Money,
+}
@@ -1,5 +1,20 @@
 // Mendeklarasikan dan mengekspos sub-modul agar bisa diakses dari luar.
 pub mod account;
+pub mod budget;
 pub mod category;
+pub mod debt;
+pub mod default_category;
+pub mod exchange_rate;
 pub mod expense;
+pub mod goal;
 pub mod income;
+pub mod investment;
+pub mod ledger;
+pub mod loan;
+pub mod money;
+pub mod net_worth;
+pub mod recurring;
+pub mod report;
+pub mod search;
+pub mod transaction_query;
+pub mod transfer;
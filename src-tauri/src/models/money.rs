@@ -0,0 +1,386 @@
+use crate::error::AppError;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Mata uang tanpa satuan minor (0 digit desimal).
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "JPY", "KRW", "VND", "CLP", "ISK", "PYG", "UGX", "XAF", "XOF",
+];
+/// Mata uang dengan tiga digit desimal.
+const THREE_DECIMAL_CURRENCIES: &[&str] = &["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+/// Kode mata uang ISO 4217, misalnya `IDR` atau `USD`.
+/// Disimpan sebagai tiga huruf kapital ASCII agar murah untuk disalin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub const IDR: Currency = Currency(*b"IDR");
+
+    /// Mem-parsing kode mata uang tiga huruf (tidak peka huruf besar/kecil).
+    pub fn parse(code: &str) -> Result<Self, AppError> {
+        let bytes = code.trim().as_bytes();
+        if bytes.len() != 3 || !bytes.iter().all(u8::is_ascii_alphabetic) {
+            return Err(AppError::validation(
+                "currency",
+                format!("Kode mata uang tidak valid: '{}'", code),
+            ));
+        }
+        Ok(Currency([
+            bytes[0].to_ascii_uppercase(),
+            bytes[1].to_ascii_uppercase(),
+            bytes[2].to_ascii_uppercase(),
+        ]))
+    }
+
+    pub fn code(&self) -> &str {
+        // Aman: konstruktor hanya menerima huruf ASCII.
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    /// Jumlah digit satuan minor menurut ISO 4217 (misal 2 untuk IDR dan USD, 0 untuk JPY).
+    pub fn minor_digits(&self) -> u32 {
+        let code = self.code();
+        if ZERO_DECIMAL_CURRENCIES.contains(&code) {
+            0
+        } else if THREE_DECIMAL_CURRENCIES.contains(&code) {
+            3
+        } else {
+            2
+        }
+    }
+
+    /// Faktor pengali dari satuan mayor ke satuan minor (10^minor_digits).
+    pub fn scale(&self) -> i64 {
+        10_i64.pow(self.minor_digits())
+    }
+}
+
+/// Ekspresi SQL yang menghasilkan faktor `scale()` untuk kolom mata uang `column`,
+/// sehingga jumlah dalam satuan minor bisa dibandingkan dalam satuan mayor langsung di SQL.
+pub fn scale_sql(column: &str) -> String {
+    let quoted = |codes: &[&str]| {
+        codes
+            .iter()
+            .map(|code| format!("'{}'", code))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!(
+        "(CASE WHEN {col} IN ({zero}) THEN 1 WHEN {col} IN ({three}) THEN 1000 ELSE 100 END)",
+        col = column,
+        zero = quoted(ZERO_DECIMAL_CURRENCIES),
+        three = quoted(THREE_DECIMAL_CURRENCIES),
+    )
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::IDR
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::parse(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+impl ToSql for Currency {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.code()))
+    }
+}
+
+impl FromSql for Currency {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let code = value.as_str()?;
+        Currency::parse(code).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+/// Nilai uang yang disimpan sebagai bilangan bulat dalam satuan minor (misal sen),
+/// sehingga penjumlahan ribuan transaksi tidak mengalami pembulatan seperti `f64`.
+///
+/// Untuk frontend, nilai ini di-serialize sebagai angka biasa dalam satuan mayor
+/// (misal `15000.5`), sama seperti sebelumnya.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(minor_units: i64, currency: Currency) -> Self {
+        Money {
+            minor_units,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    /// Membuat `Money` dari angka satuan mayor (misal 15000.50), dibulatkan ke satuan minor terdekat.
+    pub fn from_major(amount: f64, currency: Currency) -> Result<Self, AppError> {
+        Money::from_major_as(amount, currency, "amount")
+    }
+
+    /// Sama seperti `from_major`, tetapi error validasinya menyebut `field`
+    /// (misal `principal` atau `fee`) agar frontend menandai input yang tepat.
+    pub fn from_major_as(amount: f64, currency: Currency, field: &str) -> Result<Self, AppError> {
+        if !amount.is_finite() {
+            return Err(AppError::validation(field, "Jumlah uang tidak valid."));
+        }
+        let scaled = (amount * currency.scale() as f64).round();
+        if scaled >= i64::MAX as f64 || scaled <= i64::MIN as f64 {
+            return Err(AppError::validation(field, "Jumlah uang terlalu besar."));
+        }
+        Ok(Money::new(scaled as i64, currency))
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Nilai dalam satuan mayor, hanya untuk ditampilkan atau dikirim ke frontend.
+    pub fn to_major(&self) -> f64 {
+        self.minor_units as f64 / self.currency.scale() as f64
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.minor_units > 0
+    }
+
+    /// Nilai yang sama dengan tanda berlawanan.
+    pub fn negated(self) -> Money {
+        Money::new(-self.minor_units, self.currency)
+    }
+
+    /// Menjumlahkan dua nilai dengan mata uang yang sama.
+    pub fn checked_add(self, other: Money) -> Result<Money, AppError> {
+        self.ensure_same_currency(other)?;
+        self.minor_units
+            .checked_add(other.minor_units)
+            .map(|v| Money::new(v, self.currency))
+            .ok_or_else(|| AppError::validation("amount", "Jumlah uang terlalu besar."))
+    }
+
+    /// Mengurangkan dua nilai dengan mata uang yang sama.
+    pub fn checked_sub(self, other: Money) -> Result<Money, AppError> {
+        self.ensure_same_currency(other)?;
+        self.minor_units
+            .checked_sub(other.minor_units)
+            .map(|v| Money::new(v, self.currency))
+            .ok_or_else(|| AppError::validation("amount", "Jumlah uang terlalu besar."))
+    }
+
+    /// Mengonversi ke mata uang lain dengan kurs `1 self.currency = rate target`,
+    /// dibulatkan ke satuan minor terdekat pada mata uang tujuan.
+    pub fn convert(self, rate: f64, target: Currency) -> Result<Money, AppError> {
+        if self.currency == target {
+            return Ok(self);
+        }
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(AppError::validation(
+                "rate",
+                format!("Kurs tidak valid: {}", rate),
+            ));
+        }
+        let scaled = (self.minor_units as f64 * rate * target.scale() as f64
+            / self.currency.scale() as f64)
+            .round();
+        if scaled >= i64::MAX as f64 || scaled <= i64::MIN as f64 {
+            return Err(AppError::validation("amount", "Jumlah uang terlalu besar."));
+        }
+        Ok(Money::new(scaled as i64, target))
+    }
+
+    fn ensure_same_currency(&self, other: Money) -> Result<(), AppError> {
+        if self.currency != other.currency {
+            return Err(AppError::validation(
+                "currency",
+                format!(
+                    "Mata uang tidak sama: {} dan {}",
+                    self.currency, other.currency
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.currency.minor_digits() as usize;
+        let scale = self.currency.scale().unsigned_abs();
+        let abs = self.minor_units.unsigned_abs();
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+
+        if digits == 0 {
+            write!(f, "{} {}{}", self.currency, sign, abs)
+        } else {
+            write!(
+                f,
+                "{} {}{}.{:0width$}",
+                self.currency,
+                sign,
+                abs / scale,
+                abs % scale,
+                width = digits
+            )
+        }
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_major())
+    }
+}
+
+/// Menerima angka satuan mayor dari frontend dalam mata uang default (IDR).
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let amount = f64::deserialize(deserializer)?;
+        Money::from_major(amount, Currency::default()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currency(code: &str) -> Currency {
+        Currency::parse(code).unwrap()
+    }
+
+    #[test]
+    fn from_major_rounds_to_nearest_minor_unit() {
+        assert_eq!(
+            Money::from_major(15_000.5, Currency::IDR).unwrap(),
+            Money::new(1_500_050, Currency::IDR)
+        );
+        assert_eq!(
+            Money::from_major(0.125, Currency::IDR)
+                .unwrap()
+                .minor_units(),
+            13
+        );
+        assert_eq!(
+            Money::from_major(-0.125, Currency::IDR)
+                .unwrap()
+                .minor_units(),
+            -13
+        );
+        assert_eq!(
+            Money::from_major(1_234.6, currency("JPY"))
+                .unwrap()
+                .minor_units(),
+            1_235
+        );
+        assert_eq!(
+            Money::from_major(1.2346, currency("KWD"))
+                .unwrap()
+                .minor_units(),
+            1_235
+        );
+    }
+
+    #[test]
+    fn from_major_rejects_overflow_and_non_finite_values() {
+        let err = Money::from_major(1e17, Currency::IDR).unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+        let err = Money::from_major(f64::NAN, Currency::IDR).unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+        let err = Money::from_major(f64::NEG_INFINITY, Currency::IDR).unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+
+        let err = Money::from_major_as(-1e17, Currency::IDR, "fee").unwrap_err();
+        assert_eq!(err.field(), Some("fee"));
+        // Batas yang masih muat di i64 tetap diterima.
+        assert!(Money::from_major(1e16, Currency::IDR).is_ok());
+    }
+
+    #[test]
+    fn to_major_uses_currency_scale() {
+        assert_eq!(Money::new(150_050, Currency::IDR).to_major(), 1_500.5);
+        assert_eq!(Money::new(-5, Currency::IDR).to_major(), -0.05);
+        assert_eq!(Money::new(1_500, currency("JPY")).to_major(), 1_500.0);
+        assert_eq!(Money::new(1_005, currency("KWD")).to_major(), 1.005);
+    }
+
+    #[test]
+    fn currency_parse_normalizes_and_validates_codes() {
+        assert_eq!(currency(" usd ").code(), "USD");
+        assert_eq!(currency("idr"), Currency::IDR);
+        for code in ["", "US", "USDT", "U5D", "RP$"] {
+            let err = Currency::parse(code).unwrap_err();
+            assert_eq!(err.field(), Some("currency"), "kode {:?}", code);
+        }
+    }
+
+    #[test]
+    fn minor_digits_follow_iso_4217() {
+        assert_eq!(Currency::IDR.minor_digits(), 2);
+        assert_eq!(Currency::IDR.scale(), 100);
+        assert_eq!(currency("JPY").minor_digits(), 0);
+        assert_eq!(currency("JPY").scale(), 1);
+        assert_eq!(currency("KWD").minor_digits(), 3);
+        assert_eq!(currency("KWD").scale(), 1_000);
+    }
+
+    #[test]
+    fn display_pads_minor_units() {
+        assert_eq!(
+            Money::new(150_050, Currency::IDR).to_string(),
+            "IDR 1500.50"
+        );
+        assert_eq!(Money::new(-5, Currency::IDR).to_string(), "IDR -0.05");
+        assert_eq!(Money::new(1_500, currency("JPY")).to_string(), "JPY 1500");
+        assert_eq!(Money::new(1_005, currency("KWD")).to_string(), "KWD 1.005");
+        assert_eq!(currency("usd").to_string(), "USD");
+    }
+
+    #[test]
+    fn serde_uses_major_units_and_currency_codes() {
+        let money = Money::new(150_050, Currency::IDR);
+        assert_eq!(serde_json::to_string(&money).unwrap(), "1500.5");
+        assert_eq!(serde_json::from_str::<Money>("1500.5").unwrap(), money);
+        assert!(serde_json::from_str::<Money>("\"1500\"").is_err());
+
+        assert_eq!(serde_json::to_string(&currency("USD")).unwrap(), "\"USD\"");
+        assert_eq!(
+            serde_json::from_str::<Currency>("\"jpy\"").unwrap(),
+            currency("JPY")
+        );
+        assert!(serde_json::from_str::<Currency>("\"rupiah\"").is_err());
+    }
+}
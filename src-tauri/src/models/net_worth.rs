@@ -0,0 +1,64 @@
+use crate::models::money::{Currency, Money};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Rincian aset dan kewajiban dalam satu mata uang pada satu tanggal, tanpa konversi.
+/// Semua kewajiban disimpan sebagai nilai positif.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct NetWorthSnapshot {
+    pub date: NaiveDate,
+    pub currency: Currency,
+    /// Saldo positif akun kas.
+    pub cash: Money,
+    /// Kas akun investasi ditambah nilai pasar kepemilikannya (atau biaya perolehan bila
+    /// belum ada harga).
+    pub investments: Money,
+    /// Sisa pokok piutang.
+    pub receivables: Money,
+    /// Sisa pokok utang.
+    pub debts: Money,
+    /// Saldo negatif akun kas, misal kartu kredit.
+    pub overdrafts: Money,
+}
+
+impl NetWorthSnapshot {
+    pub fn zero(date: NaiveDate, currency: Currency) -> Self {
+        let zero = Money::zero(currency);
+        NetWorthSnapshot {
+            date,
+            currency,
+            cash: zero,
+            investments: zero,
+            receivables: zero,
+            debts: zero,
+            overdrafts: zero,
+        }
+    }
+}
+
+/// Kekayaan bersih dalam mata uang pelaporan, dengan rincian aset dan kewajiban.
+#[derive(Serialize, Debug)]
+pub struct NetWorth {
+    pub date: NaiveDate,
+    pub currency: Currency,
+    pub cash: Money,
+    pub investments: Money,
+    pub receivables: Money,
+    /// `cash + investments + receivables`.
+    pub total_assets: Money,
+    pub debts: Money,
+    pub overdrafts: Money,
+    /// `debts + overdrafts`.
+    pub total_liabilities: Money,
+    /// `total_assets - total_liabilities`.
+    pub net_worth: Money,
+}
+
+/// Satu titik riwayat kekayaan bersih, diambil dari cuplikan terakhir dalam periodenya.
+#[derive(Serialize, Debug)]
+pub struct NetWorthPoint {
+    /// Label periode dengan format yang sama seperti laporan per periode.
+    pub period: String,
+    #[serde(flatten)]
+    pub net_worth: NetWorth,
+}
@@ -0,0 +1,212 @@
+use crate::models::category::CategoryType;
+use crate::models::money::{Currency, Money};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Satuan pengulangan jadwal. Disimpan sebagai integer 1–4.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum RecurrenceFrequency {
+    Daily = 1,
+    Weekly = 2,
+    Monthly = 3,
+    Yearly = 4,
+}
+
+/// Jadwal pengulangan, dimulai dari `start_date`.
+///
+/// Jadwal bulanan jatuh pada tanggal yang sama dengan `start_date` (tanggal 31 menjadi
+/// hari terakhir pada bulan yang lebih pendek), atau pada hari ke-n dalam bulan bila
+/// `weekday` dan `week_of_month` diisi, misal "Jumat terakhir setiap bulan".
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub frequency: RecurrenceFrequency,
+    /// Setiap berapa satuan `frequency`, misal 2 untuk "setiap dua minggu".
+    pub interval: u32,
+    pub start_date: NaiveDate,
+    /// Tanggal terakhir yang boleh dijadwalkan (inklusif).
+    pub end_date: Option<NaiveDate>,
+    /// Jumlah kejadian maksimal, termasuk yang dilewati.
+    pub max_occurrences: Option<u32>,
+    /// Hari untuk jadwal bulanan hari ke-n: 0 = Senin sampai 6 = Minggu.
+    pub weekday: Option<u8>,
+    /// Minggu ke-1 sampai ke-4 dalam bulan, atau -1 untuk minggu terakhir.
+    pub week_of_month: Option<i8>,
+}
+
+impl Schedule {
+    /// Semua tanggal kejadian secara berurutan. Tanpa `end_date` dan `max_occurrences`
+    /// deretnya tidak berujung, jadi pemanggil harus membatasinya sendiri.
+    pub fn occurrences(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        let mut step = 0u32;
+        let mut emitted = 0u32;
+        std::iter::from_fn(move || loop {
+            if self.max_occurrences.is_some_and(|max| emitted >= max) {
+                return None;
+            }
+            let date = self.candidate(step)?;
+            step = step.checked_add(1)?;
+            // Hari ke-n pada bulan pertama bisa jatuh sebelum tanggal mulai.
+            if date < self.start_date {
+                continue;
+            }
+            if self.end_date.is_some_and(|end| date > end) {
+                return None;
+            }
+            emitted += 1;
+            return Some(date);
+        })
+    }
+
+    /// Apakah `date` termasuk salah satu tanggal kejadian jadwal ini.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.occurrences()
+            .take_while(|occurrence| *occurrence <= date)
+            .any(|occurrence| occurrence == date)
+    }
+
+    /// Tanggal untuk langkah ke-`step` dari `start_date`, sebelum dibatasi tanggal mulai
+    /// dan akhir. `None` bila tanggalnya di luar rentang yang bisa direpresentasikan.
+    fn candidate(&self, step: u32) -> Option<NaiveDate> {
+        let offset = step.checked_mul(self.interval)?;
+        let start = self.start_date;
+        match self.frequency {
+            RecurrenceFrequency::Daily => start.checked_add_days(Days::new(offset.into())),
+            RecurrenceFrequency::Weekly => start.checked_add_days(Days::new(u64::from(offset) * 7)),
+            RecurrenceFrequency::Monthly => {
+                let month = start.with_day(1)?.checked_add_months(Months::new(offset))?;
+                match (self.weekday, self.week_of_month) {
+                    (Some(weekday), Some(week)) => nth_weekday(month, weekday, week),
+                    _ => clamp_day(month, start.day()),
+                }
+            }
+            RecurrenceFrequency::Yearly => {
+                let month = start
+                    .with_day(1)?
+                    .checked_add_months(Months::new(offset.checked_mul(12)?))?;
+                clamp_day(month, start.day())
+            }
+        }
+    }
+}
+
+/// Tanggal `day` pada bulan yang dimulai `first`, atau hari terakhir bulan itu.
+fn clamp_day(first: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+    first.with_day(day.min(last.day()))
+}
+
+/// Hari `weekday` (0 = Senin) ke-`week` pada bulan yang dimulai `first`;
+/// `week` -1 berarti yang terakhir dalam bulan itu.
+fn nth_weekday(first: NaiveDate, weekday: u8, week: i8) -> Option<NaiveDate> {
+    let weekday = u64::from(weekday);
+    if week < 0 {
+        let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+        let back = (u64::from(last.weekday().num_days_from_monday()) + 7 - weekday) % 7;
+        return last.checked_sub_days(Days::new(back));
+    }
+    let forward = (weekday + 7 - u64::from(first.weekday().num_days_from_monday())) % 7;
+    let weeks = u64::try_from(week).ok()?.checked_sub(1)?;
+    first.checked_add_days(Days::new(forward + weeks * 7))
+}
+
+/// Aturan transaksi berulang: pemasukan atau pengeluaran yang dibuat otomatis sesuai jadwal.
+#[derive(Serialize, Debug)]
+pub struct RecurringRule {
+    pub id: i64,
+    /// Income untuk pemasukan, Expense untuk pengeluaran.
+    pub transaction_type: CategoryType,
+    pub description: String,
+    pub amount: Money,
+    pub currency: Currency,
+    pub category_id: i64,
+    pub account_id: i64,
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    /// Kejadian yang dijadwalkan sebelum tanggal ini tidak dibuat otomatis. Diisi saat
+    /// jadwal aturan yang sudah pernah membuat transaksi diubah.
+    pub materialize_from: Option<NaiveDate>,
+}
+
+/// Data aturan yang sudah divalidasi oleh service.
+#[derive(Debug)]
+pub struct NewRecurringRule<'a> {
+    pub transaction_type: CategoryType,
+    pub description: &'a str,
+    pub amount: Money,
+    pub category_id: i64,
+    pub account_id: i64,
+    pub schedule: Schedule,
+}
+
+/// Input aturan dari frontend. `transaction_type`: 1 untuk pemasukan, 2 untuk pengeluaran.
+/// Jumlah dalam satuan mayor mata uang akun.
+#[derive(Deserialize, Debug)]
+pub struct RecurringRuleInput {
+    pub transaction_type: i16,
+    pub description: String,
+    pub amount: f64,
+    pub category_id: i64,
+    pub account_id: i64,
+    pub frequency: RecurrenceFrequency,
+    /// Default 1.
+    pub interval: Option<u32>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub max_occurrences: Option<u32>,
+    pub weekday: Option<u8>,
+    pub week_of_month: Option<i8>,
+}
+
+/// Status satu kejadian. Disimpan sebagai integer 1–3; `Pending` tidak disimpan.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum OccurrenceStatus {
+    /// Belum dibuat dan tidak diubah.
+    Pending = 0,
+    /// Transaksinya sudah dibuat.
+    Created = 1,
+    /// Dilewati; transaksinya tidak akan dibuat.
+    Skipped = 2,
+    /// Belum dibuat, dengan perubahan khusus untuk kejadian ini.
+    Edited = 3,
+}
+
+/// Catatan tersimpan untuk satu kejadian. Field opsional hanya diisi untuk `Edited`.
+#[derive(Debug, Clone)]
+pub struct OccurrenceRecord {
+    pub rule_id: i64,
+    pub scheduled_date: NaiveDate,
+    pub status: OccurrenceStatus,
+    pub description: Option<String>,
+    pub amount: Option<Money>,
+    pub date: Option<NaiveDate>,
+    /// ID pemasukan/pengeluaran yang dibuat, untuk `Created`.
+    pub entry_id: Option<i64>,
+}
+
+/// Satu kejadian aturan berulang, untuk pratinjau.
+#[derive(Serialize, Debug)]
+pub struct RecurringOccurrence {
+    pub rule_id: i64,
+    /// Tanggal menurut jadwal; dipakai untuk melewati atau mengubah kejadian ini.
+    pub scheduled_date: NaiveDate,
+    /// Tanggal transaksi, berbeda dari `scheduled_date` bila kejadian ini dipindahkan.
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: Money,
+    pub status: OccurrenceStatus,
+    pub entry_id: Option<i64>,
+}
+
+/// Perubahan untuk satu kejadian yang belum dibuat. Field kosong memakai nilai aturan.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct OccurrenceEditInput {
+    pub description: Option<String>,
+    /// Dalam satuan mayor mata uang aturan.
+    pub amount: Option<f64>,
+    pub date: Option<NaiveDate>,
+}
@@ -0,0 +1,86 @@
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::money::{Currency, Money};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Satuan periode untuk total per periode.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportPeriod {
+    /// Label `YYYY-MM-DD`.
+    Day,
+    /// Minggu ISO 8601, label `YYYY-Www` (misal `2024-W18`).
+    Week,
+    /// Label `YYYY-MM`.
+    Month,
+    /// Label `YYYY`.
+    Year,
+}
+
+/// Filter yang berlaku untuk semua laporan.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ReportQuery {
+    /// Tanggal awal (inklusif).
+    pub date_from: Option<NaiveDate>,
+    /// Tanggal akhir (inklusif, sampai akhir hari).
+    pub date_to: Option<NaiveDate>,
+    /// Hanya transaksi dari salah satu akun ini. Kosong berarti semua akun.
+    pub account_ids: Vec<i64>,
+}
+
+impl ReportQuery {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let (Some(from), Some(to)) = (self.date_from, self.date_to) {
+            if from > to {
+                return Err(AppError::validation(
+                    "date_to",
+                    "Tanggal akhir tidak boleh sebelum tanggal awal.",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Total pemasukan dan pengeluaran. Transaksi dijumlahkan per mata uang tanpa konversi,
+/// sehingga setiap laporan bisa berisi beberapa baris untuk periode/kelompok yang sama.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct IncomeExpenseTotal {
+    pub currency: Currency,
+    pub income: Money,
+    pub expense: Money,
+    /// `income - expense`.
+    pub net: Money,
+}
+
+/// Total pemasukan dan pengeluaran dalam satu periode.
+#[derive(Serialize, Debug)]
+pub struct PeriodTotal {
+    pub period: String,
+    #[serde(flatten)]
+    pub totals: IncomeExpenseTotal,
+}
+
+/// Total pemasukan dan pengeluaran satu akun.
+#[derive(Serialize, Debug)]
+pub struct AccountTotal {
+    pub account_id: i64,
+    pub account_name: String,
+    #[serde(flatten)]
+    pub totals: IncomeExpenseTotal,
+}
+
+/// Total transaksi dalam satu kategori. Pada laporan rollup, total dan jumlah transaksi
+/// sudah termasuk seluruh sub-kategorinya.
+#[derive(Serialize, Debug)]
+pub struct CategoryTotal {
+    pub category_id: i64,
+    pub category_name: String,
+    pub category_type: CategoryType,
+    /// Induk kategori, agar hasil rollup bisa disusun ulang menjadi pohon.
+    pub parent_id: Option<i64>,
+    pub total: Money,
+    pub transaction_count: i64,
+}
@@ -0,0 +1,21 @@
+use crate::models::ledger::LedgerEntryType;
+use crate::models::money::Money;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+/// Satu hasil pencarian teks penuh: pemasukan atau pengeluaran yang cocok.
+#[derive(Serialize, Debug)]
+pub struct SearchResult {
+    /// `income` atau `expense`.
+    pub entry_type: LedgerEntryType,
+    /// ID di tabel `incomes` atau `expenses`.
+    pub entry_id: i64,
+    pub description: String,
+    /// Potongan deskripsi yang sudah di-escape untuk HTML, dengan kata yang cocok
+    /// dibungkus `<mark>...</mark>`.
+    pub snippet: String,
+    pub amount: Money,
+    pub date: NaiveDateTime,
+    pub category_id: Option<i64>,
+    pub account_id: Option<i64>,
+}
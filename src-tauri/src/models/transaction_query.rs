@@ -0,0 +1,123 @@
+use crate::error::AppError;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Jumlah data per halaman bila `limit` tidak diisi.
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+/// Batas atas `limit` agar satu permintaan tidak memuat seluruh tabel.
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+/// Kolom yang bisa dipakai untuk mengurutkan daftar transaksi.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Date,
+    Amount,
+    Description,
+    Category,
+}
+
+/// Arah pengurutan.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Filter, urutan, dan halaman untuk daftar pemasukan atau pengeluaran.
+/// Semua field opsional; query kosong mengembalikan halaman pertama, terbaru lebih dulu.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TransactionQuery {
+    /// Batas awal tanggal (inklusif).
+    pub date_from: Option<NaiveDateTime>,
+    /// Batas akhir tanggal (inklusif).
+    pub date_to: Option<NaiveDateTime>,
+    /// Hanya transaksi dengan salah satu kategori ini. Kosong berarti semua kategori.
+    pub category_ids: Vec<i64>,
+    /// Hanya transaksi dari salah satu akun ini. Kosong berarti semua akun.
+    pub account_ids: Vec<i64>,
+    /// Jumlah minimum dalam satuan mayor mata uang transaksi (inklusif).
+    pub min_amount: Option<f64>,
+    /// Jumlah maksimum dalam satuan mayor mata uang transaksi (inklusif).
+    pub max_amount: Option<f64>,
+    /// Potongan teks yang dicari pada deskripsi (tidak peka huruf besar/kecil).
+    pub search: Option<String>,
+    pub sort_by: SortKey,
+    pub sort_direction: SortDirection,
+    /// Jumlah data per halaman; default 50, maksimal 500.
+    pub limit: Option<u32>,
+    /// Jumlah data yang dilewati dari awal hasil.
+    pub offset: Option<u32>,
+}
+
+impl TransactionQuery {
+    /// Memeriksa bahwa rentang tanggal, rentang jumlah, dan ukuran halaman masuk akal.
+    pub fn validate(&self) -> Result<(), AppError> {
+        validate_date_range(self.date_from, self.date_to)?;
+        for (field, amount) in [
+            ("min_amount", self.min_amount),
+            ("max_amount", self.max_amount),
+        ] {
+            if matches!(amount, Some(value) if !value.is_finite() || value < 0.0) {
+                return Err(AppError::validation(
+                    field,
+                    "Batas jumlah harus berupa angka yang tidak negatif.",
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(AppError::validation(
+                    "max_amount",
+                    "Jumlah maksimum tidak boleh lebih kecil dari jumlah minimum.",
+                ));
+            }
+        }
+        validate_limit(self.limit)
+    }
+
+    /// Ukuran halaman yang dipakai untuk query ini.
+    pub fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE)
+    }
+}
+
+/// Memastikan tanggal akhir tidak sebelum tanggal awal.
+pub fn validate_date_range(
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+) -> Result<(), AppError> {
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(AppError::validation(
+                "date_to",
+                "Tanggal akhir tidak boleh sebelum tanggal awal.",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Memastikan ukuran halaman yang diminta berada di antara 1 dan `MAX_PAGE_SIZE`.
+pub fn validate_limit(limit: Option<u32>) -> Result<(), AppError> {
+    if matches!(limit, Some(limit) if limit == 0 || limit > MAX_PAGE_SIZE) {
+        return Err(AppError::validation(
+            "limit",
+            format!("Ukuran halaman harus antara 1 dan {}.", MAX_PAGE_SIZE),
+        ));
+    }
+    Ok(())
+}
+
+/// Satu halaman hasil query beserta jumlah seluruh data yang cocok dengan filter.
+#[derive(Serialize, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total_count: i64,
+    pub limit: u32,
+    pub offset: u32,
+}
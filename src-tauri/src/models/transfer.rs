@@ -0,0 +1,52 @@
+use crate::models::money::{Currency, Money};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Merepresentasikan perpindahan uang antar akun (misal dari rekening bank ke e-wallet).
+/// Transfer bukan pemasukan maupun pengeluaran, sehingga tidak ikut dihitung dalam
+/// total pemasukan/pengeluaran.
+#[derive(Serialize, Debug)]
+pub struct Transfer {
+    pub id: i64,
+    pub description: Option<String>,
+    pub from_account_id: i64,
+    pub to_account_id: i64,
+    /// Jumlah yang dikirim, dalam mata uang akun asal.
+    pub amount: Money,
+    pub currency: Currency,
+    /// Biaya transfer, ikut mengurangi saldo akun asal.
+    pub fee: Money,
+    /// Kurs yang dipakai: `1 currency = rate received_currency` (1 jika mata uangnya sama).
+    pub rate: f64,
+    /// Jumlah yang diterima, dalam mata uang akun tujuan.
+    pub received_amount: Money,
+    pub received_currency: Currency,
+    pub date: NaiveDateTime,
+}
+
+/// Data untuk membuat atau memperbarui transfer. Nilai-nilainya sudah divalidasi
+/// oleh service sebelum diteruskan ke repository.
+#[derive(Debug)]
+pub struct NewTransfer<'a> {
+    pub description: Option<&'a str>,
+    pub from_account_id: i64,
+    pub to_account_id: i64,
+    pub amount: Money,
+    pub fee: Money,
+    pub rate: f64,
+    pub received_amount: Money,
+    pub date: NaiveDateTime,
+}
+
+/// Input transfer dari frontend. Jumlah dalam satuan mayor mata uang akun asal;
+/// `rate` wajib diisi bila mata uang akun asal dan tujuan berbeda.
+#[derive(Deserialize, Debug)]
+pub struct TransferInput {
+    pub description: Option<String>,
+    pub from_account_id: i64,
+    pub to_account_id: i64,
+    pub amount: f64,
+    pub fee: Option<f64>,
+    pub rate: Option<f64>,
+    pub date: NaiveDateTime,
+}
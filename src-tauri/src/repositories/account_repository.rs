@@ -1,74 +1,274 @@
-use crate::db::DB_CONNECTION;
-use crate::models::account::Account;
-use rusqlite::{params, Result};
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::account::{Account, AccountType};
+use crate::models::money::{Currency, Money};
+use rusqlite::{params, Connection, Row};
+use std::error::Error;
+use std::fmt;
 
-/// Membuat entri akun baru di database.
-pub fn create(name: &str, description: Option<&str>, balance: f64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute(
-        "INSERT INTO accounts (name, description, balance) VALUES (?1, ?2, ?3)",
-        params![name, description, balance],
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "accounts";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "name",
+    "description",
+    "balance",
+    "currency",
+    "opening_balance",
+    "account_type",
+];
+
+#[derive(Debug)]
+pub struct InvalidAccountTypeError(i16);
+
+impl fmt::Display for InvalidAccountTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk AccountType: {}", self.0)
+    }
 }
 
-/// Mengambil semua data akun dari database.
-pub fn find_all() -> Result<Vec<Account>, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    let mut stmt = conn
-        .prepare("SELECT id, name, description, balance FROM accounts")
-        .map_err(|e| e.to_string())?;
-
-    let account_iter = stmt
-        .query_map([], |row| {
-            Ok(Account {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?, // rusqlite akan menangani Option<String> untuk kolom TEXT yang nullable
-                balance: row.get(3)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+impl Error for InvalidAccountTypeError {}
+
+impl TryFrom<i16> for AccountType {
+    type Error = InvalidAccountTypeError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(AccountType::Cash),
+            2 => Ok(AccountType::Investment),
+            _ => Err(InvalidAccountTypeError(value)),
+        }
+    }
+}
+
+/// Operasi penyimpanan data akun.
+pub trait AccountRepository {
+    /// Membuat entri akun baru. Saldo awal sekaligus menjadi saldo pembuka
+    /// yang dipakai saat menghitung ulang saldo dari riwayat transaksi.
+    fn create(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        balance: Money,
+        account_type: AccountType,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua data akun.
+    fn find_all(&self) -> Result<Vec<Account>, AppError>;
+
+    /// Mengambil satu data akun berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Account, AppError>;
 
-    account_iter
-        .collect::<rusqlite::Result<Vec<Account>>>()
-        .map_err(|e| e.to_string())
+    /// Memperbarui data akun yang ada. Perubahan saldo dicatat sebagai koreksi pada
+    /// saldo pembuka, sehingga `recalculate_balance` tetap menghasilkan saldo yang sama.
+    fn update(
+        &self,
+        id: i64,
+        name: &str,
+        description: Option<&str>,
+        balance: Money,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus data akun berdasarkan ID.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Menghitung ulang saldo akun dari saldo pembuka ditambah seluruh riwayat transaksinya,
+    /// termasuk transfer keluar (jumlah + biaya), transfer masuk, dan transaksi investasi.
+    fn recalculate_balance(&self, id: i64) -> Result<(), AppError>;
+
+    /// Menghitung jumlah transaksi (termasuk transfer dan transaksi investasi) yang terhubung
+    /// ke akun.
+    fn count_transactions(&self, id: i64) -> Result<i64, AppError>;
 }
 
-/// Mengambil satu data akun berdasarkan ID.
-pub fn find_by_id(id: i64) -> Result<Account, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.query_row(
-        "SELECT id, name, description, balance FROM accounts WHERE id = ?1",
-        params![id],
-        |row| {
-            Ok(Account {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                balance: row.get(3)?,
-            })
-        },
-    )
-    .map_err(|e| e.to_string())
+/// Implementasi `AccountRepository` di atas SQLite.
+pub struct SqliteAccountRepository<'a> {
+    db: &'a Database,
 }
 
-/// Memperbarui data akun yang ada di database.
-pub fn update(id: i64, name: &str, description: Option<&str>, balance: f64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute(
-        "UPDATE accounts SET name = ?1, description = ?2, balance = ?3 WHERE id = ?4",
-        params![name, description, balance, id],
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+impl<'a> SqliteAccountRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteAccountRepository { db }
+    }
+}
+
+const SELECT_ACCOUNTS: &str =
+    "SELECT id, name, description, balance, currency, opening_balance, account_type FROM accounts";
+
+/// Memetakan satu baris hasil query `SELECT_ACCOUNTS` ke `Account`.
+fn map_row(row: &Row) -> rusqlite::Result<Account> {
+    let currency: Currency = row.get(4)?;
+    let type_val: i16 = row.get(6)?;
+    let account_type = AccountType::try_from(type_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    Ok(Account {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?, // rusqlite akan menangani Option<String> untuk kolom TEXT yang nullable
+        balance: Money::new(row.get(3)?, currency),
+        currency,
+        opening_balance: Money::new(row.get(5)?, currency),
+        account_type,
+    })
+}
+
+impl AccountRepository for SqliteAccountRepository<'_> {
+    fn create(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        balance: Money,
+        account_type: AccountType,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO accounts (name, description, balance, currency, opening_balance,
+                                       account_type)
+                 VALUES (?1, ?2, ?3, ?4, ?3, ?5)",
+                params![
+                    name,
+                    description,
+                    balance.minor_units(),
+                    balance.currency(),
+                    account_type as i16
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Account>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(SELECT_ACCOUNTS)?;
+
+            let account_iter = stmt.query_map([], map_row)?;
+
+            account_iter
+                .collect::<rusqlite::Result<Vec<Account>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Account, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_ACCOUNTS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Akun", id))
+        })
+    }
+
+    fn update(
+        &self,
+        id: i64,
+        name: &str,
+        description: Option<&str>,
+        balance: Money,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE accounts
+                 SET name = ?1, description = ?2, currency = ?4,
+                     opening_balance = opening_balance + (?3 - balance), balance = ?3
+                 WHERE id = ?5",
+                params![
+                    name,
+                    description,
+                    balance.minor_units(),
+                    balance.currency(),
+                    id
+                ],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Akun", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let deleted = conn.execute("DELETE FROM accounts WHERE id = ?1", params![id])?;
+
+            if deleted == 0 {
+                return Err(AppError::not_found("Akun", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn recalculate_balance(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE accounts SET balance = opening_balance
+                     + COALESCE((SELECT SUM(amount) FROM incomes WHERE account_id = accounts.id), 0)
+                     - COALESCE((SELECT SUM(amount) FROM expenses WHERE account_id = accounts.id), 0)
+                     - COALESCE((SELECT SUM(amount + fee) FROM transfers
+                                 WHERE from_account_id = accounts.id), 0)
+                     + COALESCE((SELECT SUM(received_amount) FROM transfers
+                                 WHERE to_account_id = accounts.id), 0)
+                     + COALESCE((SELECT SUM(CASE kind WHEN 1 THEN -(amount + fee)
+                                                      ELSE amount - fee END)
+                                 FROM investment_transactions
+                                 WHERE account_id = accounts.id), 0)
+                     + COALESCE((SELECT SUM(CASE direction WHEN 1 THEN principal
+                                                           ELSE -principal END)
+                                 FROM debts WHERE account_id = accounts.id), 0)
+                 WHERE id = ?1",
+                params![id],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Akun", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn count_transactions(&self, id: i64) -> Result<i64, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT (SELECT COUNT(*) FROM incomes WHERE account_id = ?1)
+                      + (SELECT COUNT(*) FROM expenses WHERE account_id = ?1)
+                      + (SELECT COUNT(*) FROM transfers
+                         WHERE from_account_id = ?1 OR to_account_id = ?1)
+                      + (SELECT COUNT(*) FROM investment_transactions WHERE account_id = ?1)
+                      + (SELECT COUNT(*) FROM debts WHERE account_id = ?1)",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(AppError::from)
+        })
+    }
 }
 
-/// Menghapus data akun dari database berdasarkan ID.
-pub fn delete(id: i64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute("DELETE FROM accounts WHERE id = ?1", params![id])
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+/// Menambahkan `delta` ke saldo akun. Dipanggil oleh repository transaksi di dalam
+/// transaksi SQLite yang sama dengan perubahan datanya, sehingga saldo selalu konsisten.
+/// Gagal jika akun tidak ada atau mata uangnya berbeda dengan `delta`.
+pub fn adjust_balance(conn: &Connection, account_id: i64, delta: Money) -> Result<(), AppError> {
+    let currency: Currency = conn
+        .query_row(
+            "SELECT currency FROM accounts WHERE id = ?1",
+            params![account_id],
+            |row| row.get(0),
+        )
+        .map_err(not_found_as("Akun", account_id))?;
+
+    if currency != delta.currency() {
+        return Err(AppError::Conflict(format!(
+            "Mata uang transaksi ({}) berbeda dengan mata uang akun ({}).",
+            delta.currency(),
+            currency
+        )));
+    }
+
+    conn.execute(
+        "UPDATE accounts SET balance = balance + ?1 WHERE id = ?2",
+        params![delta.minor_units(), account_id],
+    )?;
+    Ok(())
 }
@@ -0,0 +1,250 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::budget::{Budget, BudgetPeriod, NewBudget};
+use crate::models::money::{Currency, Money};
+use chrono::NaiveDate;
+use rusqlite::{params, Row};
+use std::error::Error;
+use std::fmt;
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "budgets";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "category_id",
+    "period",
+    "amount",
+    "currency",
+    "rollover",
+    "start_date",
+];
+
+#[derive(Debug)]
+pub struct InvalidBudgetPeriodError(i16);
+
+impl fmt::Display for InvalidBudgetPeriodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk BudgetPeriod: {}", self.0)
+    }
+}
+
+impl Error for InvalidBudgetPeriodError {}
+
+impl TryFrom<i16> for BudgetPeriod {
+    type Error = InvalidBudgetPeriodError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(BudgetPeriod::Weekly),
+            2 => Ok(BudgetPeriod::Monthly),
+            3 => Ok(BudgetPeriod::Yearly),
+            _ => Err(InvalidBudgetPeriodError(value)),
+        }
+    }
+}
+
+/// Operasi penyimpanan data anggaran.
+pub trait BudgetRepository {
+    /// Membuat anggaran baru. Gagal dengan `Conflict` bila kategori tersebut sudah
+    /// memiliki anggaran dengan periode dan mata uang yang sama.
+    fn create(&self, budget: &NewBudget) -> Result<(), AppError>;
+
+    /// Mengambil semua anggaran.
+    fn find_all(&self) -> Result<Vec<Budget>, AppError>;
+
+    /// Mengambil satu anggaran berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Budget, AppError>;
+
+    /// Memperbarui anggaran yang ada.
+    fn update(&self, id: i64, budget: &NewBudget) -> Result<(), AppError>;
+
+    /// Menghapus anggaran berdasarkan ID.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Total pengeluaran kategori `category_id` beserta seluruh sub-kategorinya dalam
+    /// `currency`, dikelompokkan per `period`, untuk tanggal `from` sampai sebelum `until`.
+    /// Setiap baris berisi tanggal awal periode dan totalnya, terlama lebih dulu;
+    /// periode tanpa pengeluaran tidak muncul.
+    fn spent_by_period(
+        &self,
+        category_id: i64,
+        currency: Currency,
+        period: BudgetPeriod,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, Money)>, AppError>;
+}
+
+/// Implementasi `BudgetRepository` di atas SQLite.
+pub struct SqliteBudgetRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteBudgetRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteBudgetRepository { db }
+    }
+}
+
+const SELECT_BUDGETS: &str =
+    "SELECT id, category_id, period, amount, currency, rollover, start_date FROM budgets";
+
+/// Memetakan satu baris hasil `SELECT_BUDGETS` ke `Budget`.
+fn map_row(row: &Row) -> rusqlite::Result<Budget> {
+    let period_val: i16 = row.get(2)?;
+    let period = BudgetPeriod::try_from(period_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    let date_str: String = row.get(6)?;
+    let start_date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    let currency: Currency = row.get(4)?;
+    Ok(Budget {
+        id: row.get(0)?,
+        category_id: row.get(1)?,
+        period,
+        amount: Money::new(row.get(3)?, currency),
+        currency,
+        rollover: row.get(5)?,
+        start_date,
+    })
+}
+
+/// Ekspresi SQL yang menghasilkan tanggal awal periode untuk kolom `date` pengeluaran.
+fn period_start_sql(period: BudgetPeriod) -> &'static str {
+    match period {
+        // Senin pada atau sebelum tanggal tersebut.
+        BudgetPeriod::Weekly => "date(e.date, '-6 days', 'weekday 1')",
+        BudgetPeriod::Monthly => "strftime('%Y-%m-01', e.date)",
+        BudgetPeriod::Yearly => "strftime('%Y-01-01', e.date)",
+    }
+}
+
+impl BudgetRepository for SqliteBudgetRepository<'_> {
+    fn create(&self, budget: &NewBudget) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO budgets (category_id, period, amount, currency, rollover, start_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    budget.category_id,
+                    budget.period as i16,
+                    budget.amount.minor_units(),
+                    budget.amount.currency(),
+                    budget.rollover,
+                    budget.start_date.format("%Y-%m-%d").to_string()
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Budget>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(&format!("{} ORDER BY id", SELECT_BUDGETS))?;
+
+            let budget_iter = stmt.query_map([], map_row)?;
+
+            budget_iter
+                .collect::<rusqlite::Result<Vec<Budget>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Budget, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_BUDGETS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Anggaran", id))
+        })
+    }
+
+    fn update(&self, id: i64, budget: &NewBudget) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE budgets
+                 SET category_id = ?1, period = ?2, amount = ?3, currency = ?4,
+                     rollover = ?5, start_date = ?6
+                 WHERE id = ?7",
+                params![
+                    budget.category_id,
+                    budget.period as i16,
+                    budget.amount.minor_units(),
+                    budget.amount.currency(),
+                    budget.rollover,
+                    budget.start_date.format("%Y-%m-%d").to_string(),
+                    id
+                ],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Anggaran", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM budgets WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
+    fn spent_by_period(
+        &self,
+        category_id: i64,
+        currency: Currency,
+        period: BudgetPeriod,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, Money)>, AppError> {
+        self.db.read(|conn| {
+            let sql = format!(
+                "WITH RECURSIVE budget_categories(id) AS (
+                     SELECT ?1
+                     UNION
+                     SELECT c.id FROM categories c
+                     JOIN budget_categories b ON c.parent_id = b.id
+                 )
+                 SELECT {} AS period_start, SUM(e.amount)
+                 FROM expenses e
+                 WHERE e.category_id IN (SELECT id FROM budget_categories)
+                   AND e.currency = ?2 AND e.date >= ?3 AND e.date < ?4
+                 GROUP BY period_start
+                 ORDER BY period_start",
+                period_start_sql(period)
+            );
+            let mut stmt = conn.prepare(&sql)?;
+
+            let rows = stmt.query_map(
+                params![
+                    category_id,
+                    currency,
+                    from.format("%Y-%m-%d").to_string(),
+                    until.format("%Y-%m-%d").to_string()
+                ],
+                |row| {
+                    let date_str: String = row.get(0)?;
+                    let start = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            0,
+                            rusqlite::types::Type::Text,
+                            Box::new(e),
+                        )
+                    })?;
+                    Ok((start, Money::new(row.get(1)?, currency)))
+                },
+            )?;
+
+            rows.collect::<rusqlite::Result<Vec<_>>>()
+                .map_err(AppError::from)
+        })
+    }
+}
@@ -1,9 +1,15 @@
-use crate::db::DB_CONNECTION;
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
 use crate::models::category::{Category, CategoryType};
-use rusqlite::{params, Result};
+use crate::models::default_category::{DefaultCategory, DefaultCategorySet};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use std::error::Error;
 use std::fmt;
 
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "categories";
+pub const COLUMNS: &[&str] = &["id", "name", "category_type", "parent_id", "icon", "color"];
+
 // Definisikan struct error kustom untuk konversi yang gagal.
 #[derive(Debug)]
 pub struct InvalidCategoryTypeError(i16);
@@ -33,92 +39,364 @@ impl TryFrom<i16> for CategoryType {
     }
 }
 
-/// Membuat entri kategori baru di database.
-pub fn create(name: &str, category_type: CategoryType) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute(
-        "INSERT INTO categories (name, category_type) VALUES (?1, ?2)",
-        // Simpan enum sebagai integer (1 atau 2)
-        params![name, category_type as i16],
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+/// Operasi penyimpanan data kategori.
+pub trait CategoryRepository {
+    /// Membuat entri kategori baru.
+    fn create(
+        &self,
+        name: &str,
+        category_type: CategoryType,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua data kategori, urut berdasarkan nama.
+    fn find_all(&self) -> Result<Vec<Category>, AppError>;
+
+    /// Mengambil satu data kategori berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Category, AppError>;
+
+    /// Memperbarui data kategori yang ada.
+    fn update(
+        &self,
+        id: i64,
+        name: &str,
+        category_type: CategoryType,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus data kategori berdasarkan ID. Sub-kategorinya dipindahkan ke induk
+    /// kategori yang dihapus (atau menjadi kategori akar) dalam transaksi yang sama.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Memindahkan semua pemasukan, pengeluaran, anggaran, dan data lain yang memakai kategori
+    /// `id` ke `replacement_id`, lalu menghapus kategori `id`, dalam satu transaksi.
+    /// Sub-kategorinya naik ke induk kategori yang dihapus. Mengembalikan jumlah transaksi
+    /// yang dipindahkan.
+    fn delete_reassigning(&self, id: i64, replacement_id: i64) -> Result<usize, AppError>;
+
+    /// Menggabungkan kategori `source_id` ke `target_id`: transaksi, anggaran, dan
+    /// sub-kategorinya dipindahkan ke `target_id`, lalu `source_id` dihapus, dalam satu
+    /// transaksi.
+    /// Mengembalikan jumlah transaksi yang dipindahkan.
+    fn merge(&self, source_id: i64, target_id: i64) -> Result<usize, AppError>;
+
+    /// ID kategori ini beserta seluruh leluhurnya, dari dirinya sendiri sampai akar.
+    fn find_ancestor_ids(&self, id: i64) -> Result<Vec<i64>, AppError>;
+
+    /// Menghitung jumlah sub-kategori langsung.
+    fn count_children(&self, id: i64) -> Result<i64, AppError>;
+
+    /// Menghitung jumlah pemasukan, pengeluaran, aturan transaksi berulang, anggaran, dan
+    /// utang yang memakai kategori ini.
+    fn count_usage(&self, id: i64) -> Result<i64, AppError>;
+
+    /// Menambahkan kategori bawaan dari `set` yang belum ada, dalam satu transaksi.
+    /// Mengembalikan jumlah kategori yang ditambahkan.
+    fn install_defaults(&self, set: &DefaultCategorySet) -> Result<usize, AppError>;
 }
 
-/// Mengambil semua data kategori dari database.
-pub fn find_all() -> Result<Vec<Category>, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    let mut stmt = conn
-        .prepare("SELECT id, name, category_type FROM categories")
-        .map_err(|e| e.to_string())?;
-
-    let category_iter = stmt
-        .query_map([], |row| {
-            let type_val: i16 = row.get(2)?;
-            // Sekarang `e` adalah tipe error kustom kita yang mengimplementasikan `Error`,
-            // sehingga `Box::new(e)` valid.
-            let category_type = CategoryType::try_from(type_val).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    2,
-                    rusqlite::types::Type::Integer,
-                    Box::new(e),
-                )
-            })?;
-
-            Ok(Category {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                category_type,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+/// Implementasi `CategoryRepository` di atas SQLite.
+pub struct SqliteCategoryRepository<'a> {
+    db: &'a Database,
+}
 
-    category_iter
-        .collect::<rusqlite::Result<Vec<Category>>>()
-        .map_err(|e| e.to_string())
+impl<'a> SqliteCategoryRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteCategoryRepository { db }
+    }
 }
 
-/// Mengambil satu data kategori berdasarkan ID.
-pub fn find_by_id(id: i64) -> Result<Category, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.query_row(
-        "SELECT id, name, category_type FROM categories WHERE id = ?1",
-        params![id],
-        |row| {
-            let type_val: i16 = row.get(2)?;
-            let category_type = CategoryType::try_from(type_val).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    2,
-                    rusqlite::types::Type::Integer,
-                    Box::new(e),
-                )
-            })?;
-
-            Ok(Category {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                category_type,
-            })
-        },
-    )
-    .map_err(|e| e.to_string())
+/// Memetakan satu baris hasil query
+/// `SELECT id, name, category_type, parent_id, icon, color` ke `Category`.
+fn map_row(row: &Row) -> rusqlite::Result<Category> {
+    let type_val: i16 = row.get(2)?;
+    // `e` adalah tipe error kustom kita yang mengimplementasikan `Error`,
+    // sehingga `Box::new(e)` valid.
+    let category_type = CategoryType::try_from(type_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    Ok(Category {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        category_type,
+        parent_id: row.get(3)?,
+        icon: row.get(4)?,
+        color: row.get(5)?,
+    })
 }
 
-/// Memperbarui data kategori yang ada di database.
-pub fn update(id: i64, name: &str, category_type: CategoryType) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
+/// Memindahkan semua pemasukan, pengeluaran, aturan transaksi berulang, anggaran, dan kategori
+/// cicilan utang dari kategori `from` ke `to`. Mengembalikan jumlah pemasukan dan pengeluaran
+/// yang dipindahkan.
+///
+/// Anggaran `from` yang periode dan mata uangnya sudah dimiliki `to` tidak bisa digabung,
+/// sehingga seluruh pemindahan ditolak alih-alih membuang salah satu anggaran.
+fn move_transactions(conn: &Connection, from: i64, to: i64) -> Result<usize, AppError> {
+    let clashing_budgets: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM budgets source
+         WHERE source.category_id = ?1
+           AND EXISTS (SELECT 1 FROM budgets target
+                       WHERE target.category_id = ?2
+                         AND target.period = source.period
+                         AND target.currency = source.currency)",
+        params![from, to],
+        |row| row.get(0),
+    )?;
+    if clashing_budgets > 0 {
+        return Err(AppError::Conflict(
+            "Kategori tujuan sudah memiliki anggaran dengan periode dan mata uang yang sama. \
+             Hapus salah satu anggaran terlebih dahulu."
+                .to_string(),
+        ));
+    }
+
+    let incomes = conn.execute(
+        "UPDATE incomes SET category_id = ?2 WHERE category_id = ?1",
+        params![from, to],
+    )?;
+    let expenses = conn.execute(
+        "UPDATE expenses SET category_id = ?2 WHERE category_id = ?1",
+        params![from, to],
+    )?;
+    conn.execute(
+        "UPDATE recurring_rules SET category_id = ?2 WHERE category_id = ?1",
+        params![from, to],
+    )?;
     conn.execute(
-        "UPDATE categories SET name = ?1, category_type = ?2 WHERE id = ?3",
-        params![name, category_type as i16, id],
+        "UPDATE budgets SET category_id = ?2 WHERE category_id = ?1",
+        params![from, to],
+    )?;
+    conn.execute(
+        "UPDATE debts SET category_id = ?2 WHERE category_id = ?1",
+        params![from, to],
+    )?;
+    Ok(incomes + expenses)
+}
+
+impl CategoryRepository for SqliteCategoryRepository<'_> {
+    fn create(
+        &self,
+        name: &str,
+        category_type: CategoryType,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO categories (name, category_type, parent_id, icon, color)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                // Simpan enum sebagai integer (1 atau 2)
+                params![name, category_type as i16, parent_id, icon, color],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Category>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, name, category_type, parent_id, icon, color FROM categories
+                 ORDER BY name COLLATE NOCASE, id",
+            )?;
+
+            let category_iter = stmt.query_map([], map_row)?;
+
+            category_iter
+                .collect::<rusqlite::Result<Vec<Category>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Category, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT id, name, category_type, parent_id, icon, color
+                 FROM categories WHERE id = ?1",
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Kategori", id))
+        })
+    }
+
+    fn update(
+        &self,
+        id: i64,
+        name: &str,
+        category_type: CategoryType,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "UPDATE categories
+                 SET name = ?1, category_type = ?2, parent_id = ?3, icon = ?4, color = ?5
+                 WHERE id = ?6",
+                params![name, category_type as i16, parent_id, icon, color, id],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "UPDATE categories
+                 SET parent_id = (SELECT parent_id FROM categories WHERE id = ?1)
+                 WHERE parent_id = ?1",
+                params![id],
+            )?;
+            tx.execute("DELETE FROM categories WHERE id = ?1", params![id])?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn delete_reassigning(&self, id: i64, replacement_id: i64) -> Result<usize, AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let moved = move_transactions(&tx, id, replacement_id)?;
+            tx.execute(
+                "UPDATE categories
+                 SET parent_id = (SELECT parent_id FROM categories WHERE id = ?1)
+                 WHERE parent_id = ?1",
+                params![id],
+            )?;
+            tx.execute("DELETE FROM categories WHERE id = ?1", params![id])?;
+
+            tx.commit()?;
+            Ok(moved)
+        })
+    }
+
+    fn merge(&self, source_id: i64, target_id: i64) -> Result<usize, AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let moved = move_transactions(&tx, source_id, target_id)?;
+            tx.execute(
+                "UPDATE categories SET parent_id = ?2 WHERE parent_id = ?1",
+                params![source_id, target_id],
+            )?;
+            tx.execute("DELETE FROM categories WHERE id = ?1", params![source_id])?;
+
+            tx.commit()?;
+            Ok(moved)
+        })
+    }
+
+    fn find_ancestor_ids(&self, id: i64) -> Result<Vec<i64>, AppError> {
+        self.db.read(|conn| {
+            // `UNION` (bukan `UNION ALL`) menghentikan rekursi bila data lama sudah berputar.
+            let mut stmt = conn.prepare(
+                "WITH RECURSIVE ancestors(id) AS (
+                     SELECT ?1
+                     UNION
+                     SELECT c.parent_id FROM categories c
+                     JOIN ancestors a ON c.id = a.id
+                     WHERE c.parent_id IS NOT NULL
+                 )
+                 SELECT id FROM ancestors",
+            )?;
+
+            let id_iter = stmt.query_map(params![id], |row| row.get(0))?;
+
+            id_iter
+                .collect::<rusqlite::Result<Vec<i64>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn count_children(&self, id: i64) -> Result<i64, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*) FROM categories WHERE parent_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(AppError::from)
+        })
+    }
+
+    fn count_usage(&self, id: i64) -> Result<i64, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT (SELECT COUNT(*) FROM incomes WHERE category_id = ?1)
+                      + (SELECT COUNT(*) FROM expenses WHERE category_id = ?1)
+                      + (SELECT COUNT(*) FROM recurring_rules WHERE category_id = ?1)
+                      + (SELECT COUNT(*) FROM budgets WHERE category_id = ?1)
+                      + (SELECT COUNT(*) FROM debts WHERE category_id = ?1)",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(AppError::from)
+        })
+    }
+
+    fn install_defaults(&self, set: &DefaultCategorySet) -> Result<usize, AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let inserted = insert_defaults(&tx, set)?;
+            tx.commit()?;
+            Ok(inserted)
+        })
+    }
+}
+
+/// Menambahkan kategori bawaan dari `set` yang belum ada. Kategori yang namanya sudah
+/// dipakai (tidak peka huruf besar/kecil, dengan tipe dan induk yang sama) dibiarkan
+/// apa adanya, termasuk ikon dan warnanya. Mengembalikan jumlah kategori yang ditambahkan.
+pub fn insert_defaults(conn: &Connection, set: &DefaultCategorySet) -> Result<usize, AppError> {
+    Ok(
+        insert_default_level(conn, CategoryType::Income, None, None, set.income)?
+            + insert_default_level(conn, CategoryType::Expense, None, None, set.expense)?,
     )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
 }
 
-/// Menghapus data kategori dari database berdasarkan ID.
-pub fn delete(id: i64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute("DELETE FROM categories WHERE id = ?1", params![id])
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+fn insert_default_level(
+    conn: &Connection,
+    category_type: CategoryType,
+    parent_id: Option<i64>,
+    parent_color: Option<&str>,
+    defaults: &[DefaultCategory],
+) -> Result<usize, AppError> {
+    let mut inserted = 0;
+    for default in defaults {
+        let color = default.color.or(parent_color);
+        let existing: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM categories
+                 WHERE name = ?1 COLLATE NOCASE AND category_type = ?2 AND parent_id IS ?3",
+                params![default.name, category_type as i16, parent_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                conn.execute(
+                    "INSERT INTO categories (name, category_type, parent_id, icon, color)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        default.name,
+                        category_type as i16,
+                        parent_id,
+                        default.icon,
+                        color
+                    ],
+                )?;
+                inserted += 1;
+                conn.last_insert_rowid()
+            }
+        };
+        inserted += insert_default_level(conn, category_type, Some(id), color, default.children)?;
+    }
+    Ok(inserted)
 }
@@ -0,0 +1,562 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::debt::{Debt, DebtDirection, DebtPayment, NewDebt, NewDebtPayment};
+use crate::models::loan::{InterestMethod, ScheduledInstallment};
+use crate::models::money::{Currency, Money};
+use crate::repositories::{account_repository, expense_repository, income_repository};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::error::Error;
+use std::fmt;
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "debts";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "counterparty",
+    "direction",
+    "principal",
+    "currency",
+    "start_date",
+    "due_date",
+    "interest_rate",
+    "installment_count",
+    "interest_method",
+    "category_id",
+    "description",
+    "account_id",
+];
+pub const PAYMENTS_TABLE: &str = "debt_payments";
+pub const PAYMENT_COLUMNS: &[&str] = &["id", "debt_id", "expense_id", "income_id", "auto_linked"];
+pub const INSTALLMENTS_TABLE: &str = "debt_installments";
+pub const INSTALLMENT_COLUMNS: &[&str] = &[
+    "debt_id",
+    "number",
+    "due_date",
+    "principal",
+    "interest",
+    "remaining_principal",
+];
+
+/// Selisih hari maksimal antara tanggal transaksi dan jatuh tempo cicilan agar
+/// transaksi tersebut dianggap sebagai pembayaran cicilan.
+pub const INSTALLMENT_MATCH_DAYS: i64 = 10;
+
+#[derive(Debug)]
+pub struct InvalidDebtDirectionError(i16);
+
+impl fmt::Display for InvalidDebtDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk DebtDirection: {}", self.0)
+    }
+}
+
+impl Error for InvalidDebtDirectionError {}
+
+impl TryFrom<i16> for DebtDirection {
+    type Error = InvalidDebtDirectionError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(DebtDirection::Payable),
+            2 => Ok(DebtDirection::Receivable),
+            _ => Err(InvalidDebtDirectionError(value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidInterestMethodError(i16);
+
+impl fmt::Display for InvalidInterestMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk InterestMethod: {}", self.0)
+    }
+}
+
+impl Error for InvalidInterestMethodError {}
+
+impl TryFrom<i16> for InterestMethod {
+    type Error = InvalidInterestMethodError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(InterestMethod::Flat),
+            2 => Ok(InterestMethod::Annuity),
+            _ => Err(InvalidInterestMethodError(value)),
+        }
+    }
+}
+
+/// Operasi penyimpanan utang/piutang dan pembayarannya.
+pub trait DebtRepository {
+    /// Membuat utang baru dalam mata uang `principal` beserta tabel angsurannya, dan mencatat
+    /// pencairannya pada akun `account_id` (jika ada) dalam transaksi yang sama.
+    fn create(&self, debt: &NewDebt) -> Result<(), AppError>;
+
+    /// Mengambil semua utang dan piutang.
+    fn find_all(&self) -> Result<Vec<Debt>, AppError>;
+
+    /// Mengambil satu utang berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Debt, AppError>;
+
+    /// Memperbarui utang yang ada, mengganti tabel angsurannya, dan menyesuaikan saldo akun
+    /// pencairan lama dan baru.
+    fn update(&self, id: i64, debt: &NewDebt) -> Result<(), AppError>;
+
+    /// Menghapus utang beserta tautan pembayarannya dan membatalkan pencairannya pada saldo
+    /// akun. Transaksi pembayarannya tetap ada.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Mencatat pembayaran sebagai pengeluaran (utang) atau pemasukan (piutang) dan
+    /// menautkannya ke utang, dalam satu transaksi.
+    fn add_payment(
+        &self,
+        debt_id: i64,
+        direction: DebtDirection,
+        payment: &NewDebtPayment,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil tabel angsuran sebuah utang, urut nomor cicilan.
+    fn find_installments(&self, debt_id: i64) -> Result<Vec<ScheduledInstallment>, AppError>;
+
+    /// Mengambil semua pembayaran sebuah utang, terlama lebih dulu.
+    fn find_payments(&self, debt_id: i64) -> Result<Vec<DebtPayment>, AppError>;
+
+    /// Menghapus pembayaran beserta transaksinya (saldo akun dikembalikan).
+    fn delete_payment(&self, id: i64) -> Result<(), AppError>;
+}
+
+/// Implementasi `DebtRepository` di atas SQLite.
+pub struct SqliteDebtRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteDebtRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteDebtRepository { db }
+    }
+}
+
+const SELECT_DEBTS: &str = "SELECT id, counterparty, direction, principal, currency, start_date,
+            due_date, interest_rate, installment_count, interest_method, category_id, description,
+            account_id
+     FROM debts";
+
+/// Mengubah kolom TEXT `YYYY-MM-DD` pada indeks `index` menjadi `NaiveDate`.
+fn parse_date(index: usize, value: &str) -> rusqlite::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Memetakan satu baris hasil `SELECT_DEBTS` ke `Debt`.
+fn map_row(row: &Row) -> rusqlite::Result<Debt> {
+    let direction_val: i16 = row.get(2)?;
+    let direction = DebtDirection::try_from(direction_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    let currency: Currency = row.get(4)?;
+    let method_val: i16 = row.get(9)?;
+    let interest_method = InterestMethod::try_from(method_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(9, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    Ok(Debt {
+        id: row.get(0)?,
+        counterparty: row.get(1)?,
+        direction,
+        principal: Money::new(row.get(3)?, currency),
+        currency,
+        start_date: parse_date(5, &row.get::<_, String>(5)?)?,
+        due_date: row
+            .get::<_, Option<String>>(6)?
+            .map(|value| parse_date(6, &value))
+            .transpose()?,
+        interest_rate: row.get(7)?,
+        installment_count: row.get(8)?,
+        interest_method,
+        category_id: row.get(10)?,
+        description: row.get(11)?,
+        account_id: row.get(12)?,
+    })
+}
+
+/// Memetakan satu baris hasil query pembayaran ke `DebtPayment`.
+fn map_payment(row: &Row) -> rusqlite::Result<DebtPayment> {
+    let date_str: String = row.get(4)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(3)?;
+
+    Ok(DebtPayment {
+        id: row.get(0)?,
+        debt_id: row.get(1)?,
+        amount: Money::new(row.get(2)?, currency),
+        date,
+        account_id: row.get(5)?,
+        expense_id: row.get(6)?,
+        income_id: row.get(7)?,
+    })
+}
+
+/// Memetakan satu baris `debt_installments` (tanpa `debt_id`) ke `ScheduledInstallment`.
+fn map_installment(row: &Row, currency: Currency) -> rusqlite::Result<ScheduledInstallment> {
+    let principal = Money::new(row.get(2)?, currency);
+    let interest = Money::new(row.get(3)?, currency);
+    Ok(ScheduledInstallment {
+        number: row.get(0)?,
+        due_date: parse_date(1, &row.get::<_, String>(1)?)?,
+        principal,
+        interest,
+        amount: Money::new(principal.minor_units() + interest.minor_units(), currency),
+        remaining_principal: Money::new(row.get(4)?, currency),
+    })
+}
+
+/// Pengaruh pencairan terhadap saldo akun: utang menambah saldo sebesar pokoknya, piutang
+/// menguranginya.
+fn disbursement_effect(direction: DebtDirection, principal: Money) -> Money {
+    match direction {
+        DebtDirection::Payable => principal,
+        DebtDirection::Receivable => principal.negated(),
+    }
+}
+
+/// Membatalkan pencairan utang `id` pada saldo akunnya (jika terhubung ke akun).
+fn revert_disbursement(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let old = conn
+        .query_row(
+            &format!("{} WHERE id = ?1", SELECT_DEBTS),
+            params![id],
+            map_row,
+        )
+        .map_err(not_found_as("Utang", id))?;
+
+    match old.account_id {
+        Some(account_id) => account_repository::adjust_balance(
+            conn,
+            account_id,
+            disbursement_effect(old.direction, old.principal).negated(),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Menerapkan pencairan utang baru pada saldo akunnya (jika terhubung ke akun).
+fn apply_disbursement(conn: &Connection, debt: &NewDebt) -> Result<(), AppError> {
+    match debt.account_id {
+        Some(account_id) => account_repository::adjust_balance(
+            conn,
+            account_id,
+            disbursement_effect(debt.direction, debt.principal),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Mengganti tabel angsuran utang `debt_id` dengan `schedule`.
+fn replace_schedule(
+    conn: &Connection,
+    debt_id: i64,
+    schedule: &[ScheduledInstallment],
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM debt_installments WHERE debt_id = ?1",
+        params![debt_id],
+    )?;
+    let mut stmt = conn.prepare(
+        "INSERT INTO debt_installments
+             (debt_id, number, due_date, principal, interest, remaining_principal)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for installment in schedule {
+        stmt.execute(params![
+            debt_id,
+            installment.number,
+            installment.due_date.format("%Y-%m-%d").to_string(),
+            installment.principal.minor_units(),
+            installment.interest.minor_units(),
+            installment.remaining_principal.minor_units()
+        ])?;
+    }
+    Ok(())
+}
+
+/// Tabel transaksi pembayaran dan kolom tautannya di `debt_payments`: pengeluaran untuk
+/// utang, pemasukan untuk piutang.
+fn payment_source(direction: DebtDirection) -> (&'static str, &'static str) {
+    match direction {
+        DebtDirection::Payable => (expense_repository::TABLE, "expense_id"),
+        DebtDirection::Receivable => (income_repository::TABLE, "income_id"),
+    }
+}
+
+/// Mencatat transaksi `entry_id` (pengeluaran untuk utang, pemasukan untuk piutang) sebagai
+/// pembayaran cicilan bila cocok dengan cicilan yang belum lunas: kategori sama dengan
+/// kategori cicilan, mata uang dan jumlahnya sama dengan cicilan, serta tanggalnya paling
+/// jauh `INSTALLMENT_MATCH_DAYS` hari dari jatuh tempo. Bila ada beberapa yang cocok, dipilih
+/// jatuh tempo paling awal. Mengembalikan ID utang yang dibayar, jika ada.
+pub fn link_matching_installment(
+    conn: &Connection,
+    direction: DebtDirection,
+    entry_id: i64,
+) -> Result<Option<i64>, AppError> {
+    let (table, column) = payment_source(direction);
+    let debt_id: Option<i64> = conn
+        .query_row(
+            &format!(
+                "SELECT i.debt_id
+                 FROM {table} e
+                 JOIN debts d ON d.category_id = e.category_id
+                             AND d.currency = e.currency
+                             AND d.direction = ?4
+                 JOIN debt_installments i ON i.debt_id = d.id
+                 WHERE e.id = ?1
+                   AND i.principal + i.interest = e.amount
+                   AND i.due_date BETWEEN date(e.date, ?2) AND date(e.date, ?3)
+                   AND NOT EXISTS (SELECT 1 FROM debt_payments p WHERE p.{column} = e.id)
+                   AND (SELECT COALESCE(SUM(pe.amount), 0)
+                        FROM debt_payments p JOIN {table} pe ON pe.id = p.{column}
+                        WHERE p.debt_id = d.id)
+                     < (SELECT SUM(c.principal + c.interest) FROM debt_installments c
+                        WHERE c.debt_id = d.id AND c.number <= i.number)
+                 ORDER BY i.due_date, d.id
+                 LIMIT 1"
+            ),
+            params![
+                entry_id,
+                format!("-{} days", INSTALLMENT_MATCH_DAYS),
+                format!("+{} days", INSTALLMENT_MATCH_DAYS),
+                direction as i16
+            ],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(debt_id) = debt_id {
+        conn.execute(
+            &format!(
+                "INSERT INTO debt_payments (debt_id, {column}, auto_linked) VALUES (?1, ?2, 1)"
+            ),
+            params![debt_id, entry_id],
+        )?;
+    }
+    Ok(debt_id)
+}
+
+/// Menilai ulang tautan cicilan transaksi `entry_id` setelah diubah: tautan otomatis yang
+/// lama dihapus lalu dicocokkan kembali. Pembayaran yang dicatat langsung pada utang/piutang
+/// tetap tertaut. Mengembalikan ID utang yang dibayar, jika ada.
+pub fn relink_matching_installment(
+    conn: &Connection,
+    direction: DebtDirection,
+    entry_id: i64,
+) -> Result<Option<i64>, AppError> {
+    let (_, column) = payment_source(direction);
+    conn.execute(
+        &format!("DELETE FROM debt_payments WHERE {column} = ?1 AND auto_linked = 1"),
+        params![entry_id],
+    )?;
+    link_matching_installment(conn, direction, entry_id)
+}
+
+impl DebtRepository for SqliteDebtRepository<'_> {
+    fn create(&self, debt: &NewDebt) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO debts (counterparty, direction, principal, currency, start_date,
+                                    due_date, interest_rate, installment_count, interest_method,
+                                    category_id, description, account_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    debt.counterparty,
+                    debt.direction as i16,
+                    debt.principal.minor_units(),
+                    debt.principal.currency(),
+                    debt.start_date.format("%Y-%m-%d").to_string(),
+                    debt.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                    debt.interest_rate,
+                    debt.installment_count,
+                    debt.interest_method as i16,
+                    debt.category_id,
+                    debt.description,
+                    debt.account_id
+                ],
+            )?;
+            replace_schedule(&tx, tx.last_insert_rowid(), &debt.schedule)?;
+            apply_disbursement(&tx, debt)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Debt>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{} ORDER BY counterparty COLLATE NOCASE, start_date, id",
+                SELECT_DEBTS
+            ))?;
+
+            let debt_iter = stmt.query_map([], map_row)?;
+
+            debt_iter
+                .collect::<rusqlite::Result<Vec<Debt>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Debt, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_DEBTS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Utang", id))
+        })
+    }
+
+    fn update(&self, id: i64, debt: &NewDebt) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            revert_disbursement(&tx, id)?;
+            tx.execute(
+                "UPDATE debts
+                 SET counterparty = ?1, direction = ?2, principal = ?3, currency = ?4,
+                     start_date = ?5, due_date = ?6, interest_rate = ?7,
+                     installment_count = ?8, interest_method = ?9, category_id = ?10,
+                     description = ?11, account_id = ?12
+                 WHERE id = ?13",
+                params![
+                    debt.counterparty,
+                    debt.direction as i16,
+                    debt.principal.minor_units(),
+                    debt.principal.currency(),
+                    debt.start_date.format("%Y-%m-%d").to_string(),
+                    debt.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                    debt.interest_rate,
+                    debt.installment_count,
+                    debt.interest_method as i16,
+                    debt.category_id,
+                    debt.description,
+                    debt.account_id,
+                    id
+                ],
+            )?;
+            replace_schedule(&tx, id, &debt.schedule)?;
+            apply_disbursement(&tx, debt)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            revert_disbursement(&tx, id)?;
+            tx.execute("DELETE FROM debts WHERE id = ?1", params![id])?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn add_payment(
+        &self,
+        debt_id: i64,
+        direction: DebtDirection,
+        payment: &NewDebtPayment,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let insert = match direction {
+                DebtDirection::Payable => expense_repository::insert,
+                DebtDirection::Receivable => income_repository::insert,
+            };
+            let entry_id = insert(
+                &tx,
+                payment.description,
+                payment.amount,
+                payment.date,
+                payment.category_id,
+                payment.account_id,
+            )?;
+            let (expense_id, income_id) = match direction {
+                DebtDirection::Payable => (Some(entry_id), None),
+                DebtDirection::Receivable => (None, Some(entry_id)),
+            };
+            tx.execute(
+                "INSERT INTO debt_payments (debt_id, expense_id, income_id) VALUES (?1, ?2, ?3)",
+                params![debt_id, expense_id, income_id],
+            )?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_installments(&self, debt_id: i64) -> Result<Vec<ScheduledInstallment>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT i.number, i.due_date, i.principal, i.interest, i.remaining_principal,
+                        d.currency
+                 FROM debt_installments i
+                 JOIN debts d ON d.id = i.debt_id
+                 WHERE i.debt_id = ?1
+                 ORDER BY i.number",
+            )?;
+
+            let installment_iter =
+                stmt.query_map(params![debt_id], |row| map_installment(row, row.get(5)?))?;
+
+            installment_iter
+                .collect::<rusqlite::Result<Vec<ScheduledInstallment>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_payments(&self, debt_id: i64) -> Result<Vec<DebtPayment>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT p.id, p.debt_id, COALESCE(e.amount, i.amount),
+                        COALESCE(e.currency, i.currency), COALESCE(e.date, i.date),
+                        COALESCE(e.account_id, i.account_id), p.expense_id, p.income_id
+                 FROM debt_payments p
+                 LEFT JOIN expenses e ON e.id = p.expense_id
+                 LEFT JOIN incomes i ON i.id = p.income_id
+                 WHERE p.debt_id = ?1
+                 ORDER BY COALESCE(e.date, i.date), p.id",
+            )?;
+
+            let payment_iter = stmt.query_map(params![debt_id], map_payment)?;
+
+            payment_iter
+                .collect::<rusqlite::Result<Vec<DebtPayment>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn delete_payment(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let (expense_id, income_id): (Option<i64>, Option<i64>) = tx
+                .query_row(
+                    "SELECT expense_id, income_id FROM debt_payments WHERE id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?
+                .ok_or_else(|| AppError::not_found("Pembayaran utang", id))?;
+            // Tautan pembayaran ikut terhapus lewat `ON DELETE CASCADE`.
+            if let Some(expense_id) = expense_id {
+                expense_repository::remove(&tx, expense_id)?;
+            }
+            if let Some(income_id) = income_id {
+                income_repository::remove(&tx, income_id)?;
+            }
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::exchange_rate::{ExchangeRate, RateSource};
+use crate::models::money::Currency;
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::error::Error;
+use std::fmt;
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "exchange_rates";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "date",
+    "base_currency",
+    "quote_currency",
+    "rate",
+    "source",
+];
+
+#[derive(Debug)]
+pub struct InvalidRateSourceError(i16);
+
+impl fmt::Display for InvalidRateSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk RateSource: {}", self.0)
+    }
+}
+
+impl Error for InvalidRateSourceError {}
+
+impl TryFrom<i16> for RateSource {
+    type Error = InvalidRateSourceError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RateSource::Manual),
+            2 => Ok(RateSource::Csv),
+            _ => Err(InvalidRateSourceError(value)),
+        }
+    }
+}
+
+/// Memetakan satu baris hasil query
+/// `SELECT id, date, base_currency, quote_currency, rate, source` ke `ExchangeRate`.
+fn map_row(row: &Row) -> rusqlite::Result<ExchangeRate> {
+    // Kolom date disimpan sebagai TEXT dengan format YYYY-MM-DD.
+    let date_str: String = row.get(1)?;
+    let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    let source_val: i16 = row.get(5)?;
+    let source = RateSource::try_from(source_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    Ok(ExchangeRate {
+        id: row.get(0)?,
+        date,
+        base_currency: row.get(2)?,
+        quote_currency: row.get(3)?,
+        rate: row.get(4)?,
+        source,
+    })
+}
+
+/// Operasi penyimpanan data kurs.
+pub trait ExchangeRateRepository {
+    /// Menyimpan satu kurs. Jika kurs untuk tanggal dan pasangan yang sama sudah ada,
+    /// nilainya diganti.
+    fn upsert(
+        &self,
+        date: NaiveDate,
+        base: Currency,
+        quote: Currency,
+        rate: f64,
+        source: RateSource,
+    ) -> Result<(), AppError>;
+
+    /// Menyimpan banyak kurs sekaligus dalam satu transaksi (dipakai saat impor CSV).
+    fn upsert_many(
+        &self,
+        rates: &[(NaiveDate, Currency, Currency, f64)],
+        source: RateSource,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua kurs, terbaru lebih dulu.
+    fn find_all(&self) -> Result<Vec<ExchangeRate>, AppError>;
+
+    /// Mengambil kurs terbaru untuk pasangan `base -> quote` pada atau sebelum `date`.
+    fn find_latest_on_or_before(
+        &self,
+        base: Currency,
+        quote: Currency,
+        date: NaiveDate,
+    ) -> Result<Option<f64>, AppError>;
+
+    /// Menghapus data kurs berdasarkan ID.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+}
+
+/// Implementasi `ExchangeRateRepository` di atas SQLite.
+pub struct SqliteExchangeRateRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteExchangeRateRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteExchangeRateRepository { db }
+    }
+}
+
+fn upsert_with(
+    conn: &Connection,
+    date: NaiveDate,
+    base: Currency,
+    quote: Currency,
+    rate: f64,
+    source: RateSource,
+) -> rusqlite::Result<()> {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    conn.execute(
+        "INSERT INTO exchange_rates (date, base_currency, quote_currency, rate, source)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (date, base_currency, quote_currency)
+         DO UPDATE SET rate = excluded.rate, source = excluded.source",
+        params![date_str, base, quote, rate, source as i16],
+    )
+    .map(|_| ())
+}
+
+impl ExchangeRateRepository for SqliteExchangeRateRepository<'_> {
+    fn upsert(
+        &self,
+        date: NaiveDate,
+        base: Currency,
+        quote: Currency,
+        rate: f64,
+        source: RateSource,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            upsert_with(conn, date, base, quote, rate, source).map_err(AppError::from)
+        })
+    }
+
+    fn upsert_many(
+        &self,
+        rates: &[(NaiveDate, Currency, Currency, f64)],
+        source: RateSource,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+
+            for (date, base, quote, rate) in rates {
+                upsert_with(&tx, *date, *base, *quote, *rate, source)?;
+            }
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<ExchangeRate>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, date, base_currency, quote_currency, rate, source
+                 FROM exchange_rates ORDER BY date DESC, base_currency, quote_currency",
+            )?;
+
+            let rate_iter = stmt.query_map([], map_row)?;
+
+            rate_iter
+                .collect::<rusqlite::Result<Vec<ExchangeRate>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_latest_on_or_before(
+        &self,
+        base: Currency,
+        quote: Currency,
+        date: NaiveDate,
+    ) -> Result<Option<f64>, AppError> {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT rate FROM exchange_rates
+                 WHERE base_currency = ?1 AND quote_currency = ?2 AND date <= ?3
+                 ORDER BY date DESC LIMIT 1",
+                params![base, quote, date_str],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(AppError::from)
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM exchange_rates WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+}
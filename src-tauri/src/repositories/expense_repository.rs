@@ -1,118 +1,240 @@
-use crate::db::DB_CONNECTION;
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::debt::DebtDirection;
 use crate::models::expense::Expense;
-use chrono::NaiveDateTime; // Diubah dari NaiveDate ke NaiveDateTime
-use rusqlite::{params, Result};
+use crate::models::money::{Currency, Money};
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::{account_repository, debt_repository, transaction_filter};
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, Row};
 
-/// Membuat entri pengeluaran baru di database.
-pub fn create(
-    description: &str,
-    amount: f64,
-    date: NaiveDateTime,
-    category_id: i64,
-) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "expenses";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "description",
+    "amount",
+    "currency",
+    "date",
+    "category_id",
+    "account_id",
+];
 
-    // Simpan datetime sebagai string dengan format YYYY-MM-DD HH:MM:SS
-    let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
+const SELECT_COLUMNS: &str =
+    "SELECT id, description, amount, currency, date, category_id, account_id FROM expenses";
 
-    conn.execute(
-        "INSERT INTO expenses (description, amount, date, category_id) VALUES (?1, ?2, ?3, ?4)",
-        params![description, amount, date_str, category_id],
-    )
-    .map(|_| ()) // Mengubah hasil dari execute menjadi ()
-    .map_err(|e| e.to_string())
+/// Operasi penyimpanan data pengeluaran.
+pub trait ExpenseRepository {
+    /// Membuat entri pengeluaran baru dan memperbarui saldo akunnya dalam satu transaksi.
+    /// Pengeluaran yang cocok dengan cicilan utang langsung dicatat sebagai pembayarannya.
+    fn create(
+        &self,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua data pengeluaran.
+    fn find_all(&self) -> Result<Vec<Expense>, AppError>;
+
+    /// Mengambil satu data pengeluaran berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Expense, AppError>;
+
+    /// Mengambil satu halaman pengeluaran yang cocok dengan filter, beserta jumlah totalnya.
+    fn find_page(&self, query: &TransactionQuery) -> Result<Page<Expense>, AppError>;
+
+    /// Memperbarui data pengeluaran yang ada. Pengaruh nilai lama terhadap saldo
+    /// dibatalkan lalu nilai baru diterapkan, termasuk bila akunnya berpindah.
+    fn update(
+        &self,
+        id: i64,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus data pengeluaran berdasarkan ID dan mengembalikan saldo akunnya.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
 }
 
-/// Mengambil semua data pengeluaran dari database.
-pub fn find_all() -> Result<Vec<Expense>, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    let mut stmt = conn
-        .prepare("SELECT id, description, amount, date, category_id FROM expenses")
-        .map_err(|e| e.to_string())?;
-
-    let expense_iter = stmt
-        .query_map([], |row| {
-            // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
-            let date_str: String = row.get(3)?;
-            let date =
-                NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-
-            Ok(Expense {
-                id: row.get(0)?,
-                description: row.get(1)?,
-                amount: row.get(2)?,
-                date, // Sekarang menjadi NaiveDateTime
-                category_id: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+/// Implementasi `ExpenseRepository` di atas SQLite.
+pub struct SqliteExpenseRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteExpenseRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteExpenseRepository { db }
+    }
+}
 
-    let expenses = expense_iter
-        .collect::<rusqlite::Result<Vec<Expense>>>()
-        .map_err(|e| e.to_string())?;
+/// Memetakan satu baris hasil query `SELECT_COLUMNS` ke `Expense`.
+fn map_row(row: &Row) -> rusqlite::Result<Expense> {
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(4)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(3)?;
 
-    Ok(expenses)
+    Ok(Expense {
+        id: row.get(0)?,
+        description: row.get(1)?,
+        amount: Money::new(row.get(2)?, currency),
+        currency,
+        date,
+        category_id: row.get(5)?,
+        account_id: row.get(6)?,
+    })
 }
 
-/// Mengambil satu data pengeluaran berdasarkan ID.
-pub fn find_by_id(id: i64) -> Result<Expense, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.query_row(
-        "SELECT id, description, amount, date, category_id FROM expenses WHERE id = ?1",
-        params![id],
-        |row| {
-            // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
-            let date_str: String = row.get(3)?;
-            let date =
-                NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-
-            Ok(Expense {
-                id: row.get(0)?,
-                description: row.get(1)?,
-                amount: row.get(2)?,
-                date, // Sekarang menjadi NaiveDateTime
-                category_id: row.get(4)?,
-            })
-        },
-    )
-    .map_err(|e| e.to_string())
+/// Pengaruh satu pengeluaran terhadap saldo akun: saldo berkurang sebesar `amount`.
+fn balance_effect(amount: Money) -> Money {
+    amount.negated()
 }
 
-/// Memperbarui data pengeluaran yang ada di database.
-pub fn update(
-    id: i64,
+/// Membatalkan pengaruh pengeluaran lama terhadap saldo akunnya (jika terhubung ke akun).
+fn revert_balance_effect(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let old = conn
+        .query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            map_row,
+        )
+        .map_err(not_found_as("Pengeluaran", id))?;
+
+    match old.account_id {
+        Some(account_id) => account_repository::adjust_balance(
+            conn,
+            account_id,
+            balance_effect(old.amount).negated(),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Menyimpan satu pengeluaran dan memperbarui saldo akunnya memakai koneksi atau transaksi
+/// milik pemanggil, sehingga bisa digabung dengan perubahan lain secara atomik.
+/// Mengembalikan ID pengeluaran yang baru.
+pub fn insert(
+    conn: &Connection,
     description: &str,
-    amount: f64,
+    amount: Money,
     date: NaiveDateTime,
     category_id: i64,
-) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
+    account_id: i64,
+) -> Result<i64, AppError> {
+    // Simpan datetime sebagai string dengan format YYYY-MM-DD HH:MM:SS
     let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
 
     conn.execute(
-        "UPDATE expenses SET description = ?1, amount = ?2, date = ?3, category_id = ?4 WHERE id = ?5",
-        params![description, amount, date_str, category_id, id],
-    )
-    .map(|_| ()) // Mengubah hasil dari execute menjadi ()
-    .map_err(|e| e.to_string())
+        "INSERT INTO expenses (description, amount, currency, date, category_id, account_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            description,
+            amount.minor_units(),
+            amount.currency(),
+            date_str,
+            category_id,
+            account_id
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    account_repository::adjust_balance(conn, account_id, balance_effect(amount))?;
+    Ok(id)
 }
 
-/// Menghapus data pengeluaran dari database berdasarkan ID.
-pub fn delete(id: i64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute("DELETE FROM expenses WHERE id = ?1", params![id])
-        .map(|_| ()) // Mengubah hasil dari execute menjadi ()
-        .map_err(|e| e.to_string())
+/// Menghapus satu pengeluaran dan mengembalikan saldo akunnya memakai koneksi atau transaksi
+/// milik pemanggil.
+pub fn remove(conn: &Connection, id: i64) -> Result<(), AppError> {
+    revert_balance_effect(conn, id)?;
+    conn.execute("DELETE FROM expenses WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+impl ExpenseRepository for SqliteExpenseRepository<'_> {
+    fn create(
+        &self,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let id = insert(&tx, description, amount, date, category_id, account_id)?;
+            debt_repository::link_matching_installment(&tx, DebtDirection::Payable, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Expense>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(SELECT_COLUMNS)?;
+
+            let expense_iter = stmt.query_map([], map_row)?;
+
+            expense_iter
+                .collect::<rusqlite::Result<Vec<Expense>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Expense, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Pengeluaran", id))
+        })
+    }
+
+    fn find_page(&self, query: &TransactionQuery) -> Result<Page<Expense>, AppError> {
+        self.db
+            .read(|conn| transaction_filter::find_page(conn, TABLE, SELECT_COLUMNS, query, map_row))
+    }
+
+    fn update(
+        &self,
+        id: i64,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            revert_balance_effect(&tx, id)?;
+            tx.execute(
+                "UPDATE expenses
+                 SET description = ?1, amount = ?2, currency = ?3, date = ?4, category_id = ?5, account_id = ?6
+                 WHERE id = ?7",
+                params![description, amount.minor_units(), amount.currency(), date_str, category_id, account_id, id],
+            )?;
+            account_repository::adjust_balance(&tx, account_id, balance_effect(amount))?;
+            debt_repository::relink_matching_installment(&tx, DebtDirection::Payable, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            remove(&tx, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
 }
@@ -0,0 +1,253 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::goal::{Goal, GoalContribution, NewGoal};
+use crate::models::money::{Currency, Money};
+use crate::models::transfer::NewTransfer;
+use crate::repositories::transfer_repository;
+use chrono::NaiveDate;
+use rusqlite::{params, OptionalExtension, Row};
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "goals";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "name",
+    "target_amount",
+    "currency",
+    "target_date",
+    "account_id",
+];
+pub const CONTRIBUTIONS_TABLE: &str = "goal_contributions";
+pub const CONTRIBUTION_COLUMNS: &[&str] =
+    &["id", "goal_id", "amount", "date", "note", "transfer_id"];
+
+/// Operasi penyimpanan target tabungan dan setorannya.
+pub trait GoalRepository {
+    /// Membuat target baru dalam mata uang `target_amount`.
+    fn create(&self, goal: &NewGoal) -> Result<(), AppError>;
+
+    /// Mengambil semua target.
+    fn find_all(&self) -> Result<Vec<Goal>, AppError>;
+
+    /// Mengambil satu target berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Goal, AppError>;
+
+    /// Memperbarui target yang ada.
+    fn update(&self, id: i64, goal: &NewGoal) -> Result<(), AppError>;
+
+    /// Menghapus target beserta catatan setorannya. Transfer yang sudah dibuat tetap ada.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Mencatat setoran. Bila `transfer` diisi, transfernya dibuat dan ditautkan ke setoran
+    /// dalam satu transaksi.
+    fn add_contribution(
+        &self,
+        goal_id: i64,
+        amount: Money,
+        date: NaiveDate,
+        note: Option<&str>,
+        transfer: Option<&NewTransfer>,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua setoran sebuah target, terlama lebih dulu.
+    fn find_contributions(&self, goal_id: i64) -> Result<Vec<GoalContribution>, AppError>;
+
+    /// Menghapus setoran beserta transfer yang ditautkan (saldo akun dikembalikan).
+    fn delete_contribution(&self, id: i64) -> Result<(), AppError>;
+}
+
+/// Implementasi `GoalRepository` di atas SQLite.
+pub struct SqliteGoalRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteGoalRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteGoalRepository { db }
+    }
+}
+
+const SELECT_GOALS: &str =
+    "SELECT id, name, target_amount, currency, target_date, account_id FROM goals";
+
+/// Mengubah kolom TEXT `YYYY-MM-DD` pada indeks `index` menjadi `NaiveDate`.
+fn parse_date(index: usize, value: &str) -> rusqlite::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Memetakan satu baris hasil `SELECT_GOALS` ke `Goal`.
+fn map_row(row: &Row) -> rusqlite::Result<Goal> {
+    let currency: Currency = row.get(3)?;
+    Ok(Goal {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        target_amount: Money::new(row.get(2)?, currency),
+        currency,
+        target_date: row
+            .get::<_, Option<String>>(4)?
+            .map(|value| parse_date(4, &value))
+            .transpose()?,
+        account_id: row.get(5)?,
+    })
+}
+
+/// Memetakan satu baris `SELECT c.id, c.goal_id, c.amount, c.date, c.note, c.transfer_id,
+/// g.currency` ke `GoalContribution`.
+fn map_contribution(row: &Row) -> rusqlite::Result<GoalContribution> {
+    let currency: Currency = row.get(6)?;
+    Ok(GoalContribution {
+        id: row.get(0)?,
+        goal_id: row.get(1)?,
+        amount: Money::new(row.get(2)?, currency),
+        date: parse_date(3, &row.get::<_, String>(3)?)?,
+        note: row.get(4)?,
+        transfer_id: row.get(5)?,
+    })
+}
+
+impl GoalRepository for SqliteGoalRepository<'_> {
+    fn create(&self, goal: &NewGoal) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO goals (name, target_amount, currency, target_date, account_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    goal.name,
+                    goal.target_amount.minor_units(),
+                    goal.target_amount.currency(),
+                    goal.target_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                    goal.account_id
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Goal>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{} ORDER BY target_date IS NULL, target_date, id",
+                SELECT_GOALS
+            ))?;
+
+            let goal_iter = stmt.query_map([], map_row)?;
+
+            goal_iter
+                .collect::<rusqlite::Result<Vec<Goal>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Goal, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_GOALS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Target tabungan", id))
+        })
+    }
+
+    fn update(&self, id: i64, goal: &NewGoal) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE goals
+                 SET name = ?1, target_amount = ?2, currency = ?3, target_date = ?4,
+                     account_id = ?5
+                 WHERE id = ?6",
+                params![
+                    goal.name,
+                    goal.target_amount.minor_units(),
+                    goal.target_amount.currency(),
+                    goal.target_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                    goal.account_id,
+                    id
+                ],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Target tabungan", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM goals WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
+    fn add_contribution(
+        &self,
+        goal_id: i64,
+        amount: Money,
+        date: NaiveDate,
+        note: Option<&str>,
+        transfer: Option<&NewTransfer>,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let transfer_id = transfer
+                .map(|transfer| transfer_repository::insert(&tx, transfer))
+                .transpose()?;
+            tx.execute(
+                "INSERT INTO goal_contributions (goal_id, amount, date, note, transfer_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    goal_id,
+                    amount.minor_units(),
+                    date.format("%Y-%m-%d").to_string(),
+                    note,
+                    transfer_id
+                ],
+            )?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_contributions(&self, goal_id: i64) -> Result<Vec<GoalContribution>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT c.id, c.goal_id, c.amount, c.date, c.note, c.transfer_id, g.currency
+                 FROM goal_contributions c
+                 JOIN goals g ON g.id = c.goal_id
+                 WHERE c.goal_id = ?1
+                 ORDER BY c.date, c.id",
+            )?;
+
+            let contribution_iter = stmt.query_map(params![goal_id], map_contribution)?;
+
+            contribution_iter
+                .collect::<rusqlite::Result<Vec<GoalContribution>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn delete_contribution(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let transfer_id: Option<i64> = tx
+                .query_row(
+                    "SELECT transfer_id FROM goal_contributions WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()?
+                .ok_or_else(|| AppError::not_found("Setoran", id))?;
+            // Setoran dihapus lebih dulu agar penghapusan transfer tidak perlu memicu
+            // `ON DELETE SET NULL` pada baris yang akan dihapus.
+            tx.execute("DELETE FROM goal_contributions WHERE id = ?1", params![id])?;
+            if let Some(transfer_id) = transfer_id {
+                transfer_repository::remove(&tx, transfer_id)?;
+            }
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+}
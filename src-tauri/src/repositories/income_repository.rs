@@ -1,117 +1,239 @@
-use crate::db::DB_CONNECTION;
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::debt::DebtDirection;
 use crate::models::income::Income;
+use crate::models::money::{Currency, Money};
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::{account_repository, debt_repository, transaction_filter};
 use chrono::NaiveDateTime;
-use rusqlite::{params, Result};
+use rusqlite::{params, Connection, Row};
 
-/// Membuat entri pemasukan baru di database.
-pub fn create(
-    description: &str,
-    amount: f64,
-    date: NaiveDateTime,
-    category_id: i64,
-) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "incomes";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "description",
+    "amount",
+    "currency",
+    "date",
+    "category_id",
+    "account_id",
+];
 
-    // Simpan datetime sebagai string dengan format YYYY-MM-DD HH:MM:SS
-    let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
+const SELECT_COLUMNS: &str =
+    "SELECT id, description, amount, currency, date, category_id, account_id FROM incomes";
 
-    conn.execute(
-        "INSERT INTO incomes (description, amount, date, category_id) VALUES (?1, ?2, ?3, ?4)",
-        params![description, amount, date_str, category_id],
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+/// Operasi penyimpanan data pemasukan.
+pub trait IncomeRepository {
+    /// Membuat entri pemasukan baru dan memperbarui saldo akunnya dalam satu transaksi.
+    fn create(
+        &self,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua data pemasukan.
+    fn find_all(&self) -> Result<Vec<Income>, AppError>;
+
+    /// Mengambil satu data pemasukan berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Income, AppError>;
+
+    /// Mengambil satu halaman pemasukan yang cocok dengan filter, beserta jumlah totalnya.
+    fn find_page(&self, query: &TransactionQuery) -> Result<Page<Income>, AppError>;
+
+    /// Memperbarui data pemasukan yang ada. Pengaruh nilai lama terhadap saldo
+    /// dibatalkan lalu nilai baru diterapkan, termasuk bila akunnya berpindah.
+    fn update(
+        &self,
+        id: i64,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus data pemasukan berdasarkan ID dan mengembalikan saldo akunnya.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
 }
 
-/// Mengambil semua data pemasukan dari database.
-pub fn find_all() -> Result<Vec<Income>, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    let mut stmt = conn
-        .prepare("SELECT id, description, amount, date, category_id FROM incomes")
-        .map_err(|e| e.to_string())?;
-
-    let income_iter = stmt
-        .query_map([], |row| {
-            // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
-            let date_str: String = row.get(3)?;
-            let date =
-                NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-
-            Ok(Income {
-                id: row.get(0)?,
-                description: row.get(1)?,
-                amount: row.get(2)?,
-                date,
-                category_id: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+/// Implementasi `IncomeRepository` di atas SQLite.
+pub struct SqliteIncomeRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteIncomeRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteIncomeRepository { db }
+    }
+}
 
-    let incomes = income_iter
-        .collect::<rusqlite::Result<Vec<Income>>>()
-        .map_err(|e| e.to_string())?;
+/// Memetakan satu baris hasil query `SELECT_COLUMNS` ke `Income`.
+fn map_row(row: &Row) -> rusqlite::Result<Income> {
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(4)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(3)?;
 
-    Ok(incomes)
+    Ok(Income {
+        id: row.get(0)?,
+        description: row.get(1)?,
+        amount: Money::new(row.get(2)?, currency),
+        currency,
+        date,
+        category_id: row.get(5)?,
+        account_id: row.get(6)?,
+    })
 }
 
-/// Mengambil satu data pemasukan berdasarkan ID.
-pub fn find_by_id(id: i64) -> Result<Income, String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.query_row(
-        "SELECT id, description, amount, date, category_id FROM incomes WHERE id = ?1",
-        params![id],
-        |row| {
-            let date_str: String = row.get(3)?;
-            let date =
-                NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
-                    rusqlite::Error::FromSqlConversionFailure(
-                        3,
-                        rusqlite::types::Type::Text,
-                        Box::new(e),
-                    )
-                })?;
-
-            Ok(Income {
-                id: row.get(0)?,
-                description: row.get(1)?,
-                amount: row.get(2)?,
-                date,
-                category_id: row.get(4)?,
-            })
-        },
-    )
-    .map_err(|e| e.to_string())
+/// Pengaruh satu pemasukan terhadap saldo akun: saldo bertambah sebesar `amount`.
+fn balance_effect(amount: Money) -> Money {
+    amount
 }
 
-/// Memperbarui data pemasukan yang ada di database.
-pub fn update(
-    id: i64,
+/// Membatalkan pengaruh pemasukan lama terhadap saldo akunnya (jika terhubung ke akun).
+fn revert_balance_effect(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let old = conn
+        .query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            map_row,
+        )
+        .map_err(not_found_as("Pemasukan", id))?;
+
+    match old.account_id {
+        Some(account_id) => account_repository::adjust_balance(
+            conn,
+            account_id,
+            balance_effect(old.amount).negated(),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Menyimpan satu pemasukan dan memperbarui saldo akunnya memakai koneksi atau transaksi
+/// milik pemanggil, sehingga bisa digabung dengan perubahan lain secara atomik.
+/// Mengembalikan ID pemasukan yang baru.
+pub fn insert(
+    conn: &Connection,
     description: &str,
-    amount: f64,
+    amount: Money,
     date: NaiveDateTime,
     category_id: i64,
-) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
+    account_id: i64,
+) -> Result<i64, AppError> {
+    // Simpan datetime sebagai string dengan format YYYY-MM-DD HH:MM:SS
     let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
 
     conn.execute(
-        "UPDATE incomes SET description = ?1, amount = ?2, date = ?3, category_id = ?4 WHERE id = ?5",
-        params![description, amount, date_str, category_id, id],
-    )
-    .map(|_| ())
-    .map_err(|e| e.to_string())
+        "INSERT INTO incomes (description, amount, currency, date, category_id, account_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            description,
+            amount.minor_units(),
+            amount.currency(),
+            date_str,
+            category_id,
+            account_id
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    account_repository::adjust_balance(conn, account_id, balance_effect(amount))?;
+    Ok(id)
 }
 
-/// Menghapus data pemasukan dari database berdasarkan ID.
-pub fn delete(id: i64) -> Result<(), String> {
-    let conn = DB_CONNECTION.lock().unwrap();
-    conn.execute("DELETE FROM incomes WHERE id = ?1", params![id])
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+/// Menghapus satu pemasukan dan mengembalikan saldo akunnya memakai koneksi atau transaksi
+/// milik pemanggil.
+pub fn remove(conn: &Connection, id: i64) -> Result<(), AppError> {
+    revert_balance_effect(conn, id)?;
+    conn.execute("DELETE FROM incomes WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+impl IncomeRepository for SqliteIncomeRepository<'_> {
+    fn create(
+        &self,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let id = insert(&tx, description, amount, date, category_id, account_id)?;
+            debt_repository::link_matching_installment(&tx, DebtDirection::Receivable, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Income>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(SELECT_COLUMNS)?;
+
+            let income_iter = stmt.query_map([], map_row)?;
+
+            income_iter
+                .collect::<rusqlite::Result<Vec<Income>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Income, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Pemasukan", id))
+        })
+    }
+
+    fn find_page(&self, query: &TransactionQuery) -> Result<Page<Income>, AppError> {
+        self.db
+            .read(|conn| transaction_filter::find_page(conn, TABLE, SELECT_COLUMNS, query, map_row))
+    }
+
+    fn update(
+        &self,
+        id: i64,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let date_str = date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            revert_balance_effect(&tx, id)?;
+            tx.execute(
+                "UPDATE incomes
+                 SET description = ?1, amount = ?2, currency = ?3, date = ?4, category_id = ?5, account_id = ?6
+                 WHERE id = ?7",
+                params![description, amount.minor_units(), amount.currency(), date_str, category_id, account_id, id],
+            )?;
+            account_repository::adjust_balance(&tx, account_id, balance_effect(amount))?;
+            debt_repository::relink_matching_installment(&tx, DebtDirection::Receivable, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            remove(&tx, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
 }
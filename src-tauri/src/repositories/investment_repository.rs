@@ -0,0 +1,485 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::investment::{
+    Instrument, InstrumentKind, InstrumentPrice, InvestmentTransaction, InvestmentTransactionKind,
+    NewInvestmentTransaction, PriceSource,
+};
+use crate::models::money::{Currency, Money};
+use crate::repositories::account_repository;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, Row};
+use std::error::Error;
+use std::fmt;
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "investment_transactions";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "account_id",
+    "instrument_id",
+    "kind",
+    "units",
+    "amount",
+    "fee",
+    "currency",
+    "date",
+    "note",
+];
+pub const INSTRUMENTS_TABLE: &str = "instruments";
+pub const INSTRUMENT_COLUMNS: &[&str] = &["id", "symbol", "name", "kind", "currency"];
+pub const PRICES_TABLE: &str = "instrument_prices";
+pub const PRICE_COLUMNS: &[&str] = &["id", "instrument_id", "date", "price", "source"];
+
+#[derive(Debug)]
+pub struct InvalidInstrumentKindError(i16);
+
+impl fmt::Display for InvalidInstrumentKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk InstrumentKind: {}", self.0)
+    }
+}
+
+impl Error for InvalidInstrumentKindError {}
+
+impl TryFrom<i16> for InstrumentKind {
+    type Error = InvalidInstrumentKindError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(InstrumentKind::MutualFund),
+            2 => Ok(InstrumentKind::Stock),
+            3 => Ok(InstrumentKind::Gold),
+            4 => Ok(InstrumentKind::Bond),
+            5 => Ok(InstrumentKind::Other),
+            _ => Err(InvalidInstrumentKindError(value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidInvestmentTransactionKindError(i16);
+
+impl fmt::Display for InvalidInvestmentTransactionKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Nilai tidak valid untuk InvestmentTransactionKind: {}",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidInvestmentTransactionKindError {}
+
+impl TryFrom<i16> for InvestmentTransactionKind {
+    type Error = InvalidInvestmentTransactionKindError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(InvestmentTransactionKind::Buy),
+            2 => Ok(InvestmentTransactionKind::Sell),
+            3 => Ok(InvestmentTransactionKind::Dividend),
+            _ => Err(InvalidInvestmentTransactionKindError(value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidPriceSourceError(i16);
+
+impl fmt::Display for InvalidPriceSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk PriceSource: {}", self.0)
+    }
+}
+
+impl Error for InvalidPriceSourceError {}
+
+impl TryFrom<i16> for PriceSource {
+    type Error = InvalidPriceSourceError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PriceSource::Manual),
+            2 => Ok(PriceSource::Csv),
+            _ => Err(InvalidPriceSourceError(value)),
+        }
+    }
+}
+
+/// Operasi penyimpanan instrumen, transaksi investasi, dan riwayat harga.
+pub trait InvestmentRepository {
+    /// Membuat instrumen baru.
+    fn create_instrument(
+        &self,
+        symbol: &str,
+        name: &str,
+        kind: InstrumentKind,
+        currency: Currency,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil semua instrumen, urut simbol.
+    fn find_all_instruments(&self) -> Result<Vec<Instrument>, AppError>;
+
+    /// Mengambil satu instrumen berdasarkan ID.
+    fn find_instrument_by_id(&self, id: i64) -> Result<Instrument, AppError>;
+
+    /// Memperbarui instrumen yang ada.
+    fn update_instrument(
+        &self,
+        id: i64,
+        symbol: &str,
+        name: &str,
+        kind: InstrumentKind,
+        currency: Currency,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus instrumen beserta riwayat harganya. Gagal bila masih ada transaksinya.
+    fn delete_instrument(&self, id: i64) -> Result<(), AppError>;
+
+    /// Mencatat transaksi investasi dan menerapkan mutasi kasnya ke saldo akun dalam satu
+    /// transaksi.
+    fn add_transaction(&self, transaction: &NewInvestmentTransaction) -> Result<(), AppError>;
+
+    /// Mengambil transaksi investasi (hanya milik `account_id` bila diisi), terlama lebih dulu.
+    fn find_transactions(
+        &self,
+        account_id: Option<i64>,
+    ) -> Result<Vec<InvestmentTransaction>, AppError>;
+
+    /// Mengambil satu transaksi investasi berdasarkan ID.
+    fn find_transaction_by_id(&self, id: i64) -> Result<InvestmentTransaction, AppError>;
+
+    /// Menghapus transaksi investasi dan mengembalikan saldo kas akunnya.
+    fn delete_transaction(&self, id: i64) -> Result<(), AppError>;
+
+    /// Menyimpan satu harga. Harga untuk instrumen dan tanggal yang sama diganti.
+    fn upsert_price(
+        &self,
+        instrument_id: i64,
+        date: NaiveDate,
+        price: f64,
+        source: PriceSource,
+    ) -> Result<(), AppError>;
+
+    /// Menyimpan banyak harga sekaligus dalam satu transaksi (dipakai saat impor CSV).
+    fn upsert_prices(
+        &self,
+        prices: &[(i64, NaiveDate, f64)],
+        source: PriceSource,
+    ) -> Result<(), AppError>;
+
+    /// Mengambil riwayat harga sebuah instrumen, terlama lebih dulu.
+    fn find_prices(&self, instrument_id: i64) -> Result<Vec<InstrumentPrice>, AppError>;
+
+    /// Menghapus satu harga berdasarkan ID.
+    fn delete_price(&self, id: i64) -> Result<(), AppError>;
+}
+
+/// Implementasi `InvestmentRepository` di atas SQLite.
+pub struct SqliteInvestmentRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteInvestmentRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteInvestmentRepository { db }
+    }
+}
+
+const SELECT_TRANSACTIONS: &str =
+    "SELECT id, account_id, instrument_id, kind, units, amount, fee, currency, date, note
+     FROM investment_transactions";
+
+/// Memetakan satu baris `SELECT id, symbol, name, kind, currency` ke `Instrument`.
+fn map_instrument(row: &Row) -> rusqlite::Result<Instrument> {
+    let kind_val: i16 = row.get(3)?;
+    let kind = InstrumentKind::try_from(kind_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    Ok(Instrument {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        name: row.get(2)?,
+        kind,
+        currency: row.get(4)?,
+    })
+}
+
+/// Memetakan satu baris hasil `SELECT_TRANSACTIONS` ke `InvestmentTransaction`.
+fn map_transaction(row: &Row) -> rusqlite::Result<InvestmentTransaction> {
+    let kind_val: i16 = row.get(3)?;
+    let kind = InvestmentTransactionKind::try_from(kind_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(8)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(7)?;
+
+    Ok(InvestmentTransaction {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        instrument_id: row.get(2)?,
+        kind,
+        units: row.get(4)?,
+        amount: Money::new(row.get(5)?, currency),
+        fee: Money::new(row.get(6)?, currency),
+        currency,
+        date,
+        note: row.get(9)?,
+    })
+}
+
+/// Memetakan satu baris `SELECT id, instrument_id, date, price, source` ke `InstrumentPrice`.
+fn map_price(row: &Row) -> rusqlite::Result<InstrumentPrice> {
+    let date_str: String = row.get(2)?;
+    let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let source_val: i16 = row.get(4)?;
+    let source = PriceSource::try_from(source_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    Ok(InstrumentPrice {
+        id: row.get(0)?,
+        instrument_id: row.get(1)?,
+        date,
+        price: row.get(3)?,
+        source,
+    })
+}
+
+/// Pengaruh satu transaksi investasi terhadap kas akun: beli mengurangi `amount + fee`,
+/// jual dan dividen menambah `amount - fee`.
+fn balance_effect(
+    kind: InvestmentTransactionKind,
+    amount: Money,
+    fee: Money,
+) -> Result<Money, AppError> {
+    match kind {
+        InvestmentTransactionKind::Buy => Ok(amount.checked_add(fee)?.negated()),
+        InvestmentTransactionKind::Sell | InvestmentTransactionKind::Dividend => {
+            amount.checked_sub(fee)
+        }
+    }
+}
+
+fn upsert_price_with(
+    conn: &Connection,
+    instrument_id: i64,
+    date: NaiveDate,
+    price: f64,
+    source: PriceSource,
+) -> rusqlite::Result<()> {
+    let date_str = date.format("%Y-%m-%d").to_string();
+    conn.execute(
+        "INSERT INTO instrument_prices (instrument_id, date, price, source)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (instrument_id, date)
+         DO UPDATE SET price = excluded.price, source = excluded.source",
+        params![instrument_id, date_str, price, source as i16],
+    )
+    .map(|_| ())
+}
+
+impl InvestmentRepository for SqliteInvestmentRepository<'_> {
+    fn create_instrument(
+        &self,
+        symbol: &str,
+        name: &str,
+        kind: InstrumentKind,
+        currency: Currency,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO instruments (symbol, name, kind, currency) VALUES (?1, ?2, ?3, ?4)",
+                params![symbol, name, kind as i16, currency],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all_instruments(&self) -> Result<Vec<Instrument>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, symbol, name, kind, currency FROM instruments ORDER BY symbol",
+            )?;
+
+            let instrument_iter = stmt.query_map([], map_instrument)?;
+
+            instrument_iter
+                .collect::<rusqlite::Result<Vec<Instrument>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_instrument_by_id(&self, id: i64) -> Result<Instrument, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                "SELECT id, symbol, name, kind, currency FROM instruments WHERE id = ?1",
+                params![id],
+                map_instrument,
+            )
+            .map_err(not_found_as("Instrumen", id))
+        })
+    }
+
+    fn update_instrument(
+        &self,
+        id: i64,
+        symbol: &str,
+        name: &str,
+        kind: InstrumentKind,
+        currency: Currency,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE instruments SET symbol = ?1, name = ?2, kind = ?3, currency = ?4
+                 WHERE id = ?5",
+                params![symbol, name, kind as i16, currency, id],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Instrumen", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_instrument(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM instruments WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
+    fn add_transaction(&self, transaction: &NewInvestmentTransaction) -> Result<(), AppError> {
+        let effect = balance_effect(transaction.kind, transaction.amount, transaction.fee)?;
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO investment_transactions
+                     (account_id, instrument_id, kind, units, amount, fee, currency, date, note)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    transaction.account_id,
+                    transaction.instrument_id,
+                    transaction.kind as i16,
+                    transaction.units,
+                    transaction.amount.minor_units(),
+                    transaction.fee.minor_units(),
+                    transaction.amount.currency(),
+                    transaction.date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    transaction.note
+                ],
+            )?;
+            account_repository::adjust_balance(&tx, transaction.account_id, effect)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_transactions(
+        &self,
+        account_id: Option<i64>,
+    ) -> Result<Vec<InvestmentTransaction>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "{} WHERE ?1 IS NULL OR account_id = ?1 ORDER BY date, id",
+                SELECT_TRANSACTIONS
+            ))?;
+
+            let transaction_iter = stmt.query_map(params![account_id], map_transaction)?;
+
+            transaction_iter
+                .collect::<rusqlite::Result<Vec<InvestmentTransaction>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_transaction_by_id(&self, id: i64) -> Result<InvestmentTransaction, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_TRANSACTIONS),
+                params![id],
+                map_transaction,
+            )
+            .map_err(not_found_as("Transaksi investasi", id))
+        })
+    }
+
+    fn delete_transaction(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            let old = tx
+                .query_row(
+                    &format!("{} WHERE id = ?1", SELECT_TRANSACTIONS),
+                    params![id],
+                    map_transaction,
+                )
+                .map_err(not_found_as("Transaksi investasi", id))?;
+            let effect = balance_effect(old.kind, old.amount, old.fee)?;
+            account_repository::adjust_balance(&tx, old.account_id, effect.negated())?;
+            tx.execute(
+                "DELETE FROM investment_transactions WHERE id = ?1",
+                params![id],
+            )?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn upsert_price(
+        &self,
+        instrument_id: i64,
+        date: NaiveDate,
+        price: f64,
+        source: PriceSource,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            upsert_price_with(conn, instrument_id, date, price, source).map_err(AppError::from)
+        })
+    }
+
+    fn upsert_prices(
+        &self,
+        prices: &[(i64, NaiveDate, f64)],
+        source: PriceSource,
+    ) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+
+            for (instrument_id, date, price) in prices {
+                upsert_price_with(&tx, *instrument_id, *date, *price, source)?;
+            }
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_prices(&self, instrument_id: i64) -> Result<Vec<InstrumentPrice>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, instrument_id, date, price, source
+                 FROM instrument_prices WHERE instrument_id = ?1 ORDER BY date",
+            )?;
+
+            let price_iter = stmt.query_map(params![instrument_id], map_price)?;
+
+            price_iter
+                .collect::<rusqlite::Result<Vec<InstrumentPrice>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn delete_price(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM instrument_prices WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::ledger::{LedgerEntry, LedgerEntryType, LedgerQuery};
+use crate::models::money::{Currency, Money};
+use crate::models::transaction_query::{Page, SortDirection};
+use chrono::NaiveDateTime;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Row};
+use std::error::Error;
+use std::fmt;
+
+/// View dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "ledger_entries";
+pub const COLUMNS: &[&str] = &[
+    "entry_type",
+    "entry_id",
+    "account_id",
+    "counterparty_account_id",
+    "category_id",
+    "description",
+    "delta",
+    "currency",
+    "date",
+];
+
+#[derive(Debug)]
+pub struct InvalidLedgerEntryTypeError(i16);
+
+impl fmt::Display for InvalidLedgerEntryTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk LedgerEntryType: {}", self.0)
+    }
+}
+
+impl Error for InvalidLedgerEntryTypeError {}
+
+impl TryFrom<i16> for LedgerEntryType {
+    type Error = InvalidLedgerEntryTypeError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(LedgerEntryType::Income),
+            2 => Ok(LedgerEntryType::Expense),
+            3 => Ok(LedgerEntryType::TransferOut),
+            4 => Ok(LedgerEntryType::TransferIn),
+            5 => Ok(LedgerEntryType::Investment),
+            6 => Ok(LedgerEntryType::DebtDisbursement),
+            _ => Err(InvalidLedgerEntryTypeError(value)),
+        }
+    }
+}
+
+/// Saldo berjalan dihitung dengan window function per akun atas seluruh riwayat,
+/// baru kemudian difilter, sehingga halaman mana pun tetap menunjukkan saldo yang benar.
+/// Urutan `date, entry_type, entry_id` dipakai baik untuk saldo berjalan maupun hasil.
+const LEDGER_WITH_BALANCE: &str = "
+    WITH entries AS (
+        SELECT l.*,
+               CASE WHEN l.account_id IS NULL THEN NULL
+                    ELSE a.opening_balance + SUM(l.delta) OVER (
+                        PARTITION BY l.account_id
+                        ORDER BY l.date, l.entry_type, l.entry_id
+                        ROWS UNBOUNDED PRECEDING)
+               END AS balance_after
+        FROM ledger_entries l
+        LEFT JOIN accounts a ON a.id = l.account_id
+    )
+    SELECT entry_type, entry_id, account_id, counterparty_account_id, category_id,
+           description, delta, currency, date, balance_after
+    FROM entries";
+
+/// Operasi baca buku besar gabungan.
+pub trait LedgerRepository {
+    /// Mengambil satu halaman entri buku besar beserta jumlah totalnya.
+    fn find_page(&self, query: &LedgerQuery) -> Result<Page<LedgerEntry>, AppError>;
+}
+
+/// Implementasi `LedgerRepository` di atas SQLite.
+pub struct SqliteLedgerRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteLedgerRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteLedgerRepository { db }
+    }
+}
+
+/// Memetakan satu baris hasil query `LEDGER_WITH_BALANCE` ke `LedgerEntry`.
+fn map_row(row: &Row) -> rusqlite::Result<LedgerEntry> {
+    let type_val: i16 = row.get(0)?;
+    let entry_type = LedgerEntryType::try_from(type_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(8)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(7)?;
+    let balance_after: Option<i64> = row.get(9)?;
+
+    Ok(LedgerEntry {
+        entry_type,
+        entry_id: row.get(1)?,
+        account_id: row.get(2)?,
+        counterparty_account_id: row.get(3)?,
+        category_id: row.get(4)?,
+        description: row.get(5)?,
+        amount: Money::new(row.get(6)?, currency),
+        date,
+        balance_after: balance_after.map(|balance| Money::new(balance, currency)),
+    })
+}
+
+impl LedgerRepository for SqliteLedgerRepository<'_> {
+    fn find_page(&self, query: &LedgerQuery) -> Result<Page<LedgerEntry>, AppError> {
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        if let Some(account_id) = query.account_id {
+            conditions.push("account_id = ?");
+            values.push(Value::Integer(account_id));
+        }
+        if let Some(from) = query.date_from {
+            conditions.push("date >= ?");
+            values.push(Value::Text(from.format("%Y-%m-%d %H:%M:%S").to_string()));
+        }
+        if let Some(to) = query.date_to {
+            conditions.push("date <= ?");
+            values.push(Value::Text(to.format("%Y-%m-%d %H:%M:%S").to_string()));
+        }
+        let clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+        let direction = match query.sort_direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+        let limit = query.page_size();
+        let offset = query.offset.unwrap_or(0);
+
+        self.db.read(|conn| {
+            let tx = conn.unchecked_transaction()?;
+
+            let total_count: i64 = tx.query_row(
+                &format!("SELECT COUNT(*) FROM ledger_entries{}", clause),
+                params_from_iter(values.iter()),
+                |row| row.get(0),
+            )?;
+
+            let sql = format!(
+                "{}{} ORDER BY date {dir}, entry_type {dir}, entry_id {dir} LIMIT ? OFFSET ?",
+                LEDGER_WITH_BALANCE,
+                clause,
+                dir = direction
+            );
+            let page_values = values
+                .iter()
+                .cloned()
+                .chain([Value::Integer(limit.into()), Value::Integer(offset.into())]);
+            let items = {
+                let mut stmt = tx.prepare(&sql)?;
+                let rows = stmt.query_map(params_from_iter(page_values), map_row)?;
+                rows.collect::<rusqlite::Result<Vec<LedgerEntry>>>()?
+            };
+
+            tx.commit()?;
+            Ok(Page {
+                items,
+                total_count,
+                limit,
+                offset,
+            })
+        })
+    }
+}
@@ -1,5 +1,17 @@
 // Mendeklarasikan dan mengekspos sub-modul repository.
 pub mod account_repository;
+pub mod budget_repository;
 pub mod category_repository;
+pub mod debt_repository;
+pub mod exchange_rate_repository;
 pub mod expense_repository;
+pub mod goal_repository;
 pub mod income_repository;
+pub mod investment_repository;
+pub mod ledger_repository;
+pub mod net_worth_repository;
+pub mod recurring_repository;
+pub mod report_repository;
+pub mod search_repository;
+pub mod transaction_filter;
+pub mod transfer_repository;
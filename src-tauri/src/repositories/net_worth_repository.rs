@@ -0,0 +1,127 @@
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::money::{Currency, Money};
+use crate::models::net_worth::NetWorthSnapshot;
+use chrono::NaiveDate;
+use rusqlite::{params, Row};
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "net_worth_snapshots";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "date",
+    "currency",
+    "cash",
+    "investments",
+    "receivables",
+    "debts",
+    "overdrafts",
+];
+
+/// Operasi penyimpanan cuplikan kekayaan bersih.
+pub trait NetWorthRepository {
+    /// Mengganti seluruh cuplikan pada `date` dengan `snapshots` dalam satu transaksi.
+    fn replace_snapshots(
+        &self,
+        date: NaiveDate,
+        snapshots: &[NetWorthSnapshot],
+    ) -> Result<(), AppError>;
+
+    /// Mengambil cuplikan dalam rentang tanggal (inklusif, batas kosong berarti tanpa batas),
+    /// terlama lebih dulu.
+    fn find_snapshots(
+        &self,
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+    ) -> Result<Vec<NetWorthSnapshot>, AppError>;
+}
+
+/// Implementasi `NetWorthRepository` di atas SQLite.
+pub struct SqliteNetWorthRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteNetWorthRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteNetWorthRepository { db }
+    }
+}
+
+/// Memetakan satu baris `net_worth_snapshots` (tanpa `id`) ke `NetWorthSnapshot`.
+fn map_row(row: &Row) -> rusqlite::Result<NetWorthSnapshot> {
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDate.
+    let date_str: String = row.get(0)?;
+    let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(1)?;
+
+    Ok(NetWorthSnapshot {
+        date,
+        currency,
+        cash: Money::new(row.get(2)?, currency),
+        investments: Money::new(row.get(3)?, currency),
+        receivables: Money::new(row.get(4)?, currency),
+        debts: Money::new(row.get(5)?, currency),
+        overdrafts: Money::new(row.get(6)?, currency),
+    })
+}
+
+impl NetWorthRepository for SqliteNetWorthRepository<'_> {
+    fn replace_snapshots(
+        &self,
+        date: NaiveDate,
+        snapshots: &[NetWorthSnapshot],
+    ) -> Result<(), AppError> {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "DELETE FROM net_worth_snapshots WHERE date = ?1",
+                params![date_str],
+            )?;
+
+            for snapshot in snapshots {
+                tx.execute(
+                    "INSERT INTO net_worth_snapshots
+                         (date, currency, cash, investments, receivables, debts, overdrafts)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        date_str,
+                        snapshot.currency,
+                        snapshot.cash.minor_units(),
+                        snapshot.investments.minor_units(),
+                        snapshot.receivables.minor_units(),
+                        snapshot.debts.minor_units(),
+                        snapshot.overdrafts.minor_units()
+                    ],
+                )?;
+            }
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_snapshots(
+        &self,
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+    ) -> Result<Vec<NetWorthSnapshot>, AppError> {
+        let date_from = date_from.map(|d| d.format("%Y-%m-%d").to_string());
+        let date_to = date_to.map(|d| d.format("%Y-%m-%d").to_string());
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT date, currency, cash, investments, receivables, debts, overdrafts
+                 FROM net_worth_snapshots
+                 WHERE (?1 IS NULL OR date >= ?1) AND (?2 IS NULL OR date <= ?2)
+                 ORDER BY date, currency",
+            )?;
+
+            let snapshot_iter = stmt.query_map(params![date_from, date_to], map_row)?;
+
+            snapshot_iter
+                .collect::<rusqlite::Result<Vec<NetWorthSnapshot>>>()
+                .map_err(AppError::from)
+        })
+    }
+}
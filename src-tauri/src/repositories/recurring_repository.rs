@@ -0,0 +1,438 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::category::CategoryType;
+use crate::models::debt::DebtDirection;
+use crate::models::money::{Currency, Money};
+use crate::models::recurring::{
+    NewRecurringRule, OccurrenceRecord, OccurrenceStatus, RecurrenceFrequency, RecurringRule,
+    Schedule,
+};
+use crate::repositories::{debt_repository, expense_repository, income_repository};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{params, OptionalExtension, Row};
+use std::error::Error;
+use std::fmt;
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "recurring_rules";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "transaction_type",
+    "description",
+    "amount",
+    "currency",
+    "category_id",
+    "account_id",
+    "frequency",
+    "interval",
+    "weekday",
+    "week_of_month",
+    "start_date",
+    "end_date",
+    "max_occurrences",
+    "materialize_from",
+];
+pub const OCCURRENCES_TABLE: &str = "recurring_occurrences";
+pub const OCCURRENCE_COLUMNS: &[&str] = &[
+    "rule_id",
+    "scheduled_date",
+    "status",
+    "description",
+    "amount",
+    "date",
+    "entry_id",
+];
+
+#[derive(Debug)]
+pub struct InvalidRecurrenceFrequencyError(i16);
+
+impl fmt::Display for InvalidRecurrenceFrequencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk RecurrenceFrequency: {}", self.0)
+    }
+}
+
+impl Error for InvalidRecurrenceFrequencyError {}
+
+impl TryFrom<i16> for RecurrenceFrequency {
+    type Error = InvalidRecurrenceFrequencyError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RecurrenceFrequency::Daily),
+            2 => Ok(RecurrenceFrequency::Weekly),
+            3 => Ok(RecurrenceFrequency::Monthly),
+            4 => Ok(RecurrenceFrequency::Yearly),
+            _ => Err(InvalidRecurrenceFrequencyError(value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidOccurrenceStatusError(i16);
+
+impl fmt::Display for InvalidOccurrenceStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Nilai tidak valid untuk OccurrenceStatus: {}", self.0)
+    }
+}
+
+impl Error for InvalidOccurrenceStatusError {}
+
+impl TryFrom<i16> for OccurrenceStatus {
+    type Error = InvalidOccurrenceStatusError;
+
+    fn try_from(value: i16) -> std::result::Result<Self, Self::Error> {
+        match value {
+            1 => Ok(OccurrenceStatus::Created),
+            2 => Ok(OccurrenceStatus::Skipped),
+            3 => Ok(OccurrenceStatus::Edited),
+            _ => Err(InvalidOccurrenceStatusError(value)),
+        }
+    }
+}
+
+/// Operasi penyimpanan aturan transaksi berulang dan kejadiannya.
+pub trait RecurringRepository {
+    /// Membuat aturan baru.
+    fn create(&self, rule: &NewRecurringRule) -> Result<(), AppError>;
+
+    /// Mengambil semua aturan.
+    fn find_all(&self) -> Result<Vec<RecurringRule>, AppError>;
+
+    /// Mengambil satu aturan berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<RecurringRule, AppError>;
+
+    /// Memperbarui aturan yang ada beserta batas awal pembuatan otomatisnya. Transaksi
+    /// yang sudah dibuat tidak ikut berubah.
+    fn update(
+        &self,
+        id: i64,
+        rule: &NewRecurringRule,
+        materialize_from: Option<NaiveDate>,
+    ) -> Result<(), AppError>;
+
+    /// Menghapus aturan beserta catatan kejadiannya. Transaksi yang sudah dibuat tetap ada.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+
+    /// Mengambil semua catatan kejadian sebuah aturan, urut berdasarkan tanggal jadwal.
+    fn find_occurrences(&self, rule_id: i64) -> Result<Vec<OccurrenceRecord>, AppError>;
+
+    /// Menyimpan status `Skipped` atau `Edited` untuk satu kejadian. Gagal dengan
+    /// `Conflict` bila transaksi kejadian tersebut sudah dibuat.
+    fn save_occurrence(&self, record: &OccurrenceRecord) -> Result<(), AppError>;
+
+    /// Membuat pemasukan/pengeluaran untuk kejadian `scheduled_date` dan menandainya
+    /// `Created` dalam satu transaksi. Tidak melakukan apa pun dan mengembalikan `false`
+    /// bila kejadian itu sudah dibuat atau dilewati.
+    fn materialize(
+        &self,
+        rule: &RecurringRule,
+        scheduled_date: NaiveDate,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+    ) -> Result<bool, AppError>;
+}
+
+/// Implementasi `RecurringRepository` di atas SQLite.
+pub struct SqliteRecurringRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteRecurringRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteRecurringRepository { db }
+    }
+}
+
+const SELECT_RULES: &str = "SELECT id, transaction_type, description, amount, currency,
+    category_id, account_id, frequency, interval, weekday, week_of_month,
+    start_date, end_date, max_occurrences, materialize_from
+    FROM recurring_rules";
+
+/// Mengubah kolom TEXT `YYYY-MM-DD` pada indeks `index` menjadi `NaiveDate`.
+fn parse_date(index: usize, value: &str) -> rusqlite::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Memetakan satu baris hasil `SELECT_RULES` ke `RecurringRule`.
+fn map_row(row: &Row) -> rusqlite::Result<RecurringRule> {
+    let type_val: i16 = row.get(1)?;
+    let transaction_type = CategoryType::try_from(type_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    let frequency_val: i16 = row.get(7)?;
+    let frequency = RecurrenceFrequency::try_from(frequency_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(7, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+
+    let start_date = parse_date(11, &row.get::<_, String>(11)?)?;
+    let end_date = row
+        .get::<_, Option<String>>(12)?
+        .map(|value| parse_date(12, &value))
+        .transpose()?;
+    let materialize_from = row
+        .get::<_, Option<String>>(14)?
+        .map(|value| parse_date(14, &value))
+        .transpose()?;
+
+    let currency: Currency = row.get(4)?;
+    Ok(RecurringRule {
+        id: row.get(0)?,
+        transaction_type,
+        description: row.get(2)?,
+        amount: Money::new(row.get(3)?, currency),
+        currency,
+        category_id: row.get(5)?,
+        account_id: row.get(6)?,
+        schedule: Schedule {
+            frequency,
+            interval: row.get(8)?,
+            start_date,
+            end_date,
+            max_occurrences: row.get(13)?,
+            weekday: row.get(9)?,
+            week_of_month: row.get(10)?,
+        },
+        materialize_from,
+    })
+}
+
+/// Memetakan satu baris `SELECT rule_id, scheduled_date, status, description, amount,
+/// date, entry_id, currency` ke `OccurrenceRecord`. `currency` adalah mata uang aturannya.
+fn map_occurrence(row: &Row) -> rusqlite::Result<OccurrenceRecord> {
+    let status_val: i16 = row.get(2)?;
+    let status = OccurrenceStatus::try_from(status_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    let currency: Currency = row.get(7)?;
+
+    Ok(OccurrenceRecord {
+        rule_id: row.get(0)?,
+        scheduled_date: parse_date(1, &row.get::<_, String>(1)?)?,
+        status,
+        description: row.get(3)?,
+        amount: row
+            .get::<_, Option<i64>>(4)?
+            .map(|amount| Money::new(amount, currency)),
+        date: row
+            .get::<_, Option<String>>(5)?
+            .map(|value| parse_date(5, &value))
+            .transpose()?,
+        entry_id: row.get(6)?,
+    })
+}
+
+impl RecurringRepository for SqliteRecurringRepository<'_> {
+    fn create(&self, rule: &NewRecurringRule) -> Result<(), AppError> {
+        let schedule = &rule.schedule;
+        self.db.write(|conn| {
+            conn.execute(
+                "INSERT INTO recurring_rules (transaction_type, description, amount, currency,
+                     category_id, account_id, frequency, interval, weekday, week_of_month,
+                     start_date, end_date, max_occurrences)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    rule.transaction_type as i16,
+                    rule.description,
+                    rule.amount.minor_units(),
+                    rule.amount.currency(),
+                    rule.category_id,
+                    rule.account_id,
+                    schedule.frequency as i16,
+                    schedule.interval,
+                    schedule.weekday,
+                    schedule.week_of_month,
+                    format_date(schedule.start_date),
+                    schedule.end_date.map(format_date),
+                    schedule.max_occurrences
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<RecurringRule>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(&format!("{} ORDER BY id", SELECT_RULES))?;
+
+            let rule_iter = stmt.query_map([], map_row)?;
+
+            rule_iter
+                .collect::<rusqlite::Result<Vec<RecurringRule>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<RecurringRule, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_RULES),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Transaksi berulang", id))
+        })
+    }
+
+    fn update(
+        &self,
+        id: i64,
+        rule: &NewRecurringRule,
+        materialize_from: Option<NaiveDate>,
+    ) -> Result<(), AppError> {
+        let schedule = &rule.schedule;
+        self.db.write(|conn| {
+            let updated = conn.execute(
+                "UPDATE recurring_rules
+                 SET transaction_type = ?1, description = ?2, amount = ?3, currency = ?4,
+                     category_id = ?5, account_id = ?6, frequency = ?7, interval = ?8,
+                     weekday = ?9, week_of_month = ?10, start_date = ?11, end_date = ?12,
+                     max_occurrences = ?13, materialize_from = ?14
+                 WHERE id = ?15",
+                params![
+                    rule.transaction_type as i16,
+                    rule.description,
+                    rule.amount.minor_units(),
+                    rule.amount.currency(),
+                    rule.category_id,
+                    rule.account_id,
+                    schedule.frequency as i16,
+                    schedule.interval,
+                    schedule.weekday,
+                    schedule.week_of_month,
+                    format_date(schedule.start_date),
+                    schedule.end_date.map(format_date),
+                    schedule.max_occurrences,
+                    materialize_from.map(format_date),
+                    id
+                ],
+            )?;
+
+            if updated == 0 {
+                return Err(AppError::not_found("Transaksi berulang", id));
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            conn.execute("DELETE FROM recurring_rules WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
+    fn find_occurrences(&self, rule_id: i64) -> Result<Vec<OccurrenceRecord>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT o.rule_id, o.scheduled_date, o.status, o.description, o.amount,
+                        o.date, o.entry_id, r.currency
+                 FROM recurring_occurrences o
+                 JOIN recurring_rules r ON r.id = o.rule_id
+                 WHERE o.rule_id = ?1
+                 ORDER BY o.scheduled_date",
+            )?;
+
+            let occurrence_iter = stmt.query_map(params![rule_id], map_occurrence)?;
+
+            occurrence_iter
+                .collect::<rusqlite::Result<Vec<OccurrenceRecord>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn save_occurrence(&self, record: &OccurrenceRecord) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let saved = conn.execute(
+                "INSERT INTO recurring_occurrences
+                     (rule_id, scheduled_date, status, description, amount, date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT (rule_id, scheduled_date) DO UPDATE
+                 SET status = excluded.status, description = excluded.description,
+                     amount = excluded.amount, date = excluded.date
+                 WHERE recurring_occurrences.status <> 1",
+                params![
+                    record.rule_id,
+                    format_date(record.scheduled_date),
+                    record.status as i16,
+                    record.description,
+                    record.amount.map(|amount| amount.minor_units()),
+                    record.date.map(format_date)
+                ],
+            )?;
+
+            if saved == 0 {
+                return Err(AppError::Conflict(
+                    "Transaksi untuk jadwal ini sudah dibuat; ubah transaksinya secara langsung."
+                        .to_string(),
+                ));
+            }
+            Ok(())
+        })
+    }
+
+    fn materialize(
+        &self,
+        rule: &RecurringRule,
+        scheduled_date: NaiveDate,
+        description: &str,
+        amount: Money,
+        date: NaiveDateTime,
+    ) -> Result<bool, AppError> {
+        let scheduled = format_date(scheduled_date);
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+
+            let status: Option<i16> = tx
+                .query_row(
+                    "SELECT status FROM recurring_occurrences
+                     WHERE rule_id = ?1 AND scheduled_date = ?2",
+                    params![rule.id, scheduled],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if matches!(
+                status.map(OccurrenceStatus::try_from),
+                Some(Ok(OccurrenceStatus::Created | OccurrenceStatus::Skipped))
+            ) {
+                return Ok(false);
+            }
+
+            let insert = match rule.transaction_type {
+                CategoryType::Income => income_repository::insert,
+                CategoryType::Expense => expense_repository::insert,
+            };
+            let entry_id = insert(
+                &tx,
+                description,
+                amount,
+                date,
+                rule.category_id,
+                rule.account_id,
+            )?;
+            let direction = match rule.transaction_type {
+                CategoryType::Income => DebtDirection::Receivable,
+                CategoryType::Expense => DebtDirection::Payable,
+            };
+            debt_repository::link_matching_installment(&tx, direction, entry_id)?;
+            tx.execute(
+                "INSERT INTO recurring_occurrences (rule_id, scheduled_date, status, entry_id)
+                 VALUES (?1, ?2, 1, ?3)
+                 ON CONFLICT (rule_id, scheduled_date) DO UPDATE
+                 SET status = 1, entry_id = excluded.entry_id",
+                params![rule.id, scheduled, entry_id],
+            )?;
+
+            tx.commit()?;
+            Ok(true)
+        })
+    }
+}
@@ -0,0 +1,253 @@
+//! Agregasi laporan keuangan langsung di SQL (`GROUP BY`) di atas view `ledger_entries`,
+//! sehingga dashboard tidak perlu memuat seluruh riwayat transaksi.
+//! Transfer tidak ikut dihitung: hanya entri pemasukan (1) dan pengeluaran (2). Pembayaran
+//! utang/piutang yang tertaut di `debt_payments` juga dikecualikan karena hanya memindahkan
+//! pokok pinjaman; sisanya terlihat pada status utang, bukan sebagai pemasukan/pengeluaran.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::money::{Currency, Money};
+use crate::models::report::{
+    AccountTotal, CategoryTotal, IncomeExpenseTotal, PeriodTotal, ReportPeriod, ReportQuery,
+};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+/// Operasi baca untuk laporan keuangan.
+pub trait ReportRepository {
+    /// Total pemasukan, pengeluaran, dan selisihnya per periode, terlama lebih dulu.
+    fn totals_by_period(
+        &self,
+        period: ReportPeriod,
+        query: &ReportQuery,
+    ) -> Result<Vec<PeriodTotal>, AppError>;
+
+    /// Total per kategori, terbesar lebih dulu. `category_type` membatasi ke kategori
+    /// pemasukan atau pengeluaran saja; `limit` membatasi jumlah kategori yang dikembalikan.
+    /// Dengan `rollup`, total setiap kategori mencakup transaksi seluruh turunannya.
+    fn totals_by_category(
+        &self,
+        category_type: Option<CategoryType>,
+        limit: Option<u32>,
+        rollup: bool,
+        query: &ReportQuery,
+    ) -> Result<Vec<CategoryTotal>, AppError>;
+
+    /// Total pemasukan, pengeluaran, dan selisihnya per akun.
+    fn totals_by_account(&self, query: &ReportQuery) -> Result<Vec<AccountTotal>, AppError>;
+
+    /// Total pemasukan, pengeluaran, dan selisihnya untuk seluruh periode filter.
+    fn income_expense_summary(
+        &self,
+        query: &ReportQuery,
+    ) -> Result<Vec<IncomeExpenseTotal>, AppError>;
+}
+
+/// Implementasi `ReportRepository` di atas SQLite.
+pub struct SqliteReportRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteReportRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteReportRepository { db }
+    }
+}
+
+/// Kolom total yang sama untuk semua laporan pemasukan-vs-pengeluaran.
+const INCOME_EXPENSE_COLUMNS: &str = "l.currency,
+    COALESCE(SUM(CASE WHEN l.entry_type = 1 THEN l.delta END), 0),
+    COALESCE(-SUM(CASE WHEN l.entry_type = 2 THEN l.delta END), 0)";
+
+/// Pasangan (kategori, leluhur) untuk setiap kategori, termasuk dirinya sendiri,
+/// sehingga transaksi sebuah sub-kategori ikut dihitung pada setiap induknya.
+const CATEGORY_ANCESTRY: &str = "
+    WITH RECURSIVE category_ancestry(category_id, ancestor_id) AS (
+        SELECT id, id FROM categories
+        UNION
+        SELECT x.category_id, c.parent_id
+        FROM category_ancestry x
+        JOIN categories c ON c.id = x.ancestor_id
+        WHERE c.parent_id IS NOT NULL
+    )";
+
+/// Membangun kondisi `WHERE` dari filter laporan (selalu dimulai dengan pembatasan jenis entri
+/// dan pengecualian pembayaran utang/piutang).
+fn build_filter(query: &ReportQuery) -> (String, Vec<Value>) {
+    let mut clause = String::from(
+        " WHERE l.entry_type IN (1, 2)
+           AND NOT EXISTS (SELECT 1 FROM debt_payments p
+                           WHERE (l.entry_type = 1 AND p.income_id = l.entry_id)
+                              OR (l.entry_type = 2 AND p.expense_id = l.entry_id))",
+    );
+    let mut values = Vec::new();
+
+    if let Some(from) = query.date_from {
+        clause.push_str(" AND l.date >= ?");
+        values.push(Value::Text(from.format("%Y-%m-%d").to_string()));
+    }
+    if let Some(to) = query.date_to {
+        // Tanggal disimpan beserta jam, jadi batas akhir adalah awal hari berikutnya.
+        clause.push_str(" AND l.date < date(?, '+1 day')");
+        values.push(Value::Text(to.format("%Y-%m-%d").to_string()));
+    }
+    if !query.account_ids.is_empty() {
+        let placeholders = vec!["?"; query.account_ids.len()].join(", ");
+        clause.push_str(&format!(" AND l.account_id IN ({})", placeholders));
+        values.extend(query.account_ids.iter().map(|id| Value::Integer(*id)));
+    }
+    (clause, values)
+}
+
+/// Memetakan `INCOME_EXPENSE_COLUMNS` yang dimulai pada kolom `start`.
+fn map_totals(row: &Row, start: usize) -> rusqlite::Result<IncomeExpenseTotal> {
+    let currency: Currency = row.get(start)?;
+    let income: i64 = row.get(start + 1)?;
+    let expense: i64 = row.get(start + 2)?;
+    Ok(IncomeExpenseTotal {
+        currency,
+        income: Money::new(income, currency),
+        expense: Money::new(expense, currency),
+        net: Money::new(income - expense, currency),
+    })
+}
+
+fn query_all<T>(
+    conn: &Connection,
+    sql: &str,
+    values: Vec<Value>,
+    map_row: impl FnMut(&Row) -> rusqlite::Result<T>,
+) -> Result<Vec<T>, AppError> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params_from_iter(values), map_row)?;
+    rows.collect::<rusqlite::Result<Vec<T>>>()
+        .map_err(AppError::from)
+}
+
+impl ReportRepository for SqliteReportRepository<'_> {
+    fn totals_by_period(
+        &self,
+        period: ReportPeriod,
+        query: &ReportQuery,
+    ) -> Result<Vec<PeriodTotal>, AppError> {
+        let format = match period {
+            ReportPeriod::Day => "%Y-%m-%d",
+            ReportPeriod::Week => "%G-W%V",
+            ReportPeriod::Month => "%Y-%m",
+            ReportPeriod::Year => "%Y",
+        };
+        let (clause, filter_values) = build_filter(query);
+        let sql = format!(
+            "SELECT strftime(?, l.date) AS period, {}
+             FROM ledger_entries l{}
+             GROUP BY period, l.currency
+             ORDER BY period, l.currency",
+            INCOME_EXPENSE_COLUMNS, clause
+        );
+        let mut values = vec![Value::Text(format.to_string())];
+        values.extend(filter_values);
+
+        self.db.read(|conn| {
+            query_all(conn, &sql, values, |row| {
+                Ok(PeriodTotal {
+                    period: row.get(0)?,
+                    totals: map_totals(row, 1)?,
+                })
+            })
+        })
+    }
+
+    fn totals_by_category(
+        &self,
+        category_type: Option<CategoryType>,
+        limit: Option<u32>,
+        rollup: bool,
+        query: &ReportQuery,
+    ) -> Result<Vec<CategoryTotal>, AppError> {
+        let (mut clause, mut values) = build_filter(query);
+        if let Some(category_type) = category_type {
+            clause.push_str(" AND c.category_type = ?");
+            values.push(Value::Integer(category_type as i64));
+        }
+        let mut sql = format!(
+            "{}
+             SELECT c.id, c.name, c.category_type, c.parent_id, l.currency,
+                    SUM(ABS(l.delta)) AS total, COUNT(*)
+             FROM ledger_entries l
+             {}{}
+             GROUP BY c.id, l.currency
+             ORDER BY total DESC, c.name",
+            if rollup { CATEGORY_ANCESTRY } else { "" },
+            if rollup {
+                "JOIN category_ancestry x ON x.category_id = l.category_id
+                 JOIN categories c ON c.id = x.ancestor_id"
+            } else {
+                "JOIN categories c ON c.id = l.category_id"
+            },
+            clause
+        );
+        if let Some(limit) = limit {
+            sql.push_str(" LIMIT ?");
+            values.push(Value::Integer(limit.into()));
+        }
+
+        self.db.read(|conn| {
+            query_all(conn, &sql, values, |row| {
+                let type_val: i16 = row.get(2)?;
+                let category_type = CategoryType::try_from(type_val).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        2,
+                        rusqlite::types::Type::Integer,
+                        Box::new(e),
+                    )
+                })?;
+                let currency: Currency = row.get(4)?;
+                Ok(CategoryTotal {
+                    category_id: row.get(0)?,
+                    category_name: row.get(1)?,
+                    category_type,
+                    parent_id: row.get(3)?,
+                    total: Money::new(row.get(5)?, currency),
+                    transaction_count: row.get(6)?,
+                })
+            })
+        })
+    }
+
+    fn totals_by_account(&self, query: &ReportQuery) -> Result<Vec<AccountTotal>, AppError> {
+        let (clause, values) = build_filter(query);
+        let sql = format!(
+            "SELECT a.id, a.name, {}
+             FROM ledger_entries l
+             JOIN accounts a ON a.id = l.account_id{}
+             GROUP BY a.id, l.currency
+             ORDER BY a.name, a.id",
+            INCOME_EXPENSE_COLUMNS, clause
+        );
+
+        self.db.read(|conn| {
+            query_all(conn, &sql, values, |row| {
+                Ok(AccountTotal {
+                    account_id: row.get(0)?,
+                    account_name: row.get(1)?,
+                    totals: map_totals(row, 2)?,
+                })
+            })
+        })
+    }
+
+    fn income_expense_summary(
+        &self,
+        query: &ReportQuery,
+    ) -> Result<Vec<IncomeExpenseTotal>, AppError> {
+        let (clause, values) = build_filter(query);
+        let sql = format!(
+            "SELECT {} FROM ledger_entries l{} GROUP BY l.currency ORDER BY l.currency",
+            INCOME_EXPENSE_COLUMNS, clause
+        );
+
+        self.db
+            .read(|conn| query_all(conn, &sql, values, |row| map_totals(row, 0)))
+    }
+}
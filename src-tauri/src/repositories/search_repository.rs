@@ -0,0 +1,82 @@
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::ledger::LedgerEntryType;
+use crate::models::money::{Currency, Money};
+use crate::models::search::SearchResult;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Row};
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "transaction_search";
+pub const COLUMNS: &[&str] = &["description", "entry_type", "entry_id"];
+
+/// Penanda awal dan akhir kata yang cocok di dalam `snippet`, dipilih karena tidak
+/// mungkin muncul di deskripsi yang diketik pengguna.
+pub const MATCH_START: char = '\u{2}';
+pub const MATCH_END: char = '\u{3}';
+
+/// Operasi pencarian teks penuh atas transaksi.
+pub trait SearchRepository {
+    /// Menjalankan ekspresi `MATCH` FTS5 yang sudah aman, paling relevan lebih dulu.
+    /// `snippet` pada hasilnya masih memakai penanda `MATCH_START`/`MATCH_END`.
+    fn search(&self, match_query: &str, limit: u32) -> Result<Vec<SearchResult>, AppError>;
+}
+
+/// Implementasi `SearchRepository` di atas SQLite FTS5.
+pub struct SqliteSearchRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteSearchRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteSearchRepository { db }
+    }
+}
+
+fn map_row(row: &Row) -> rusqlite::Result<SearchResult> {
+    let type_val: i16 = row.get(0)?;
+    let entry_type = LedgerEntryType::try_from(type_val).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Integer, Box::new(e))
+    })?;
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(6)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(5)?;
+
+    Ok(SearchResult {
+        entry_type,
+        entry_id: row.get(1)?,
+        description: row.get(2)?,
+        snippet: row.get(3)?,
+        amount: Money::new(row.get(4)?, currency),
+        date,
+        category_id: row.get(7)?,
+        account_id: row.get(8)?,
+    })
+}
+
+impl SearchRepository for SqliteSearchRepository<'_> {
+    fn search(&self, match_query: &str, limit: u32) -> Result<Vec<SearchResult>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT s.entry_type, s.entry_id, l.description,
+                        snippet(transaction_search, 0, char(2), char(3), '…', 12),
+                        ABS(l.delta), l.currency, l.date, l.category_id, l.account_id
+                 FROM transaction_search s
+                 JOIN ledger_entries l
+                   ON l.entry_type = s.entry_type AND l.entry_id = s.entry_id
+                 WHERE transaction_search MATCH ?1
+                 ORDER BY s.rank, l.date DESC
+                 LIMIT ?2",
+            )?;
+
+            let result_iter = stmt.query_map(params![match_query, limit], map_row)?;
+
+            result_iter
+                .collect::<rusqlite::Result<Vec<SearchResult>>>()
+                .map_err(AppError::from)
+        })
+    }
+}
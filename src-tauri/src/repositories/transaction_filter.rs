@@ -0,0 +1,138 @@
+//! Menerjemahkan `TransactionQuery` menjadi query SQL. Tabel `expenses` dan `incomes`
+//! memiliki kolom yang sama, sehingga keduanya memakai pembangun query ini.
+
+use crate::error::AppError;
+use crate::models::money::scale_sql;
+use crate::models::transaction_query::{Page, SortDirection, SortKey, TransactionQuery};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+/// Klausa `WHERE` beserta nilai parameternya, berurutan sesuai placeholder `?`.
+struct Filter {
+    clause: String,
+    values: Vec<Value>,
+}
+
+fn build_filter(query: &TransactionQuery) -> Filter {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(from) = query.date_from {
+        conditions.push("date >= ?".to_string());
+        values.push(Value::Text(from.format("%Y-%m-%d %H:%M:%S").to_string()));
+    }
+    if let Some(to) = query.date_to {
+        conditions.push("date <= ?".to_string());
+        values.push(Value::Text(to.format("%Y-%m-%d %H:%M:%S").to_string()));
+    }
+    for (column, ids) in [
+        ("category_id", &query.category_ids),
+        ("account_id", &query.account_ids),
+    ] {
+        if !ids.is_empty() {
+            let placeholders = vec!["?"; ids.len()].join(", ");
+            conditions.push(format!("{} IN ({})", column, placeholders));
+            values.extend(ids.iter().map(|id| Value::Integer(*id)));
+        }
+    }
+    // Jumlah disimpan dalam satuan minor, sedangkan batas diberikan dalam satuan mayor.
+    if let Some(min) = query.min_amount {
+        conditions.push(format!("amount >= ROUND(? * {})", scale_sql("currency")));
+        values.push(Value::Real(min));
+    }
+    if let Some(max) = query.max_amount {
+        conditions.push(format!("amount <= ROUND(? * {})", scale_sql("currency")));
+        values.push(Value::Real(max));
+    }
+    if let Some(search) = query.search.as_deref().map(str::trim) {
+        if !search.is_empty() {
+            conditions.push("description LIKE ? ESCAPE '\\'".to_string());
+            values.push(Value::Text(format!("%{}%", escape_like(search))));
+        }
+    }
+
+    let clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+    Filter { clause, values }
+}
+
+/// Meloloskan karakter wildcard `LIKE` agar teks pencarian dicocokkan apa adanya.
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn order_by(query: &TransactionQuery, table: &str) -> String {
+    let direction = match query.sort_direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    let key = match query.sort_by {
+        SortKey::Date => "date".to_string(),
+        // Bandingkan dalam satuan mayor agar urutan tetap wajar antar mata uang.
+        SortKey::Amount => format!("(amount * 1.0 / {})", scale_sql("currency")),
+        SortKey::Description => "description COLLATE NOCASE".to_string(),
+        SortKey::Category => format!(
+            "(SELECT name FROM categories WHERE categories.id = {}.category_id) COLLATE NOCASE",
+            table
+        ),
+    };
+    // `id` sebagai pemutus seri agar urutan antar halaman stabil.
+    format!(
+        " ORDER BY {key} {dir}, id {dir}",
+        key = key,
+        dir = direction
+    )
+}
+
+/// Menjalankan `query` terhadap `table`. `select_columns` adalah `SELECT ... FROM table`
+/// milik repository dan `map_row` pemeta barisnya. Jumlah total dan isi halaman dibaca
+/// dalam satu transaksi baca sehingga keduanya konsisten.
+pub fn find_page<T>(
+    conn: &Connection,
+    table: &str,
+    select_columns: &str,
+    query: &TransactionQuery,
+    map_row: fn(&Row) -> rusqlite::Result<T>,
+) -> Result<Page<T>, AppError> {
+    let filter = build_filter(query);
+    let limit = query.page_size();
+    let offset = query.offset.unwrap_or(0);
+
+    let tx = conn.unchecked_transaction()?;
+
+    let total_count: i64 = tx.query_row(
+        &format!("SELECT COUNT(*) FROM {}{}", table, filter.clause),
+        params_from_iter(filter.values.iter()),
+        |row| row.get(0),
+    )?;
+
+    let sql = format!(
+        "{}{}{} LIMIT ? OFFSET ?",
+        select_columns,
+        filter.clause,
+        order_by(query, table)
+    );
+    let page_values = filter
+        .values
+        .iter()
+        .cloned()
+        .chain([Value::Integer(limit.into()), Value::Integer(offset.into())]);
+    let items = {
+        let mut stmt = tx.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(page_values), map_row)?;
+        rows.collect::<rusqlite::Result<Vec<T>>>()?
+    };
+
+    tx.commit()?;
+    Ok(Page {
+        items,
+        total_count,
+        limit,
+        offset,
+    })
+}
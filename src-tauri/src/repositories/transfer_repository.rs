@@ -0,0 +1,255 @@
+use crate::db::Database;
+use crate::error::{not_found_as, AppError};
+use crate::models::money::{Currency, Money};
+use crate::models::transfer::{NewTransfer, Transfer};
+use crate::repositories::account_repository;
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, Row};
+
+/// Tabel dan kolom yang dibutuhkan repository ini (diperiksa saat startup).
+pub const TABLE: &str = "transfers";
+pub const COLUMNS: &[&str] = &[
+    "id",
+    "description",
+    "from_account_id",
+    "to_account_id",
+    "amount",
+    "currency",
+    "fee",
+    "rate",
+    "received_amount",
+    "received_currency",
+    "date",
+];
+
+const SELECT_COLUMNS: &str = "SELECT id, description, from_account_id, to_account_id, amount, \
+     currency, fee, rate, received_amount, received_currency, date FROM transfers";
+
+/// Operasi penyimpanan data transfer antar akun.
+pub trait TransferRepository {
+    /// Mencatat transfer baru dan memperbarui saldo kedua akun dalam satu transaksi SQLite.
+    fn create(&self, transfer: &NewTransfer) -> Result<(), AppError>;
+
+    /// Mengambil semua transfer, terbaru lebih dulu.
+    fn find_all(&self) -> Result<Vec<Transfer>, AppError>;
+
+    /// Mengambil satu transfer berdasarkan ID.
+    fn find_by_id(&self, id: i64) -> Result<Transfer, AppError>;
+
+    /// Memperbarui transfer: pengaruh lama terhadap saldo dibatalkan, lalu nilai baru diterapkan.
+    fn update(&self, id: i64, transfer: &NewTransfer) -> Result<(), AppError>;
+
+    /// Menghapus transfer dan mengembalikan saldo kedua akun. Transfer yang tercatat sebagai
+    /// setoran target tabungan ditolak; hapus setorannya agar transfernya ikut terhapus.
+    fn delete(&self, id: i64) -> Result<(), AppError>;
+}
+
+/// Implementasi `TransferRepository` di atas SQLite.
+pub struct SqliteTransferRepository<'a> {
+    db: &'a Database,
+}
+
+impl<'a> SqliteTransferRepository<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        SqliteTransferRepository { db }
+    }
+}
+
+/// Memetakan satu baris hasil query `SELECT_COLUMNS` ke `Transfer`.
+fn map_row(row: &Row) -> rusqlite::Result<Transfer> {
+    // Kolom date disimpan sebagai TEXT, perlu di-parse kembali ke NaiveDateTime.
+    let date_str: String = row.get(10)?;
+    let date = NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    let currency: Currency = row.get(5)?;
+    let received_currency: Currency = row.get(9)?;
+
+    Ok(Transfer {
+        id: row.get(0)?,
+        description: row.get(1)?,
+        from_account_id: row.get(2)?,
+        to_account_id: row.get(3)?,
+        amount: Money::new(row.get(4)?, currency),
+        currency,
+        fee: Money::new(row.get(6)?, currency),
+        rate: row.get(7)?,
+        received_amount: Money::new(row.get(8)?, received_currency),
+        received_currency,
+        date,
+    })
+}
+
+/// Menerapkan (atau dengan `reverse = true`, membatalkan) pengaruh transfer terhadap
+/// saldo kedua akun: akun asal berkurang `amount + fee`, akun tujuan bertambah `received_amount`.
+fn apply_balance_effect(
+    conn: &Connection,
+    from_account_id: i64,
+    to_account_id: i64,
+    debit: Money,
+    credit: Money,
+    reverse: bool,
+) -> Result<(), AppError> {
+    let (debit, credit) = if reverse {
+        (debit, credit.negated())
+    } else {
+        (debit.negated(), credit)
+    };
+    account_repository::adjust_balance(conn, from_account_id, debit)?;
+    account_repository::adjust_balance(conn, to_account_id, credit)
+}
+
+fn revert_balance_effect(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let old = conn.query_row(
+        &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+        params![id],
+        map_row,
+    )?;
+    apply_balance_effect(
+        conn,
+        old.from_account_id,
+        old.to_account_id,
+        old.amount.checked_add(old.fee)?,
+        old.received_amount,
+        true,
+    )
+}
+
+/// Mencatat transfer dan memperbarui saldo kedua akun memakai koneksi atau transaksi milik
+/// pemanggil. Mengembalikan ID transfer yang baru.
+pub fn insert(conn: &Connection, transfer: &NewTransfer) -> Result<i64, AppError> {
+    // Simpan datetime sebagai string dengan format YYYY-MM-DD HH:MM:SS
+    let date_str = transfer.date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+    conn.execute(
+        "INSERT INTO transfers (description, from_account_id, to_account_id, amount, currency,
+                                fee, rate, received_amount, received_currency, date)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            transfer.description,
+            transfer.from_account_id,
+            transfer.to_account_id,
+            transfer.amount.minor_units(),
+            transfer.amount.currency(),
+            transfer.fee.minor_units(),
+            transfer.rate,
+            transfer.received_amount.minor_units(),
+            transfer.received_amount.currency(),
+            date_str
+        ],
+    )?;
+    let id = conn.last_insert_rowid();
+    apply_balance_effect(
+        conn,
+        transfer.from_account_id,
+        transfer.to_account_id,
+        transfer.amount.checked_add(transfer.fee)?,
+        transfer.received_amount,
+        false,
+    )?;
+    Ok(id)
+}
+
+/// Menghapus transfer dan mengembalikan saldo kedua akun memakai koneksi atau transaksi
+/// milik pemanggil. Transfer yang masih ditautkan ke setoran target tabungan ditolak agar
+/// setorannya tidak tetap terhitung tanpa uangnya.
+pub fn remove(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let contributions: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM goal_contributions WHERE transfer_id = ?1",
+        params![id],
+        |row| row.get(0),
+    )?;
+    if contributions > 0 {
+        return Err(AppError::Conflict(
+            "Transfer ini tercatat sebagai setoran target tabungan. Hapus setorannya untuk \
+             membatalkan transfer."
+                .to_string(),
+        ));
+    }
+    revert_balance_effect(conn, id)?;
+    conn.execute("DELETE FROM transfers WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+impl TransferRepository for SqliteTransferRepository<'_> {
+    fn create(&self, transfer: &NewTransfer) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            insert(&tx, transfer)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn find_all(&self) -> Result<Vec<Transfer>, AppError> {
+        self.db.read(|conn| {
+            let mut stmt =
+                conn.prepare(&format!("{} ORDER BY date DESC, id DESC", SELECT_COLUMNS))?;
+
+            let transfer_iter = stmt.query_map([], map_row)?;
+
+            transfer_iter
+                .collect::<rusqlite::Result<Vec<Transfer>>>()
+                .map_err(AppError::from)
+        })
+    }
+
+    fn find_by_id(&self, id: i64) -> Result<Transfer, AppError> {
+        self.db.read(|conn| {
+            conn.query_row(
+                &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+                params![id],
+                map_row,
+            )
+            .map_err(not_found_as("Transfer", id))
+        })
+    }
+
+    fn update(&self, id: i64, transfer: &NewTransfer) -> Result<(), AppError> {
+        let date_str = transfer.date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            revert_balance_effect(&tx, id)?;
+            tx.execute(
+                "UPDATE transfers
+                 SET description = ?1, from_account_id = ?2, to_account_id = ?3, amount = ?4,
+                     currency = ?5, fee = ?6, rate = ?7, received_amount = ?8,
+                     received_currency = ?9, date = ?10
+                 WHERE id = ?11",
+                params![
+                    transfer.description,
+                    transfer.from_account_id,
+                    transfer.to_account_id,
+                    transfer.amount.minor_units(),
+                    transfer.amount.currency(),
+                    transfer.fee.minor_units(),
+                    transfer.rate,
+                    transfer.received_amount.minor_units(),
+                    transfer.received_amount.currency(),
+                    date_str,
+                    id
+                ],
+            )?;
+            apply_balance_effect(
+                &tx,
+                transfer.from_account_id,
+                transfer.to_account_id,
+                transfer.amount.checked_add(transfer.fee)?,
+                transfer.received_amount,
+                false,
+            )?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    fn delete(&self, id: i64) -> Result<(), AppError> {
+        self.db.write(|conn| {
+            let tx = conn.transaction()?;
+            remove(&tx, id)?;
+
+            tx.commit().map_err(AppError::from)
+        })
+    }
+}
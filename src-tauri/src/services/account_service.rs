@@ -1,51 +1,236 @@
 //! Modul ini berisi logika bisnis yang terkait dengan akun.
 //! Service layer bertindak sebagai perantara antara command layer (API) dan repository layer (database).
 
-use crate::models::account::Account;
-use crate::repositories::account_repository;
+use crate::error::AppError;
+use crate::models::account::{Account, AccountType};
+use crate::models::money::{Currency, Money};
+use crate::repositories::account_repository::AccountRepository;
 
-/// Membuat akun baru.
-pub fn create_account(name: &str, description: Option<&str>, balance: f64) -> Result<(), String> {
-    // Di sini Anda bisa menambahkan validasi, misalnya:
-    if name.trim().is_empty() {
-        return Err("Nama akun tidak boleh kosong.".to_string());
+/// Service akun, bergantung pada `AccountRepository` sehingga bisa diuji dengan
+/// database in-memory.
+pub struct AccountService<A> {
+    accounts: A,
+}
+
+impl<A: AccountRepository> AccountService<A> {
+    pub fn new(accounts: A) -> Self {
+        AccountService { accounts }
     }
-    if balance < 0.0 {
-        return Err("Saldo awal tidak boleh negatif.".to_string());
+
+    /// Membuat akun baru. Jika mata uang tidak diisi, akun memakai mata uang default (IDR).
+    pub fn create_account(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        balance: f64,
+        currency: Option<&str>,
+        account_type: AccountType,
+    ) -> Result<(), AppError> {
+        // Di sini Anda bisa menambahkan validasi, misalnya:
+        if name.trim().is_empty() {
+            return Err(AppError::validation(
+                "name",
+                "Nama akun tidak boleh kosong.",
+            ));
+        }
+        let currency = currency
+            .map(Currency::parse)
+            .transpose()?
+            .unwrap_or_default();
+        let balance = Money::from_major_as(balance, currency, "balance")?;
+        if balance.is_negative() {
+            return Err(AppError::validation(
+                "balance",
+                "Saldo awal tidak boleh negatif.",
+            ));
+        }
+
+        // Panggil repository untuk menyimpan data.
+        self.accounts
+            .create(name, description, balance, account_type)
     }
 
-    // Panggil repository untuk menyimpan data.
-    account_repository::create(name, description, balance)
-}
+    /// Mengambil semua akun yang ada.
+    pub fn get_all_accounts(&self) -> Result<Vec<Account>, AppError> {
+        self.accounts.find_all()
+    }
 
-/// Mengambil semua akun yang ada.
-pub fn get_all_accounts() -> Result<Vec<Account>, String> {
-    account_repository::find_all()
-}
+    /// Mengambil satu akun berdasarkan ID-nya.
+    pub fn get_account_by_id(&self, id: i64) -> Result<Account, AppError> {
+        self.accounts.find_by_id(id)
+    }
 
-/// Mengambil satu akun berdasarkan ID-nya.
-pub fn get_account_by_id(id: i64) -> Result<Account, String> {
-    account_repository::find_by_id(id)
-}
+    /// Memperbarui akun yang ada. Jika mata uang tidak diisi, mata uang akun tidak berubah.
+    pub fn update_account(
+        &self,
+        id: i64,
+        name: &str,
+        description: Option<&str>,
+        balance: f64,
+        currency: Option<&str>,
+    ) -> Result<(), AppError> {
+        // Validasi tambahan bisa diletakkan di sini.
+        if name.trim().is_empty() {
+            return Err(AppError::validation(
+                "name",
+                "Nama akun tidak boleh kosong.",
+            ));
+        }
+        let existing = self.accounts.find_by_id(id)?;
+        let currency = match currency {
+            Some(code) => Currency::parse(code)?,
+            None => existing.currency,
+        };
+        // Saldo dan transaksi tersimpan dalam mata uang akun, jadi mata uang hanya boleh
+        // diganti selama akun belum memiliki transaksi.
+        if currency != existing.currency && self.accounts.count_transactions(id)? > 0 {
+            return Err(AppError::Conflict(
+                "Mata uang akun yang sudah memiliki transaksi tidak dapat diubah.".to_string(),
+            ));
+        }
+        let balance = Money::from_major_as(balance, currency, "balance")?;
 
-/// Memperbarui akun yang ada.
-pub fn update_account(
-    id: i64,
-    name: &str,
-    description: Option<&str>,
-    balance: f64,
-) -> Result<(), String> {
-    // Validasi tambahan bisa diletakkan di sini.
-    if name.trim().is_empty() {
-        return Err("Nama akun tidak boleh kosong.".to_string());
+        self.accounts.update(id, name, description, balance)
     }
 
-    account_repository::update(id, name, description, balance)
+    /// Menghapus akun berdasarkan ID.
+    pub fn delete_account(&self, id: i64) -> Result<(), AppError> {
+        // Akun yang masih memiliki transaksi tidak boleh dihapus agar riwayatnya tidak hilang.
+        if self.accounts.count_transactions(id)? > 0 {
+            return Err(AppError::Conflict(
+                "Akun masih memiliki transaksi dan tidak dapat dihapus.".to_string(),
+            ));
+        }
+        self.accounts.delete(id)
+    }
+
+    /// Menghitung ulang saldo akun dari saldo pembuka dan seluruh riwayat transaksinya,
+    /// lalu mengembalikan akun dengan saldo yang sudah diperbaiki.
+    pub fn recalculate_balance(&self, account_id: i64) -> Result<Account, AppError> {
+        self.accounts.recalculate_balance(account_id)?;
+        self.accounts.find_by_id(account_id)
+    }
 }
 
-/// Menghapus akun berdasarkan ID.
-pub fn delete_account(id: i64) -> Result<(), String> {
-    // Mungkin ada logika bisnis tambahan sebelum menghapus,
-    // misalnya memeriksa apakah akun masih memiliki transaksi.
-    account_repository::delete(id)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::category::CategoryType;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::services::test_support::add_category;
+
+    fn service(db: &Database) -> AccountService<SqliteAccountRepository<'_>> {
+        AccountService::new(SqliteAccountRepository::new(db))
+    }
+
+    #[test]
+    fn create_account_defaults_to_idr() {
+        let db = Database::open_in_memory().unwrap();
+        let accounts = service(&db);
+
+        accounts
+            .create_account("Dompet", None, 150_000.0, None, AccountType::Cash)
+            .unwrap();
+
+        let all = accounts.get_all_accounts().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].currency, Currency::IDR);
+        assert_eq!(
+            all[0].balance,
+            Money::from_major(150_000.0, Currency::IDR).unwrap()
+        );
+        assert_eq!(all[0].opening_balance, all[0].balance);
+    }
+
+    #[test]
+    fn create_account_rejects_blank_name_and_negative_balance() {
+        let db = Database::open_in_memory().unwrap();
+        let accounts = service(&db);
+
+        let err = accounts
+            .create_account("  ", None, 0.0, None, AccountType::Cash)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("name"));
+
+        let err = accounts
+            .create_account("Dompet", None, -1.0, None, AccountType::Cash)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("balance"));
+
+        assert!(accounts.get_all_accounts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_missing_account_is_not_found() {
+        let db = Database::open_in_memory().unwrap();
+
+        let err = service(&db).get_account_by_id(42).unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn update_keeps_recalculated_balance_consistent() {
+        let db = Database::open_in_memory().unwrap();
+        let accounts = service(&db);
+        accounts
+            .create_account(
+                "Bank",
+                Some("Tabungan"),
+                1_000.0,
+                Some("USD"),
+                AccountType::Cash,
+            )
+            .unwrap();
+
+        accounts
+            .update_account(1, "Bank", Some("Tabungan"), 1_250.5, None)
+            .unwrap();
+        let recalculated = accounts.recalculate_balance(1).unwrap();
+
+        assert_eq!(recalculated.currency, Currency::parse("USD").unwrap());
+        assert_eq!(recalculated.balance.minor_units(), 125_050);
+    }
+
+    #[test]
+    fn currency_cannot_change_once_account_has_transactions() {
+        let db = Database::open_in_memory().unwrap();
+        let accounts = service(&db);
+        accounts
+            .create_account("Bank", None, 100.0, None, AccountType::Cash)
+            .unwrap();
+        add_category(&db, "Makan", CategoryType::Expense);
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO expenses (description, amount, currency, date, category_id, account_id)
+                 VALUES ('Nasi', 2000, 'IDR', '2024-01-01 12:00:00', 1, 1)",
+                [],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let err = accounts
+            .update_account(1, "Bank", None, 100.0, Some("USD"))
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+
+        let err = accounts.delete_account(1).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+
+    #[test]
+    fn delete_account_without_transactions() {
+        let db = Database::open_in_memory().unwrap();
+        let accounts = service(&db);
+        accounts
+            .create_account("Dompet", None, 0.0, None, AccountType::Cash)
+            .unwrap();
+
+        accounts.delete_account(1).unwrap();
+
+        assert!(accounts.get_all_accounts().unwrap().is_empty());
+        let err = accounts.delete_account(1).unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
 }
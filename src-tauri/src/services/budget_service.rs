@@ -0,0 +1,334 @@
+//! Modul ini berisi logika bisnis untuk anggaran per kategori.
+//! Realisasi anggaran dihitung dari data pengeluaran kategori beserta seluruh
+//! sub-kategorinya, dalam mata uang anggaran tanpa konversi.
+
+use crate::error::AppError;
+use crate::models::budget::{Budget, BudgetInput, BudgetProgress, BudgetStatus, NewBudget};
+use crate::models::category::CategoryType;
+use crate::models::money::Money;
+use crate::repositories::budget_repository::BudgetRepository;
+use crate::repositories::category_repository::CategoryRepository;
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
+
+/// Service anggaran, bergantung pada `BudgetRepository` dan `CategoryRepository`.
+pub struct BudgetService<B, C> {
+    budgets: B,
+    categories: C,
+}
+
+impl<B: BudgetRepository, C: CategoryRepository> BudgetService<B, C> {
+    pub fn new(budgets: B, categories: C) -> Self {
+        BudgetService {
+            budgets,
+            categories,
+        }
+    }
+
+    /// Memvalidasi input. `start_date` digeser ke awal periodenya.
+    fn build_budget(&self, input: &BudgetInput) -> Result<NewBudget, AppError> {
+        let category = self.categories.find_by_id(input.category_id)?;
+        if category.category_type != CategoryType::Expense {
+            return Err(AppError::validation(
+                "category_id",
+                format!("Kategori '{}' bukan kategori pengeluaran.", category.name),
+            ));
+        }
+
+        let amount = Money::from_major(input.amount, input.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Batas anggaran harus lebih besar dari nol.",
+            ));
+        }
+
+        let start_date = input
+            .start_date
+            .unwrap_or_else(|| Local::now().date_naive());
+        Ok(NewBudget {
+            category_id: category.id,
+            period: input.period,
+            amount,
+            rollover: input.rollover,
+            start_date: input.period.start_of(start_date),
+        })
+    }
+
+    /// Membuat anggaran baru.
+    pub fn create_budget(&self, input: &BudgetInput) -> Result<(), AppError> {
+        let budget = self.build_budget(input)?;
+        self.budgets.create(&budget)
+    }
+
+    /// Mengambil semua anggaran.
+    pub fn get_all_budgets(&self) -> Result<Vec<Budget>, AppError> {
+        self.budgets.find_all()
+    }
+
+    /// Mengambil satu anggaran berdasarkan ID-nya.
+    pub fn get_budget_by_id(&self, id: i64) -> Result<Budget, AppError> {
+        self.budgets.find_by_id(id)
+    }
+
+    /// Memperbarui anggaran yang ada.
+    pub fn update_budget(&self, id: i64, input: &BudgetInput) -> Result<(), AppError> {
+        let budget = self.build_budget(input)?;
+        self.budgets.update(id, &budget)
+    }
+
+    /// Menghapus anggaran berdasarkan ID.
+    pub fn delete_budget(&self, id: i64) -> Result<(), AppError> {
+        self.budgets.delete(id)
+    }
+
+    /// Realisasi semua anggaran pada periode yang memuat `date` (default hari ini).
+    pub fn get_budget_progress(
+        &self,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<BudgetProgress>, AppError> {
+        let date = date.unwrap_or_else(|| Local::now().date_naive());
+        self.budgets
+            .find_all()?
+            .into_iter()
+            .map(|budget| self.progress(budget, date))
+            .collect()
+    }
+
+    fn progress(&self, budget: Budget, date: NaiveDate) -> Result<BudgetProgress, AppError> {
+        let category = self.categories.find_by_id(budget.category_id)?;
+        let period_start = budget.period.start_of(date);
+        let next_start = budget.period.next_start(period_start);
+
+        // Dengan rollover, pengeluaran sejak periode pertama dibutuhkan untuk menghitung
+        // sisa anggaran yang terbawa.
+        let history_from = if budget.rollover {
+            budget.start_date.min(period_start)
+        } else {
+            period_start
+        };
+        let spent_by_period: HashMap<NaiveDate, Money> = self
+            .budgets
+            .spent_by_period(
+                budget.category_id,
+                budget.currency,
+                budget.period,
+                history_from,
+                next_start,
+            )?
+            .into_iter()
+            .collect();
+        let spent_in = |start: NaiveDate| {
+            spent_by_period
+                .get(&start)
+                .copied()
+                .unwrap_or(Money::zero(budget.currency))
+        };
+
+        // Hanya sisa yang dibawa; kelebihan pengeluaran tidak mengurangi periode berikutnya.
+        let mut carried_over = Money::zero(budget.currency);
+        let mut start = history_from;
+        while start < period_start {
+            let left = carried_over
+                .checked_add(budget.amount)?
+                .checked_sub(spent_in(start))?;
+            carried_over = if left.is_positive() {
+                left
+            } else {
+                Money::zero(budget.currency)
+            };
+            start = budget.period.next_start(start);
+        }
+
+        let spent = spent_in(period_start);
+        let available = budget.amount.checked_add(carried_over)?;
+        let remaining = available.checked_sub(spent)?;
+
+        let elapsed_days = (date - period_start).num_days() + 1;
+        let total_days = (next_start - period_start).num_days();
+        let projected =
+            i128::from(spent.minor_units()) * i128::from(total_days) / i128::from(elapsed_days);
+        let projected_spent = Money::new(
+            i64::try_from(projected).unwrap_or(i64::MAX),
+            budget.currency,
+        );
+
+        let status = if spent.minor_units() > available.minor_units() {
+            BudgetStatus::OverBudget
+        } else if projected_spent.minor_units() > available.minor_units() {
+            BudgetStatus::TrendingOver
+        } else {
+            BudgetStatus::OnTrack
+        };
+        let percent_used = spent.minor_units() as f64 / available.minor_units() as f64 * 100.0;
+
+        Ok(BudgetProgress {
+            budget,
+            category_name: category.name,
+            period_start,
+            period_end: next_start.pred_opt().unwrap_or(next_start),
+            carried_over,
+            available,
+            spent,
+            remaining,
+            percent_used,
+            projected_spent,
+            status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::budget::BudgetPeriod;
+    use crate::models::money::Currency;
+    use crate::repositories::budget_repository::SqliteBudgetRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::services::test_support::{add_account, add_category, add_subcategory, date, idr};
+
+    fn service(
+        db: &Database,
+    ) -> BudgetService<SqliteBudgetRepository<'_>, SqliteCategoryRepository<'_>> {
+        BudgetService::new(
+            SqliteBudgetRepository::new(db),
+            SqliteCategoryRepository::new(db),
+        )
+    }
+
+    /// Kategori 1 Makan > 2 Restoran (Expense) dan 3 Gaji (Income), plus satu akun IDR.
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Makan", CategoryType::Expense);
+        add_subcategory(&db, "Restoran", CategoryType::Expense, Some(1));
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_account(&db, "Dompet", idr(0.0));
+        db
+    }
+
+    /// Mencatat pengeluaran `amount` rupiah pada tanggal `day`.
+    fn spend(db: &Database, category_id: i64, day: &str, amount: i64) {
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO expenses (description, amount, currency, date, category_id, account_id)
+                 VALUES ('Belanja', ?1, 'IDR', ?2, ?3, 1)",
+                rusqlite::params![amount * 100, format!("{} 12:00:00", day), category_id],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn input(period: BudgetPeriod, amount: f64, rollover: bool) -> BudgetInput {
+        BudgetInput {
+            category_id: 1,
+            period,
+            amount,
+            currency: Currency::IDR,
+            rollover,
+            start_date: Some(date(2024, 4, 15)),
+        }
+    }
+
+    #[test]
+    fn progress_includes_subcategories_and_flags_trend() {
+        let db = fixture();
+        let budgets = service(&db);
+        budgets
+            .create_budget(&input(BudgetPeriod::Monthly, 1_000_000.0, false))
+            .unwrap();
+        assert_eq!(
+            budgets.get_budget_by_id(1).unwrap().start_date,
+            date(2024, 4, 1)
+        );
+        spend(&db, 1, "2024-05-02", 300_000);
+        spend(&db, 2, "2024-05-09", 200_000);
+        spend(&db, 2, "2024-06-01", 999_000);
+
+        // 500 ribu dalam 10 hari dari 31 hari: diperkirakan 1,55 juta di akhir bulan.
+        let progress = budgets
+            .get_budget_progress(Some(date(2024, 5, 10)))
+            .unwrap();
+        let progress = &progress[0];
+        assert_eq!(progress.period_start, date(2024, 5, 1));
+        assert_eq!(progress.period_end, date(2024, 5, 31));
+        assert_eq!(progress.spent.minor_units(), 50_000_000);
+        assert_eq!(progress.remaining.minor_units(), 50_000_000);
+        assert_eq!(progress.projected_spent.minor_units(), 155_000_000);
+        assert_eq!(progress.status, BudgetStatus::TrendingOver);
+
+        let progress = budgets
+            .get_budget_progress(Some(date(2024, 5, 31)))
+            .unwrap();
+        assert_eq!(progress[0].status, BudgetStatus::OnTrack);
+
+        spend(&db, 1, "2024-05-20", 600_000);
+        let progress = budgets
+            .get_budget_progress(Some(date(2024, 5, 31)))
+            .unwrap();
+        assert_eq!(progress[0].status, BudgetStatus::OverBudget);
+        assert_eq!(progress[0].remaining.minor_units(), -10_000_000);
+    }
+
+    #[test]
+    fn rollover_carries_only_unspent_amounts() {
+        let db = fixture();
+        let budgets = service(&db);
+        budgets
+            .create_budget(&input(BudgetPeriod::Monthly, 1_000_000.0, true))
+            .unwrap();
+        // April sisa 400 ribu, Mei lebih 100 ribu (sisa terbawa menjadi 300 ribu), Juni kosong.
+        spend(&db, 1, "2024-04-20", 600_000);
+        spend(&db, 2, "2024-05-20", 1_100_000);
+
+        let progress = budgets
+            .get_budget_progress(Some(date(2024, 6, 15)))
+            .unwrap();
+        let progress = &progress[0];
+        assert_eq!(progress.carried_over.minor_units(), 30_000_000);
+        assert_eq!(progress.available.minor_units(), 130_000_000);
+        assert_eq!(progress.spent.minor_units(), 0);
+    }
+
+    #[test]
+    fn weekly_periods_start_on_monday() {
+        let db = fixture();
+        let budgets = service(&db);
+        budgets
+            .create_budget(&input(BudgetPeriod::Weekly, 100_000.0, false))
+            .unwrap();
+        spend(&db, 1, "2024-05-05", 50_000);
+        spend(&db, 1, "2024-05-06", 20_000);
+
+        // 2024-05-08 adalah hari Rabu; minggunya dimulai Senin 6 Mei.
+        let progress = budgets.get_budget_progress(Some(date(2024, 5, 8))).unwrap();
+        assert_eq!(progress[0].period_start, date(2024, 5, 6));
+        assert_eq!(progress[0].period_end, date(2024, 5, 12));
+        assert_eq!(progress[0].spent.minor_units(), 2_000_000);
+    }
+
+    #[test]
+    fn rejects_income_category_duplicates_and_non_positive_limit() {
+        let db = fixture();
+        let budgets = service(&db);
+
+        let mut income = input(BudgetPeriod::Monthly, 100.0, false);
+        income.category_id = 3;
+        let err = budgets.create_budget(&income).unwrap_err();
+        assert_eq!(err.field(), Some("category_id"));
+
+        let err = budgets
+            .create_budget(&input(BudgetPeriod::Monthly, 0.0, false))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+
+        budgets
+            .create_budget(&input(BudgetPeriod::Monthly, 100.0, false))
+            .unwrap();
+        let err = budgets
+            .create_budget(&input(BudgetPeriod::Monthly, 200.0, false))
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+}
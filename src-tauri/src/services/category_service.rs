@@ -0,0 +1,536 @@
+//! Modul ini berisi logika bisnis yang terkait dengan kategori.
+
+use crate::error::AppError;
+use crate::models::category::{Category, CategoryNode, CategoryType};
+use crate::models::default_category;
+use crate::repositories::category_repository::CategoryRepository;
+use std::collections::HashMap;
+
+/// Mengubah nilai integer dari frontend (1 = Income, 2 = Expense) menjadi `CategoryType`.
+fn parse_category_type(value: i16) -> Result<CategoryType, AppError> {
+    CategoryType::try_from(value).map_err(|e| AppError::validation("category_type", e.to_string()))
+}
+
+/// Merapikan ikon dan warna dari frontend: teks kosong berarti tidak diisi, dan warna
+/// harus berformat `#RRGGBB`.
+fn normalize_appearance<'a>(
+    icon: Option<&'a str>,
+    color: Option<&'a str>,
+) -> Result<(Option<&'a str>, Option<&'a str>), AppError> {
+    let icon = icon.map(str::trim).filter(|icon| !icon.is_empty());
+    let color = color.map(str::trim).filter(|color| !color.is_empty());
+    if let Some(color) = color {
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(AppError::validation(
+                "color",
+                "Warna harus berformat #RRGGBB, misal #F97316.",
+            ));
+        }
+    }
+    Ok((icon, color))
+}
+
+/// Service kategori, bergantung pada `CategoryRepository`.
+pub struct CategoryService<C> {
+    categories: C,
+}
+
+impl<C: CategoryRepository> CategoryService<C> {
+    pub fn new(categories: C) -> Self {
+        CategoryService { categories }
+    }
+
+    /// Membuat kategori baru, opsional sebagai sub-kategori dari `parent_id`.
+    pub fn create_category(
+        &self,
+        name: &str,
+        category_type: i16,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::validation(
+                "name",
+                "Nama kategori tidak boleh kosong.",
+            ));
+        }
+        let category_type = parse_category_type(category_type)?;
+        let (icon, color) = normalize_appearance(icon, color)?;
+        self.validate_parent(None, parent_id, category_type)?;
+
+        self.categories
+            .create(name.trim(), category_type, parent_id, icon, color)
+    }
+
+    /// Mengambil semua kategori.
+    pub fn get_all_categories(&self) -> Result<Vec<Category>, AppError> {
+        self.categories.find_all()
+    }
+
+    /// Mengambil semua kategori sebagai pohon: kategori akar beserta sub-kategorinya,
+    /// masing-masing urut berdasarkan nama.
+    pub fn get_category_tree(&self) -> Result<Vec<CategoryNode>, AppError> {
+        let mut by_parent: HashMap<Option<i64>, Vec<Category>> = HashMap::new();
+        for category in self.categories.find_all()? {
+            by_parent
+                .entry(category.parent_id)
+                .or_default()
+                .push(category);
+        }
+        Ok(build_nodes(&mut by_parent, None))
+    }
+
+    /// Mengambil satu kategori berdasarkan ID-nya.
+    pub fn get_category_by_id(&self, id: i64) -> Result<Category, AppError> {
+        self.categories.find_by_id(id)
+    }
+
+    /// Memperbarui kategori yang ada. Tipe kategori tidak boleh diubah selama masih
+    /// dipakai oleh transaksi, karena pemasukan harus berkategori Income dan
+    /// pengeluaran berkategori Expense. Kategori juga tidak boleh dipindahkan ke bawah
+    /// dirinya sendiri atau sub-kategorinya.
+    pub fn update_category(
+        &self,
+        id: i64,
+        name: &str,
+        category_type: i16,
+        parent_id: Option<i64>,
+        icon: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::validation(
+                "name",
+                "Nama kategori tidak boleh kosong.",
+            ));
+        }
+        let category_type = parse_category_type(category_type)?;
+        let (icon, color) = normalize_appearance(icon, color)?;
+
+        let existing = self.categories.find_by_id(id)?;
+        if existing.category_type != category_type && self.categories.count_usage(id)? > 0 {
+            return Err(AppError::Conflict(
+                "Tipe kategori yang sudah dipakai oleh transaksi tidak dapat diubah.".to_string(),
+            ));
+        }
+        // Induk dan anak harus bertipe sama, jadi tipe kategori yang punya
+        // sub-kategori tidak bisa diubah.
+        if existing.category_type != category_type && self.categories.count_children(id)? > 0 {
+            return Err(AppError::Conflict(
+                "Tipe kategori yang memiliki sub-kategori tidak dapat diubah.".to_string(),
+            ));
+        }
+        self.validate_parent(Some(id), parent_id, category_type)?;
+
+        self.categories
+            .update(id, name.trim(), category_type, parent_id, icon, color)
+    }
+
+    /// Menambahkan kategori bawaan untuk `locale` yang belum ada. Kategori milik pengguna
+    /// tidak pernah diubah, sehingga aman dipanggil berulang kali. Mengembalikan jumlah
+    /// kategori yang ditambahkan.
+    pub fn install_default_categories(&self, locale: &str) -> Result<usize, AppError> {
+        let set = default_category::find_set(locale).ok_or_else(|| {
+            AppError::validation(
+                "locale",
+                format!("Kategori bawaan untuk bahasa '{}' tidak tersedia.", locale),
+            )
+        })?;
+        self.categories.install_defaults(set)
+    }
+
+    /// Menghapus kategori berdasarkan ID. Sub-kategorinya naik satu tingkat ke induk
+    /// kategori yang dihapus, sehingga tidak ada kategori yang ikut terhapus.
+    ///
+    /// Kategori yang masih dipakai transaksi, anggaran, atau utang hanya bisa dihapus bila
+    /// `replacement_id` diisi; semuanya lalu dipindahkan ke kategori pengganti dalam transaksi
+    /// database yang sama. Mengembalikan jumlah transaksi yang dipindahkan.
+    pub fn delete_category(&self, id: i64, replacement_id: Option<i64>) -> Result<usize, AppError> {
+        let category = self.categories.find_by_id(id)?;
+
+        match replacement_id {
+            None => {
+                let usage = self.categories.count_usage(id)?;
+                if usage > 0 {
+                    return Err(AppError::Conflict(format!(
+                        "Kategori '{}' masih dipakai oleh {} transaksi, transaksi berulang, \
+                         anggaran, atau utang. Pilih kategori pengganti untuk memindahkannya.",
+                        category.name, usage
+                    )));
+                }
+                self.categories.delete(id)?;
+                Ok(0)
+            }
+            Some(replacement_id) => {
+                self.validate_replacement(&category, replacement_id, "replacement_id")?;
+                self.categories.delete_reassigning(id, replacement_id)
+            }
+        }
+    }
+
+    /// Menggabungkan kategori `source_id` ke `target_id`: semua transaksi dan sub-kategori
+    /// `source_id` pindah ke `target_id`, lalu `source_id` dihapus. Mengembalikan jumlah
+    /// transaksi yang dipindahkan.
+    pub fn merge_categories(&self, source_id: i64, target_id: i64) -> Result<usize, AppError> {
+        let source = self.categories.find_by_id(source_id)?;
+        self.validate_replacement(&source, target_id, "target_id")?;
+        // Sub-kategori sumber akan menjadi anak target, jadi target tidak boleh berada
+        // di bawah sumber agar tidak terbentuk siklus.
+        if self
+            .categories
+            .find_ancestor_ids(target_id)?
+            .contains(&source_id)
+        {
+            return Err(AppError::validation(
+                "target_id",
+                "Kategori tidak dapat digabungkan ke sub-kategorinya sendiri.",
+            ));
+        }
+        self.categories.merge(source_id, target_id)
+    }
+
+    /// Memastikan kategori pengganti ada, berbeda, dan bertipe sama dengan `category`.
+    fn validate_replacement(
+        &self,
+        category: &Category,
+        replacement_id: i64,
+        field: &str,
+    ) -> Result<(), AppError> {
+        if replacement_id == category.id {
+            return Err(AppError::validation(
+                field,
+                "Kategori pengganti harus berbeda dengan kategori yang dihapus.",
+            ));
+        }
+        let replacement = self.categories.find_by_id(replacement_id)?;
+        if replacement.category_type != category.category_type {
+            return Err(AppError::validation(
+                field,
+                format!(
+                    "Kategori pengganti '{}' memiliki tipe yang berbeda.",
+                    replacement.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Memastikan `parent_id` (jika diisi) ada, bertipe sama, dan tidak membentuk siklus
+    /// dengan kategori `id` yang sedang diperbarui.
+    fn validate_parent(
+        &self,
+        id: Option<i64>,
+        parent_id: Option<i64>,
+        category_type: CategoryType,
+    ) -> Result<(), AppError> {
+        let Some(parent_id) = parent_id else {
+            return Ok(());
+        };
+
+        let parent = self.categories.find_by_id(parent_id)?;
+        if parent.category_type != category_type {
+            return Err(AppError::validation(
+                "parent_id",
+                format!(
+                    "Kategori induk '{}' memiliki tipe yang berbeda.",
+                    parent.name
+                ),
+            ));
+        }
+        if let Some(id) = id {
+            if self.categories.find_ancestor_ids(parent_id)?.contains(&id) {
+                return Err(AppError::validation(
+                    "parent_id",
+                    "Kategori tidak boleh menjadi sub-kategori dari dirinya sendiri atau turunannya.",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Menyusun node untuk semua kategori dengan induk `parent_id`, beserta turunannya.
+fn build_nodes(
+    by_parent: &mut HashMap<Option<i64>, Vec<Category>>,
+    parent_id: Option<i64>,
+) -> Vec<CategoryNode> {
+    by_parent
+        .remove(&parent_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|category| {
+            let children = build_nodes(by_parent, Some(category.id));
+            CategoryNode { category, children }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::services::test_support::{add_account, idr};
+
+    fn service(db: &Database) -> CategoryService<SqliteCategoryRepository<'_>> {
+        CategoryService::new(SqliteCategoryRepository::new(db))
+    }
+
+    /// 1 Makan > 2 Belanja Dapur > 3 Sayur, 1 Makan > 4 Restoran, 5 Gaji (Income), plus
+    /// akun 1 untuk mencatat pengeluaran.
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        let categories = service(&db);
+        categories
+            .create_category("Makan", 2, None, None, None)
+            .unwrap();
+        categories
+            .create_category("Belanja Dapur", 2, Some(1), None, None)
+            .unwrap();
+        categories
+            .create_category("Sayur", 2, Some(2), None, None)
+            .unwrap();
+        categories
+            .create_category("Restoran", 2, Some(1), None, None)
+            .unwrap();
+        categories
+            .create_category("Gaji", 1, None, None, None)
+            .unwrap();
+        add_account(&db, "Dompet", idr(0.0));
+        db
+    }
+
+    /// Menuliskan pohon sebagai teks, misal `Makan(Restoran)`.
+    fn render(nodes: &[CategoryNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| {
+                if node.children.is_empty() {
+                    node.category.name.clone()
+                } else {
+                    format!("{}({})", node.category.name, render(&node.children))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn tree_nests_children_sorted_by_name() {
+        let db = fixture();
+
+        let tree = service(&db).get_category_tree().unwrap();
+
+        assert_eq!(render(&tree), "Gaji, Makan(Belanja Dapur(Sayur), Restoran)");
+    }
+
+    #[test]
+    fn parent_must_exist_and_share_type() {
+        let db = fixture();
+        let categories = service(&db);
+
+        let err = categories
+            .create_category("Bonus", 1, Some(1), None, None)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("parent_id"));
+
+        let err = categories
+            .create_category("Bonus", 1, Some(99), None, None)
+            .unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let db = fixture();
+        let categories = service(&db);
+
+        let err = categories
+            .update_category(1, "Makan", 2, Some(3), None, None)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("parent_id"));
+
+        let err = categories
+            .update_category(2, "Belanja Dapur", 2, Some(2), None, None)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("parent_id"));
+
+        // Memindahkan ke cabang lain tetap diperbolehkan.
+        categories
+            .update_category(3, "Sayur", 2, Some(4), None, None)
+            .unwrap();
+        assert_eq!(categories.get_category_by_id(3).unwrap().parent_id, Some(4));
+    }
+
+    #[test]
+    fn type_of_category_with_children_cannot_change() {
+        let db = fixture();
+
+        let err = service(&db)
+            .update_category(1, "Makan", 1, None, None, None)
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+
+    #[test]
+    fn deleting_parent_moves_children_up_one_level() {
+        let db = fixture();
+        let categories = service(&db);
+
+        categories.delete_category(2, None).unwrap();
+
+        assert_eq!(categories.get_category_by_id(3).unwrap().parent_id, Some(1));
+        categories.delete_category(1, None).unwrap();
+        assert_eq!(categories.get_category_by_id(3).unwrap().parent_id, None);
+        assert_eq!(categories.get_category_by_id(4).unwrap().parent_id, None);
+    }
+
+    /// Mencatat satu pengeluaran langsung lewat SQL pada kategori `category_id`.
+    fn add_expense(db: &Database, category_id: i64) {
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO expenses (description, amount, currency, date, category_id, account_id)
+                 VALUES ('Belanja', 100, 'IDR', '2024-05-01 08:00:00', ?1, 1)",
+                [category_id],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn category_in_use_needs_replacement_of_same_type() {
+        let db = fixture();
+        let categories = service(&db);
+        add_expense(&db, 4);
+        add_expense(&db, 4);
+
+        let err = categories.delete_category(4, None).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        let err = categories.delete_category(4, Some(5)).unwrap_err();
+        assert_eq!(err.field(), Some("replacement_id"));
+        let err = categories.delete_category(4, Some(4)).unwrap_err();
+        assert_eq!(err.field(), Some("replacement_id"));
+
+        assert_eq!(categories.delete_category(4, Some(1)).unwrap(), 2);
+        assert_eq!(
+            SqliteCategoryRepository::new(&db).count_usage(1).unwrap(),
+            2
+        );
+        assert_eq!(
+            categories.get_category_by_id(4).unwrap_err().code(),
+            "NOT_FOUND"
+        );
+    }
+
+    #[test]
+    fn merge_moves_transactions_and_children_to_target() {
+        let db = fixture();
+        let categories = service(&db);
+        add_expense(&db, 2);
+
+        let err = categories.merge_categories(1, 3).unwrap_err();
+        assert_eq!(err.field(), Some("target_id"));
+
+        assert_eq!(categories.merge_categories(2, 4).unwrap(), 1);
+        assert_eq!(
+            SqliteCategoryRepository::new(&db).count_usage(4).unwrap(),
+            1
+        );
+        assert_eq!(categories.get_category_by_id(3).unwrap().parent_id, Some(4));
+        let tree = categories.get_category_tree().unwrap();
+        assert_eq!(render(&tree), "Gaji, Makan(Restoran(Sayur))");
+    }
+
+    /// Mencatat anggaran bulanan IDR dan utang bercicilan langsung lewat SQL pada kategori
+    /// `category_id`.
+    fn add_budget_and_debt(db: &Database, category_id: i64) {
+        db.write(|conn| {
+            conn.execute(
+                "INSERT INTO budgets (category_id, period, amount, currency, start_date)
+                 VALUES (?1, 2, 100000, 'IDR', '2024-05-01')",
+                [category_id],
+            )?;
+            conn.execute(
+                "INSERT INTO debts (counterparty, direction, principal, currency, start_date,
+                                    category_id)
+                 VALUES ('Leasing', 1, 100000, 'IDR', '2024-05-01', ?1)",
+                [category_id],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    /// Kategori setiap anggaran dan setiap utang, urut sesuai ID.
+    fn budget_and_debt_categories(db: &Database) -> (Vec<i64>, Vec<i64>) {
+        db.read(|conn| {
+            let ids = |sql: &str| {
+                conn.prepare(sql)?
+                    .query_map([], |row| row.get(0))?
+                    .collect::<rusqlite::Result<Vec<i64>>>()
+            };
+            Ok((
+                ids("SELECT category_id FROM budgets ORDER BY id")?,
+                ids("SELECT category_id FROM debts ORDER BY id")?,
+            ))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn budgets_and_debts_move_with_reassigned_category() {
+        let db = fixture();
+        let categories = service(&db);
+        add_budget_and_debt(&db, 4);
+
+        // Tanpa transaksi pun, anggaran dan utang tidak boleh ikut hilang.
+        let err = categories.delete_category(4, None).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+
+        // Kategori 1 sudah punya anggaran bulanan IDR, jadi penggabungan ditolak utuh.
+        add_budget_and_debt(&db, 1);
+        let err = categories.delete_category(4, Some(1)).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        let err = categories.merge_categories(4, 1).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        assert_eq!(budget_and_debt_categories(&db), (vec![4, 1], vec![4, 1]));
+
+        assert_eq!(categories.delete_category(4, Some(2)).unwrap(), 0);
+        assert_eq!(budget_and_debt_categories(&db), (vec![2, 1], vec![2, 1]));
+    }
+
+    #[test]
+    fn installing_defaults_keeps_user_categories() {
+        let db = fixture();
+        let categories = service(&db);
+        categories
+            .update_category(1, "Makan", 2, None, Some("pizza"), Some("#000000"))
+            .unwrap();
+
+        let inserted = categories.install_default_categories("id").unwrap();
+        assert!(inserted > 0);
+        let makan = categories.get_category_by_id(1).unwrap();
+        assert_eq!(makan.icon.as_deref(), Some("pizza"));
+        assert_eq!(makan.color.as_deref(), Some("#000000"));
+        // Sub-kategori bawaan ditambahkan di bawah kategori milik pengguna yang bernama sama.
+        assert_eq!(
+            render(&categories.get_category_tree().unwrap())
+                .matches("Makan(")
+                .count(),
+            1
+        );
+
+        assert_eq!(categories.install_default_categories("id").unwrap(), 0);
+        let err = categories.install_default_categories("xx").unwrap_err();
+        assert_eq!(err.field(), Some("locale"));
+        let err = categories
+            .create_category("Kopi", 2, None, None, Some("oranye"))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("color"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Modul ini berisi logika bisnis untuk mata uang dan kurs.
+//! Kurs dimasukkan manual atau diimpor dari CSV, lalu dipakai untuk mengonversi
+//! total dari berbagai mata uang ke satu mata uang pelaporan pilihan pengguna.
+
+use crate::error::AppError;
+use crate::models::exchange_rate::{ExchangeRate, RateSource};
+use crate::models::money::{Currency, Money};
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::exchange_rate_repository::ExchangeRateRepository;
+use chrono::{Local, NaiveDate};
+use std::collections::HashMap;
+
+/// Service mata uang dan kurs, bergantung pada `ExchangeRateRepository` dan
+/// `AccountRepository` (untuk total saldo).
+pub struct CurrencyService<R, A> {
+    rates: R,
+    accounts: A,
+}
+
+impl<R: ExchangeRateRepository, A: AccountRepository> CurrencyService<R, A> {
+    pub fn new(rates: R, accounts: A) -> Self {
+        CurrencyService { rates, accounts }
+    }
+
+    /// Menyimpan kurs manual `1 base = rate quote` untuk tanggal tertentu.
+    pub fn add_exchange_rate(
+        &self,
+        date: NaiveDate,
+        base: &str,
+        quote: &str,
+        rate: f64,
+    ) -> Result<(), AppError> {
+        let (base, quote) = validate_pair(base, quote)?;
+        validate_rate(rate)?;
+        self.rates
+            .upsert(date, base, quote, rate, RateSource::Manual)
+    }
+
+    /// Mengambil semua kurs yang tersimpan.
+    pub fn get_all_exchange_rates(&self) -> Result<Vec<ExchangeRate>, AppError> {
+        self.rates.find_all()
+    }
+
+    /// Menghapus kurs berdasarkan ID.
+    pub fn delete_exchange_rate(&self, id: i64) -> Result<(), AppError> {
+        self.rates.delete(id)
+    }
+
+    /// Mengimpor kurs dari file CSV dengan kolom `date,base_currency,quote_currency,rate`
+    /// (tanggal berformat YYYY-MM-DD). Baris header, baris kosong, dan baris yang diawali `#`
+    /// dilewati. Semua baris disimpan dalam satu transaksi; satu baris rusak membatalkan impor.
+    /// Mengembalikan jumlah kurs yang diimpor.
+    pub fn import_exchange_rates_csv(&self, path: &str) -> Result<usize, AppError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Io(format!("Gagal membaca file CSV: {}", e)))?;
+        let rates = parse_rates_csv(&content)?;
+        self.rates.upsert_many(&rates, RateSource::Csv)?;
+        Ok(rates.len())
+    }
+
+    /// Mencari kurs `from -> to` yang berlaku pada `date`. Jika hanya kurs kebalikannya
+    /// yang tersedia (`to -> from`), nilai kebalikannya yang dipakai.
+    pub fn find_rate(
+        &self,
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    ) -> Result<f64, AppError> {
+        if from == to {
+            return Ok(1.0);
+        }
+        if let Some(rate) = self.rates.find_latest_on_or_before(from, to, date)? {
+            return Ok(rate);
+        }
+        if let Some(rate) = self.rates.find_latest_on_or_before(to, from, date)? {
+            return Ok(1.0 / rate);
+        }
+        Err(AppError::NotFound(format!(
+            "Kurs {} ke {} pada atau sebelum {} tidak ditemukan.",
+            from, to, date
+        )))
+    }
+
+    /// Mengonversi satu nilai uang ke mata uang `target` memakai kurs yang berlaku pada `date`.
+    pub fn convert(
+        &self,
+        amount: Money,
+        target: Currency,
+        date: NaiveDate,
+    ) -> Result<Money, AppError> {
+        let rate = self.find_rate(amount.currency(), target, date)?;
+        amount.convert(rate, target)
+    }
+
+    /// Menjumlahkan nilai-nilai dalam berbagai mata uang ke mata uang `target`.
+    /// Nilai dijumlahkan per mata uang terlebih dahulu (tanpa pembulatan), baru kemudian
+    /// setiap subtotal dikonversi sekali.
+    pub fn convert_total(
+        &self,
+        amounts: &[Money],
+        target: Currency,
+        date: NaiveDate,
+    ) -> Result<Money, AppError> {
+        let mut subtotals: HashMap<Currency, Money> = HashMap::new();
+        for amount in amounts {
+            let current = subtotals
+                .get(&amount.currency())
+                .copied()
+                .unwrap_or(Money::zero(amount.currency()));
+            subtotals.insert(amount.currency(), current.checked_add(*amount)?);
+        }
+
+        let mut total = Money::zero(target);
+        for subtotal in subtotals.into_values() {
+            total = total.checked_add(self.convert(subtotal, target, date)?)?;
+        }
+        Ok(total)
+    }
+
+    /// Total saldo seluruh akun dalam mata uang pelaporan, memakai kurs hari ini.
+    pub fn get_total_balance(&self, reporting_currency: &str) -> Result<Money, AppError> {
+        let target = Currency::parse(reporting_currency)?;
+        let balances: Vec<Money> = self
+            .accounts
+            .find_all()?
+            .into_iter()
+            .map(|account| account.balance)
+            .collect();
+        self.convert_total(&balances, target, Local::now().date_naive())
+    }
+}
+
+fn parse_rates_csv(content: &str) -> Result<Vec<(NaiveDate, Currency, Currency, f64)>, AppError> {
+    let mut rates = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if index == 0 && fields[0].eq_ignore_ascii_case("date") {
+            continue;
+        }
+        if fields.len() != 4 {
+            return Err(csv_error(line_no, "harus berisi 4 kolom."));
+        }
+
+        let date = NaiveDate::parse_from_str(fields[0], "%Y-%m-%d")
+            .map_err(|_| csv_error(line_no, format!("tanggal tidak valid '{}'.", fields[0])))?;
+        let (base, quote) =
+            validate_pair(fields[1], fields[2]).map_err(|e| csv_error(line_no, e))?;
+        let rate: f64 = fields[3]
+            .parse()
+            .map_err(|_| csv_error(line_no, format!("kurs tidak valid '{}'.", fields[3])))?;
+        validate_rate(rate).map_err(|e| csv_error(line_no, e))?;
+
+        rates.push((date, base, quote, rate));
+    }
+
+    Ok(rates)
+}
+
+/// Error validasi untuk satu baris CSV, dengan nomor baris di awal pesan.
+fn csv_error(line_no: usize, message: impl std::fmt::Display) -> AppError {
+    AppError::validation("path", format!("Baris {}: {}", line_no, message))
+}
+
+fn validate_pair(base: &str, quote: &str) -> Result<(Currency, Currency), AppError> {
+    let base = Currency::parse(base)?;
+    let quote = Currency::parse(quote)?;
+    if base == quote {
+        return Err(AppError::validation(
+            "quote_currency",
+            "Mata uang asal dan tujuan tidak boleh sama.",
+        ));
+    }
+    Ok((base, quote))
+}
+
+fn validate_rate(rate: f64) -> Result<(), AppError> {
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(AppError::validation(
+            "rate",
+            "Kurs harus lebih besar dari nol.",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::exchange_rate_repository::SqliteExchangeRateRepository;
+    use crate::services::test_support::{date, usd};
+
+    fn service(
+        db: &Database,
+    ) -> CurrencyService<SqliteExchangeRateRepository<'_>, SqliteAccountRepository<'_>> {
+        CurrencyService::new(
+            SqliteExchangeRateRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    #[test]
+    fn parse_rates_csv_skips_header_and_comments() {
+        let rates = parse_rates_csv(
+            "date,base_currency,quote_currency,rate\n# komentar\n\n2024-01-02,usd,IDR,15500\n",
+        )
+        .unwrap();
+
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].0, date(2024, 1, 2));
+        assert_eq!(rates[0].1.code(), "USD");
+        assert_eq!(rates[0].3, 15_500.0);
+    }
+
+    #[test]
+    fn parse_rates_csv_reports_line_number() {
+        let err = parse_rates_csv("2024-01-02,USD,IDR,15500\n2024-01-03,USD,IDR,-1\n").unwrap_err();
+        assert!(err.to_string().starts_with("Baris 2:"));
+    }
+
+    #[test]
+    fn find_rate_uses_latest_rate_or_inverse() {
+        let db = Database::open_in_memory().unwrap();
+        let currencies = service(&db);
+        currencies
+            .add_exchange_rate(date(2024, 1, 1), "USD", "IDR", 15_000.0)
+            .unwrap();
+        currencies
+            .add_exchange_rate(date(2024, 1, 5), "USD", "IDR", 16_000.0)
+            .unwrap();
+        let usd = usd();
+
+        assert_eq!(
+            currencies
+                .find_rate(usd, Currency::IDR, date(2024, 1, 3))
+                .unwrap(),
+            15_000.0
+        );
+        assert_eq!(
+            currencies
+                .find_rate(usd, Currency::IDR, date(2024, 1, 9))
+                .unwrap(),
+            16_000.0
+        );
+        assert_eq!(
+            currencies
+                .find_rate(Currency::IDR, usd, date(2024, 1, 9))
+                .unwrap(),
+            1.0 / 16_000.0
+        );
+
+        let err = currencies.find_rate(usd, Currency::IDR, date(2024, 1, 1).pred_opt().unwrap());
+        assert_eq!(err.unwrap_err().code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn convert_total_sums_per_currency_before_converting() {
+        let db = Database::open_in_memory().unwrap();
+        let currencies = service(&db);
+        currencies
+            .add_exchange_rate(date(2024, 1, 1), "USD", "IDR", 15_000.0)
+            .unwrap();
+        let usd = usd();
+
+        let total = currencies
+            .convert_total(
+                &[
+                    Money::new(50, usd),
+                    Money::new(50, usd),
+                    Money::new(1_000_000, Currency::IDR),
+                ],
+                Currency::IDR,
+                date(2024, 1, 2),
+            )
+            .unwrap();
+
+        assert_eq!(total, Money::new(2_500_000, Currency::IDR));
+    }
+}
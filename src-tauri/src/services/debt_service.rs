@@ -0,0 +1,812 @@
+//! Modul ini berisi logika bisnis untuk utang dan piutang.
+//! Pembayaran dicatat sebagai pengeluaran atau pemasukan biasa pada akun yang dipilih,
+//! sehingga saldo akun dan laporan tetap mencerminkan uang yang benar-benar berpindah.
+//! Utang bercicilan menyimpan tabel angsuran (bunga flat atau anuitas) yang juga bisa
+//! disimulasikan tanpa disimpan lewat `calculate_loan_schedule`.
+
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::debt::{
+    CounterpartyBalance, Debt, DebtDirection, DebtInput, DebtPayment, DebtPaymentInput, DebtStatus,
+    Installment, InstallmentStatus, NewDebt, NewDebtPayment,
+};
+use crate::models::loan::{
+    InterestMethod, LoanInput, LoanSchedule, LoanTerms, ScheduledInstallment,
+};
+use crate::models::money::Money;
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::category_repository::CategoryRepository;
+use crate::repositories::debt_repository::DebtRepository;
+use chrono::{Local, NaiveDate};
+
+/// Batas jumlah cicilan (50 tahun cicilan bulanan).
+const MAX_INSTALLMENTS: u32 = 600;
+
+/// Service utang/piutang, bergantung pada `DebtRepository`, `AccountRepository`, dan
+/// `CategoryRepository`.
+pub struct DebtService<D, A, C> {
+    debts: D,
+    accounts: A,
+    categories: C,
+}
+
+impl<D: DebtRepository, A: AccountRepository, C: CategoryRepository> DebtService<D, A, C> {
+    pub fn new(debts: D, accounts: A, categories: C) -> Self {
+        DebtService {
+            debts,
+            accounts,
+            categories,
+        }
+    }
+
+    /// Memvalidasi input utang.
+    fn build_debt<'i>(&self, input: &'i DebtInput) -> Result<NewDebt<'i>, AppError> {
+        let counterparty = input.counterparty.trim();
+        if counterparty.is_empty() {
+            return Err(AppError::validation(
+                "counterparty",
+                "Nama pihak lawan tidak boleh kosong.",
+            ));
+        }
+
+        let principal = Money::from_major_as(input.principal, input.currency, "principal")?;
+        if !principal.is_positive() {
+            return Err(AppError::validation(
+                "principal",
+                "Pokok pinjaman harus lebih besar dari nol.",
+            ));
+        }
+
+        if input.installment_count.is_some() && input.due_date.is_some() {
+            return Err(AppError::validation(
+                "due_date",
+                "Jatuh tempo utang bercicilan mengikuti cicilan terakhir.",
+            ));
+        }
+        let schedule = match input.installment_count {
+            Some(count) => {
+                let rate = input.interest_rate.unwrap_or(0.0);
+                loan_terms(
+                    principal,
+                    rate,
+                    count,
+                    input.interest_method,
+                    input.start_date,
+                )?
+                .amortize()
+            }
+            None if input.interest_rate.is_some() => {
+                return Err(AppError::validation(
+                    "interest_rate",
+                    "Bunga hanya bisa dihitung untuk utang bercicilan.",
+                ))
+            }
+            None => Vec::new(),
+        };
+        if input.due_date.is_some_and(|due| due < input.start_date) {
+            return Err(AppError::validation(
+                "due_date",
+                "Jatuh tempo tidak boleh sebelum tanggal mulai.",
+            ));
+        }
+
+        if let Some(category_id) = input.category_id {
+            let category = self.categories.find_by_id(category_id)?;
+            let expected_type = match input.direction {
+                DebtDirection::Payable => CategoryType::Expense,
+                DebtDirection::Receivable => CategoryType::Income,
+            };
+            if category.category_type != expected_type {
+                return Err(AppError::validation(
+                    "category_id",
+                    "Kategori cicilan harus kategori pengeluaran untuk utang dan kategori \
+                     pemasukan untuk piutang.",
+                ));
+            }
+        }
+
+        if let Some(account_id) = input.account_id {
+            let account = self.accounts.find_by_id(account_id)?;
+            if account.currency != input.currency {
+                return Err(AppError::validation(
+                    "account_id",
+                    format!(
+                        "Mata uang akun '{}' ({}) berbeda dengan mata uang utang ({}).",
+                        account.name, account.currency, input.currency
+                    ),
+                ));
+            }
+        }
+
+        Ok(NewDebt {
+            counterparty,
+            direction: input.direction,
+            principal,
+            start_date: input.start_date,
+            due_date: input.due_date,
+            interest_rate: input.interest_rate,
+            installment_count: input.installment_count,
+            interest_method: input.interest_method,
+            category_id: input.category_id,
+            account_id: input.account_id,
+            schedule,
+            description: input
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|d| !d.is_empty()),
+        })
+    }
+
+    /// Membuat utang atau piutang baru. Dengan `account_id`, pokoknya langsung dicairkan ke
+    /// (utang) atau dari (piutang) akun tersebut.
+    pub fn create_debt(&self, input: &DebtInput) -> Result<(), AppError> {
+        let debt = self.build_debt(input)?;
+        self.debts.create(&debt)
+    }
+
+    /// Mengambil semua utang dan piutang.
+    pub fn get_all_debts(&self) -> Result<Vec<Debt>, AppError> {
+        self.debts.find_all()
+    }
+
+    /// Mengambil satu utang berdasarkan ID-nya.
+    pub fn get_debt_by_id(&self, id: i64) -> Result<Debt, AppError> {
+        self.debts.find_by_id(id)
+    }
+
+    /// Memperbarui utang yang ada. Arah dan mata uang tidak bisa diubah setelah ada
+    /// pembayaran, karena transaksi pembayarannya sudah tercatat.
+    pub fn update_debt(&self, id: i64, input: &DebtInput) -> Result<(), AppError> {
+        let current = self.debts.find_by_id(id)?;
+        let debt = self.build_debt(input)?;
+        let changes_payments =
+            debt.direction != current.direction || debt.principal.currency() != current.currency;
+        if changes_payments && !self.debts.find_payments(id)?.is_empty() {
+            return Err(AppError::Conflict(format!(
+                "Arah dan mata uang utang dengan '{}' tidak bisa diubah karena sudah ada pembayaran.",
+                current.counterparty
+            )));
+        }
+        self.debts.update(id, &debt)
+    }
+
+    /// Menghapus utang berdasarkan ID beserta pencairannya. Transaksi pembayarannya tetap
+    /// tercatat.
+    pub fn delete_debt(&self, id: i64) -> Result<(), AppError> {
+        self.debts.delete(id)
+    }
+
+    /// Mencatat pembayaran utang (pengeluaran) atau pelunasan piutang (pemasukan) pada akun
+    /// yang dipilih. Pembayaran tidak boleh melebihi sisa utang.
+    pub fn add_payment(&self, debt_id: i64, input: &DebtPaymentInput) -> Result<(), AppError> {
+        let debt = self.debts.find_by_id(debt_id)?;
+
+        let account = self.accounts.find_by_id(input.account_id)?;
+        if account.currency != debt.currency {
+            return Err(AppError::validation(
+                "account_id",
+                format!(
+                    "Mata uang akun '{}' ({}) berbeda dengan mata uang utang ({}).",
+                    account.name, account.currency, debt.currency
+                ),
+            ));
+        }
+
+        let expected_type = match debt.direction {
+            DebtDirection::Payable => CategoryType::Expense,
+            DebtDirection::Receivable => CategoryType::Income,
+        };
+        let category = self.categories.find_by_id(input.category_id)?;
+        if category.category_type != expected_type {
+            return Err(AppError::validation(
+                "category_id",
+                format!(
+                    "Tipe kategori '{}' tidak sesuai dengan jenis pembayaran.",
+                    category.name
+                ),
+            ));
+        }
+
+        let amount = Money::from_major(input.amount, debt.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah pembayaran harus lebih besar dari nol.",
+            ));
+        }
+        let outstanding = total_due(&debt, &self.debts.find_installments(debt_id)?)?
+            .checked_sub(total_paid(&debt, &self.debts.find_payments(debt_id)?)?)?;
+        if amount.minor_units() > outstanding.minor_units() {
+            return Err(AppError::validation(
+                "amount",
+                format!("Pembayaran melebihi sisa utang ({}).", outstanding),
+            ));
+        }
+
+        let description = match input.description.as_deref().map(str::trim) {
+            Some(description) if !description.is_empty() => description.to_string(),
+            _ => match debt.direction {
+                DebtDirection::Payable => format!("Bayar utang ke {}", debt.counterparty),
+                DebtDirection::Receivable => {
+                    format!("Pelunasan piutang dari {}", debt.counterparty)
+                }
+            },
+        };
+        self.debts.add_payment(
+            debt_id,
+            debt.direction,
+            &NewDebtPayment {
+                description: &description,
+                amount,
+                date: input.date,
+                category_id: category.id,
+                account_id: account.id,
+            },
+        )
+    }
+
+    /// Mengambil semua pembayaran sebuah utang.
+    pub fn get_payments(&self, debt_id: i64) -> Result<Vec<DebtPayment>, AppError> {
+        self.debts.find_by_id(debt_id)?;
+        self.debts.find_payments(debt_id)
+    }
+
+    /// Menghapus pembayaran beserta transaksinya.
+    pub fn delete_payment(&self, id: i64) -> Result<(), AppError> {
+        self.debts.delete_payment(id)
+    }
+
+    /// Sisa utang dan jadwal cicilan per `date` (default hari ini). Pembayaran setelah
+    /// `date` tidak dihitung.
+    pub fn get_debt_status(
+        &self,
+        id: i64,
+        date: Option<NaiveDate>,
+    ) -> Result<DebtStatus, AppError> {
+        let date = date.unwrap_or_else(|| Local::now().date_naive());
+        let debt = self.debts.find_by_id(id)?;
+        let payments: Vec<DebtPayment> = self
+            .debts
+            .find_payments(id)?
+            .into_iter()
+            .filter(|payment| payment.date.date() <= date)
+            .collect();
+        let schedule = self.debts.find_installments(id)?;
+        status(debt, schedule, &payments, date)
+    }
+
+    /// Sisa utang dan piutang yang belum lunas, dikelompokkan per pihak lawan (tanpa
+    /// membedakan huruf besar/kecil) dan mata uang.
+    pub fn get_counterparty_balances(&self) -> Result<Vec<CounterpartyBalance>, AppError> {
+        let mut balances: Vec<CounterpartyBalance> = Vec::new();
+        for debt in self.debts.find_all()? {
+            let paid = total_paid(&debt, &self.debts.find_payments(debt.id)?)?;
+            let outstanding =
+                total_due(&debt, &self.debts.find_installments(debt.id)?)?.checked_sub(paid)?;
+            if !outstanding.is_positive() {
+                continue;
+            }
+
+            let index = match balances.iter().position(|balance| {
+                balance.currency == debt.currency
+                    && balance.counterparty.to_lowercase() == debt.counterparty.to_lowercase()
+            }) {
+                Some(index) => index,
+                None => {
+                    let zero = Money::zero(debt.currency);
+                    balances.push(CounterpartyBalance {
+                        counterparty: debt.counterparty.clone(),
+                        currency: debt.currency,
+                        receivable: zero,
+                        payable: zero,
+                        net: zero,
+                    });
+                    balances.len() - 1
+                }
+            };
+            let balance = &mut balances[index];
+            match debt.direction {
+                DebtDirection::Payable => {
+                    balance.payable = balance.payable.checked_add(outstanding)?;
+                    balance.net = balance.net.checked_sub(outstanding)?;
+                }
+                DebtDirection::Receivable => {
+                    balance.receivable = balance.receivable.checked_add(outstanding)?;
+                    balance.net = balance.net.checked_add(outstanding)?;
+                }
+            }
+        }
+        Ok(balances)
+    }
+}
+
+/// Jumlah seluruh pembayaran sebuah utang.
+fn total_paid(debt: &Debt, payments: &[DebtPayment]) -> Result<Money, AppError> {
+    payments
+        .iter()
+        .try_fold(Money::zero(debt.currency), |total, payment| {
+            total.checked_add(payment.amount)
+        })
+}
+
+/// Tabel angsuran simulasi untuk `input` tanpa menyimpannya.
+pub fn calculate_loan_schedule(input: &LoanInput) -> Result<LoanSchedule, AppError> {
+    let principal = Money::from_major_as(input.principal, input.currency, "principal")?;
+    if !principal.is_positive() {
+        return Err(AppError::validation(
+            "principal",
+            "Pokok pinjaman harus lebih besar dari nol.",
+        ));
+    }
+    let start_date = input
+        .start_date
+        .unwrap_or_else(|| Local::now().date_naive());
+    let installments = loan_terms(
+        principal,
+        input.interest_rate,
+        input.installment_count,
+        input.method,
+        start_date,
+    )?
+    .amortize();
+
+    let total_interest = installments
+        .iter()
+        .try_fold(Money::zero(input.currency), |total, installment| {
+            total.checked_add(installment.interest)
+        })?;
+    Ok(LoanSchedule {
+        method: input.method,
+        installments,
+        total_principal: principal,
+        total_interest,
+        total_amount: principal.checked_add(total_interest)?,
+    })
+}
+
+/// Memvalidasi jumlah cicilan dan bunga.
+fn loan_terms(
+    principal: Money,
+    interest_rate: f64,
+    installment_count: u32,
+    method: InterestMethod,
+    start_date: NaiveDate,
+) -> Result<LoanTerms, AppError> {
+    if installment_count == 0 || installment_count > MAX_INSTALLMENTS {
+        return Err(AppError::validation(
+            "installment_count",
+            format!("Jumlah cicilan harus antara 1 dan {}.", MAX_INSTALLMENTS),
+        ));
+    }
+    if !interest_rate.is_finite() || interest_rate < 0.0 {
+        return Err(AppError::validation(
+            "interest_rate",
+            "Bunga tidak boleh negatif.",
+        ));
+    }
+    Ok(LoanTerms {
+        principal,
+        annual_rate: interest_rate,
+        installment_count,
+        method,
+        start_date,
+    })
+}
+
+/// Pokok ditambah seluruh bunga pada tabel angsuran (atau pokok saja tanpa cicilan).
+fn total_due(debt: &Debt, schedule: &[ScheduledInstallment]) -> Result<Money, AppError> {
+    if schedule.is_empty() {
+        return Ok(debt.principal);
+    }
+    schedule
+        .iter()
+        .try_fold(Money::zero(debt.currency), |total, installment| {
+            total.checked_add(installment.amount)
+        })
+}
+
+/// Menyusun ringkasan utang; pembayaran dialokasikan berurutan dari cicilan pertama, dan
+/// dalam setiap cicilan menutup bunga lebih dulu sebelum pokok.
+pub(crate) fn status(
+    debt: Debt,
+    schedule: Vec<ScheduledInstallment>,
+    payments: &[DebtPayment],
+    date: NaiveDate,
+) -> Result<DebtStatus, AppError> {
+    let total_due = total_due(&debt, &schedule)?;
+    let total_interest = total_due.checked_sub(debt.principal)?;
+    let paid = total_paid(&debt, payments)?;
+    let outstanding = total_due.checked_sub(paid)?;
+
+    let mut unallocated = paid.minor_units();
+    let mut principal_paid = 0;
+    let installments: Vec<Installment> = schedule
+        .into_iter()
+        .map(|scheduled| {
+            let allocated = unallocated.clamp(0, scheduled.amount.minor_units());
+            unallocated -= allocated;
+            principal_paid += (allocated - scheduled.interest.minor_units()).max(0);
+            let status = if allocated == scheduled.amount.minor_units() {
+                InstallmentStatus::Paid
+            } else if scheduled.due_date < date {
+                InstallmentStatus::Overdue
+            } else if allocated > 0 {
+                InstallmentStatus::Partial
+            } else {
+                InstallmentStatus::Upcoming
+            };
+            Installment {
+                paid: Money::new(allocated, debt.currency),
+                status,
+                scheduled,
+            }
+        })
+        .collect();
+    if installments.is_empty() {
+        principal_paid = paid.minor_units();
+    }
+    let remaining_principal = Money::new(
+        (debt.principal.minor_units() - principal_paid).max(0),
+        debt.currency,
+    );
+
+    let overdue = outstanding.is_positive()
+        && (debt.due_date.is_some_and(|due| due < date)
+            || installments
+                .iter()
+                .any(|installment| installment.status == InstallmentStatus::Overdue));
+
+    Ok(DebtStatus {
+        debt,
+        total_interest,
+        total_due,
+        paid,
+        outstanding,
+        remaining_principal,
+        overdue,
+        installments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::money::Currency;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::repositories::debt_repository::SqliteDebtRepository;
+    use crate::repositories::expense_repository::{ExpenseRepository, SqliteExpenseRepository};
+    use crate::repositories::income_repository::{IncomeRepository, SqliteIncomeRepository};
+    use crate::services::test_support::{add_account, add_category, at, balance, date, idr, usd};
+    use chrono::NaiveDateTime;
+
+    fn service(
+        db: &Database,
+    ) -> DebtService<
+        SqliteDebtRepository<'_>,
+        SqliteAccountRepository<'_>,
+        SqliteCategoryRepository<'_>,
+    > {
+        DebtService::new(
+            SqliteDebtRepository::new(db),
+            SqliteAccountRepository::new(db),
+            SqliteCategoryRepository::new(db),
+        )
+    }
+
+    /// Akun 1 Bank (IDR 20.000.000), kategori 1 Cicilan (pengeluaran) dan 2 Pelunasan
+    /// (pemasukan).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(20_000_000.0));
+        add_category(&db, "Cicilan", CategoryType::Expense);
+        add_category(&db, "Pelunasan", CategoryType::Income);
+        db
+    }
+
+    fn debt(counterparty: &str, direction: DebtDirection, principal: f64) -> DebtInput {
+        DebtInput {
+            counterparty: counterparty.to_string(),
+            direction,
+            principal,
+            currency: Currency::IDR,
+            start_date: date(2024, 1, 31),
+            due_date: None,
+            interest_rate: None,
+            installment_count: None,
+            interest_method: InterestMethod::Flat,
+            category_id: None,
+            description: None,
+            account_id: None,
+        }
+    }
+
+    fn payment(amount: f64, date: NaiveDateTime, category_id: i64) -> DebtPaymentInput {
+        DebtPaymentInput {
+            amount,
+            date,
+            account_id: 1,
+            category_id,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn installment_plan_with_flat_interest_tracks_payments() {
+        let db = fixture();
+        let debts = service(&db);
+        let mut input = debt("Leasing Motor", DebtDirection::Payable, 12_000_000.0);
+        input.interest_rate = Some(10.0);
+        input.installment_count = Some(12);
+        debts.create_debt(&input).unwrap();
+
+        // Bunga flat 10% setahun: 1,2 juta; cicilan 1,1 juta per bulan.
+        debts
+            .add_payment(1, &payment(1_100_000.0, at(2024, 2, 28), 1))
+            .unwrap();
+        debts
+            .add_payment(1, &payment(1_650_000.0, at(2024, 3, 29), 1))
+            .unwrap();
+        assert_eq!(balance(&db, 1), 1_725_000_000);
+
+        let status = debts.get_debt_status(1, Some(date(2024, 5, 1))).unwrap();
+        assert_eq!(status.total_due.minor_units(), 1_320_000_000);
+        assert_eq!(status.outstanding.minor_units(), 1_045_000_000);
+        // Cicilan ketiga baru menutup bunga 100 ribu dan pokok 450 ribu.
+        assert_eq!(status.remaining_principal.minor_units(), 955_000_000);
+        assert_eq!(status.installments.len(), 12);
+        // Cicilan jatuh tempo pada akhir bulan mengikuti tanggal mulai 31 Januari.
+        assert_eq!(status.installments[0].scheduled.due_date, date(2024, 2, 29));
+        assert_eq!(status.installments[1].status, InstallmentStatus::Paid);
+        assert_eq!(status.installments[2].scheduled.due_date, date(2024, 4, 30));
+        assert_eq!(status.installments[2].paid.minor_units(), 55_000_000);
+        assert_eq!(status.installments[2].status, InstallmentStatus::Overdue);
+        assert_eq!(status.installments[3].status, InstallmentStatus::Upcoming);
+        assert!(status.overdue);
+    }
+
+    #[test]
+    fn calculator_builds_flat_and_annuity_tables() {
+        let mut input = LoanInput {
+            principal: 100_000_000.0,
+            currency: Currency::IDR,
+            interest_rate: 12.0,
+            installment_count: 12,
+            method: InterestMethod::Annuity,
+            start_date: Some(date(2024, 1, 15)),
+        };
+
+        // Anuitas 1% per bulan: cicilan tetap Rp8.884.878,87.
+        let annuity = calculate_loan_schedule(&input).unwrap();
+        let first = &annuity.installments[0];
+        assert_eq!(first.due_date, date(2024, 2, 15));
+        assert_eq!(first.amount.minor_units(), 888_487_887);
+        assert_eq!(first.interest.minor_units(), 100_000_000);
+        assert_eq!(first.principal.minor_units(), 788_487_887);
+        let last = annuity.installments.last().unwrap();
+        assert_eq!(last.remaining_principal.minor_units(), 0);
+        assert!((last.amount.minor_units() - 888_487_887).abs() < 10);
+
+        input.method = InterestMethod::Flat;
+        let flat = calculate_loan_schedule(&input).unwrap();
+        assert_eq!(flat.total_interest.minor_units(), 1_200_000_000);
+        assert!(flat.total_interest.minor_units() > annuity.total_interest.minor_units());
+        assert!(flat
+            .installments
+            .iter()
+            .all(
+                |installment| installment.amount.minor_units() == 933_333_333
+                    || installment.number == 12
+            ));
+
+        input.installment_count = 0;
+        let err = calculate_loan_schedule(&input).unwrap_err();
+        assert_eq!(err.field(), Some("installment_count"));
+    }
+
+    #[test]
+    fn matching_expense_is_recorded_as_installment_payment() {
+        let db = fixture();
+        let debts = service(&db);
+        let mut input = debt("Leasing Motor", DebtDirection::Payable, 12_000_000.0);
+        input.interest_rate = Some(10.0);
+        input.installment_count = Some(12);
+        input.category_id = Some(1);
+        debts.create_debt(&input).unwrap();
+
+        let expenses = SqliteExpenseRepository::new(&db);
+        let cicilan = idr(1_100_000.0);
+        // Tiga hari setelah jatuh tempo 29 Februari: cocok.
+        expenses
+            .create("Cicilan motor", cicilan, at(2024, 3, 3), 1, 1)
+            .unwrap();
+        // Jumlah berbeda dan tanggal terlalu jauh dari jatuh tempo: tidak cocok.
+        expenses
+            .create("Servis motor", idr(250_000.0), at(2024, 3, 28), 1, 1)
+            .unwrap();
+        expenses
+            .create("Cicilan motor", cicilan, at(2024, 6, 15), 1, 1)
+            .unwrap();
+
+        let payments = debts.get_payments(1).unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].expense_id, Some(1));
+        let status = debts.get_debt_status(1, Some(date(2024, 3, 10))).unwrap();
+        assert_eq!(status.installments[0].status, InstallmentStatus::Paid);
+        assert!(!status.overdue);
+
+        // Piutang memakai kategori pemasukan.
+        let mut input = debt("Adik", DebtDirection::Receivable, 1_000_000.0);
+        input.category_id = Some(1);
+        let err = debts.create_debt(&input).unwrap_err();
+        assert_eq!(err.field(), Some("category_id"));
+    }
+
+    #[test]
+    fn editing_expense_reevaluates_installment_link() {
+        let db = fixture();
+        let debts = service(&db);
+        let mut input = debt("Leasing Motor", DebtDirection::Payable, 12_000_000.0);
+        input.interest_rate = Some(10.0);
+        input.installment_count = Some(12);
+        input.category_id = Some(1);
+        debts.create_debt(&input).unwrap();
+
+        let expenses = SqliteExpenseRepository::new(&db);
+        let cicilan = idr(1_100_000.0);
+        expenses
+            .create("Cicilan motor", cicilan, at(2024, 3, 3), 1, 1)
+            .unwrap();
+        assert_eq!(debts.get_payments(1).unwrap().len(), 1);
+
+        // Jumlahnya dikoreksi sehingga tidak lagi sama dengan cicilan.
+        expenses
+            .update(1, "Cicilan motor", idr(1_000_000.0), at(2024, 3, 3), 1, 1)
+            .unwrap();
+        assert!(debts.get_payments(1).unwrap().is_empty());
+        expenses
+            .update(1, "Cicilan motor", cicilan, at(2024, 3, 3), 1, 1)
+            .unwrap();
+        assert_eq!(debts.get_payments(1).unwrap().len(), 1);
+
+        // Pembayaran yang dicatat langsung tetap tertaut walau tidak cocok dengan cicilan.
+        debts
+            .add_payment(1, &payment(500_000.0, at(2024, 3, 20), 1))
+            .unwrap();
+        expenses
+            .update(2, "Tambahan cicilan", idr(600_000.0), at(2024, 3, 20), 1, 1)
+            .unwrap();
+        let payments = debts.get_payments(1).unwrap();
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[1].amount, idr(600_000.0));
+    }
+
+    #[test]
+    fn matching_income_is_recorded_as_receivable_installment() {
+        let db = fixture();
+        let debts = service(&db);
+        let mut input = debt("Adik", DebtDirection::Receivable, 3_000_000.0);
+        input.installment_count = Some(3);
+        input.category_id = Some(2);
+        debts.create_debt(&input).unwrap();
+
+        let incomes = SqliteIncomeRepository::new(&db);
+        // Sehari setelah jatuh tempo 29 Februari: cocok.
+        incomes
+            .create("Cicilan dari adik", idr(1_000_000.0), at(2024, 3, 1), 2, 1)
+            .unwrap();
+        let payments = debts.get_payments(1).unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].income_id, Some(1));
+        let status = debts.get_debt_status(1, Some(date(2024, 3, 10))).unwrap();
+        assert_eq!(status.installments[0].status, InstallmentStatus::Paid);
+
+        incomes
+            .update(1, "Cicilan dari adik", idr(900_000.0), at(2024, 3, 1), 2, 1)
+            .unwrap();
+        assert!(debts.get_payments(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn receivable_payments_create_income_and_delete_restores_balance() {
+        let db = fixture();
+        let debts = service(&db);
+        debts
+            .create_debt(&debt("Adik", DebtDirection::Receivable, 3_000_000.0))
+            .unwrap();
+
+        let err = debts
+            .add_payment(1, &payment(1_000_000.0, at(2024, 2, 1), 1))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("category_id"));
+        let err = debts
+            .add_payment(1, &payment(3_000_001.0, at(2024, 2, 1), 2))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+
+        debts
+            .add_payment(1, &payment(1_000_000.0, at(2024, 2, 1), 2))
+            .unwrap();
+        assert_eq!(balance(&db, 1), 2_100_000_000);
+        let payments = debts.get_payments(1).unwrap();
+        assert!(payments[0].income_id.is_some());
+
+        debts.delete_payment(payments[0].id).unwrap();
+        assert_eq!(balance(&db, 1), 2_000_000_000);
+        assert!(debts.get_payments(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn balances_are_grouped_per_counterparty() {
+        let db = fixture();
+        let debts = service(&db);
+        debts
+            .create_debt(&debt("Budi", DebtDirection::Receivable, 3_000_000.0))
+            .unwrap();
+        debts
+            .create_debt(&debt("budi ", DebtDirection::Payable, 1_000_000.0))
+            .unwrap();
+        debts
+            .create_debt(&debt("Sari", DebtDirection::Payable, 500_000.0))
+            .unwrap();
+        debts
+            .add_payment(3, &payment(500_000.0, at(2024, 2, 1), 1))
+            .unwrap();
+
+        let balances = debts.get_counterparty_balances().unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].counterparty, "Budi");
+        assert_eq!(balances[0].receivable.minor_units(), 300_000_000);
+        assert_eq!(balances[0].payable.minor_units(), 100_000_000);
+        assert_eq!(balances[0].net.minor_units(), 200_000_000);
+    }
+
+    #[test]
+    fn rejects_interest_without_plan_and_direction_change_after_payment() {
+        let db = fixture();
+        let debts = service(&db);
+
+        let mut input = debt("Bank", DebtDirection::Payable, 1_000_000.0);
+        input.interest_rate = Some(5.0);
+        let err = debts.create_debt(&input).unwrap_err();
+        assert_eq!(err.field(), Some("interest_rate"));
+
+        let input = debt("Bank", DebtDirection::Payable, 1_000_000.0);
+        debts.create_debt(&input).unwrap();
+        debts
+            .add_payment(1, &payment(100_000.0, at(2024, 2, 1), 1))
+            .unwrap();
+        let err = debts
+            .update_debt(1, &debt("Bank", DebtDirection::Receivable, 1_000_000.0))
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+
+    #[test]
+    fn disbursement_moves_account_balance_until_deleted() {
+        let db = fixture();
+        add_account(&db, "Dolar", Money::zero(usd()));
+        let debts = service(&db);
+
+        let mut input = debt("Adik", DebtDirection::Receivable, 3_000_000.0);
+        input.account_id = Some(2);
+        let err = debts.create_debt(&input).unwrap_err();
+        assert_eq!(err.field(), Some("account_id"));
+
+        input.account_id = Some(1);
+        debts.create_debt(&input).unwrap();
+        assert_eq!(balance(&db, 1), 1_700_000_000);
+
+        // Mengubahnya menjadi utang membalik arah pencairan.
+        let mut input = debt("Adik", DebtDirection::Payable, 1_000_000.0);
+        input.account_id = Some(1);
+        debts.update_debt(1, &input).unwrap();
+        assert_eq!(balance(&db, 1), 2_100_000_000);
+
+        debts.delete_debt(1).unwrap();
+        assert_eq!(balance(&db, 1), 2_000_000_000);
+        assert_eq!(debts.delete_debt(1).unwrap_err().code(), "NOT_FOUND");
+    }
+}
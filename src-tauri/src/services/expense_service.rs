@@ -0,0 +1,293 @@
+//! Modul ini berisi logika bisnis yang terkait dengan pengeluaran.
+
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::expense::Expense;
+use crate::models::money::Money;
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::category_repository::CategoryRepository;
+use crate::repositories::expense_repository::ExpenseRepository;
+use chrono::NaiveDateTime;
+
+/// Service pengeluaran. Validasi membutuhkan data kategori dan akun, sehingga service ini
+/// bergantung pada tiga repository.
+pub struct ExpenseService<R, C, A> {
+    expenses: R,
+    categories: C,
+    accounts: A,
+}
+
+impl<R: ExpenseRepository, C: CategoryRepository, A: AccountRepository> ExpenseService<R, C, A> {
+    pub fn new(expenses: R, categories: C, accounts: A) -> Self {
+        ExpenseService {
+            expenses,
+            categories,
+            accounts,
+        }
+    }
+
+    /// Memvalidasi input pengeluaran dan mengubah jumlahnya ke mata uang akun.
+    fn validate(
+        &self,
+        description: &str,
+        amount: f64,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<Money, AppError> {
+        if description.trim().is_empty() {
+            return Err(AppError::validation(
+                "description",
+                "Deskripsi pengeluaran tidak boleh kosong.",
+            ));
+        }
+
+        let category = self.categories.find_by_id(category_id)?;
+        if category.category_type != CategoryType::Expense {
+            return Err(AppError::validation(
+                "category_id",
+                format!("Kategori '{}' bukan kategori pengeluaran.", category.name),
+            ));
+        }
+
+        let account = self.accounts.find_by_id(account_id)?;
+        let amount = Money::from_major(amount, account.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah pengeluaran harus lebih besar dari nol.",
+            ));
+        }
+
+        Ok(amount)
+    }
+
+    /// Mencatat pengeluaran baru dan memperbarui saldo akunnya.
+    pub fn create_expense(
+        &self,
+        description: &str,
+        amount: f64,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let amount = self.validate(description, amount, category_id, account_id)?;
+        self.expenses
+            .create(description.trim(), amount, date, category_id, account_id)
+    }
+
+    /// Mengambil semua pengeluaran.
+    pub fn get_all_expenses(&self) -> Result<Vec<Expense>, AppError> {
+        self.expenses.find_all()
+    }
+
+    /// Mengambil satu pengeluaran berdasarkan ID-nya.
+    pub fn get_expense_by_id(&self, id: i64) -> Result<Expense, AppError> {
+        self.expenses.find_by_id(id)
+    }
+
+    /// Mencari pengeluaran dengan filter, urutan, dan paginasi yang dijalankan di SQL.
+    pub fn query_expenses(&self, query: &TransactionQuery) -> Result<Page<Expense>, AppError> {
+        query.validate()?;
+        self.expenses.find_page(query)
+    }
+
+    /// Memperbarui pengeluaran yang ada.
+    pub fn update_expense(
+        &self,
+        id: i64,
+        description: &str,
+        amount: f64,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let amount = self.validate(description, amount, category_id, account_id)?;
+        self.expenses.update(
+            id,
+            description.trim(),
+            amount,
+            date,
+            category_id,
+            account_id,
+        )
+    }
+
+    /// Menghapus pengeluaran berdasarkan ID.
+    pub fn delete_expense(&self, id: i64) -> Result<(), AppError> {
+        self.expenses.delete(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::category::CategoryType;
+    use crate::models::transaction_query::{SortDirection, SortKey};
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::repositories::expense_repository::SqliteExpenseRepository;
+    use crate::services::test_support::{add_account, add_category, at, balance, idr};
+
+    fn service(
+        db: &Database,
+    ) -> ExpenseService<
+        SqliteExpenseRepository<'_>,
+        SqliteCategoryRepository<'_>,
+        SqliteAccountRepository<'_>,
+    > {
+        ExpenseService::new(
+            SqliteExpenseRepository::new(db),
+            SqliteCategoryRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Database dengan akun 1 (saldo IDR 100.000,00), kategori 1 (Expense) dan 2 (Income).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Makan", CategoryType::Expense);
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_account(&db, "Dompet", idr(100_000.0));
+        db
+    }
+
+    #[test]
+    fn create_update_delete_keep_balance_in_sync() {
+        let db = fixture();
+        let expenses = service(&db);
+
+        expenses
+            .create_expense("Makan siang", 25_000.0, at(2024, 5, 1), 1, 1)
+            .unwrap();
+        assert_eq!(balance(&db, 1), 7_500_000);
+
+        let saved = expenses.get_expense_by_id(1).unwrap();
+        assert_eq!(saved.date, at(2024, 5, 1));
+        assert_eq!(saved.account_id, Some(1));
+
+        expenses
+            .update_expense(1, "Makan malam", 40_000.0, at(2024, 5, 1), 1, 1)
+            .unwrap();
+        assert_eq!(balance(&db, 1), 6_000_000);
+
+        expenses.delete_expense(1).unwrap();
+        assert_eq!(balance(&db, 1), 10_000_000);
+        assert!(expenses.get_all_expenses().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_income_category_and_non_positive_amount() {
+        let db = fixture();
+        let expenses = service(&db);
+
+        let err = expenses
+            .create_expense("Gaji", 10.0, at(2024, 5, 1), 2, 1)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("category_id"));
+
+        let err = expenses
+            .create_expense("Kosong", 0.0, at(2024, 5, 1), 1, 1)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+
+        let err = expenses
+            .create_expense(" ", 10.0, at(2024, 5, 1), 1, 1)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("description"));
+
+        assert_eq!(balance(&db, 1), 10_000_000);
+    }
+
+    #[test]
+    fn missing_account_is_not_found() {
+        let db = fixture();
+
+        let err = service(&db)
+            .create_expense("Makan", 10.0, at(2024, 5, 1), 1, 99)
+            .unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn update_and_delete_of_missing_expense_are_not_found() {
+        let db = fixture();
+        let expenses = service(&db);
+
+        let err = expenses
+            .update_expense(99, "Makan", 10.0, at(2024, 5, 1), 1, 1)
+            .unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert!(err.to_string().starts_with("Pengeluaran dengan ID 99"));
+
+        let err = expenses.delete_expense(99).unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert_eq!(balance(&db, 1), 10_000_000);
+    }
+
+    #[test]
+    fn query_filters_sorts_and_paginates_in_sql() {
+        let db = fixture();
+        let expenses = service(&db);
+        expenses
+            .create_expense("Kopi", 15_000.0, at(2024, 5, 1), 1, 1)
+            .unwrap();
+        expenses
+            .create_expense("Makan siang", 30_000.0, at(2024, 5, 2), 1, 1)
+            .unwrap();
+        expenses
+            .create_expense("Diskon 100%", 5_000.0, at(2024, 5, 3), 1, 1)
+            .unwrap();
+        expenses
+            .create_expense("Makan malam", 45_000.0, at(2024, 5, 4), 1, 1)
+            .unwrap();
+
+        let page = expenses
+            .query_expenses(&TransactionQuery {
+                search: Some("makan".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.items[0].description, "Makan malam");
+
+        let page = expenses
+            .query_expenses(&TransactionQuery {
+                search: Some("100%".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total_count, 1);
+
+        let page = expenses
+            .query_expenses(&TransactionQuery {
+                date_from: Some(at(2024, 5, 2)),
+                min_amount: Some(10_000.0),
+                max_amount: Some(45_000.0),
+                sort_by: SortKey::Amount,
+                sort_direction: SortDirection::Asc,
+                limit: Some(1),
+                offset: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].description, "Makan malam");
+    }
+
+    #[test]
+    fn query_rejects_inverted_ranges() {
+        let db = fixture();
+
+        let err = service(&db)
+            .query_expenses(&TransactionQuery {
+                min_amount: Some(10.0),
+                max_amount: Some(1.0),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err.field(), Some("max_amount"));
+    }
+}
@@ -0,0 +1,480 @@
+//! Modul ini berisi logika bisnis untuk target tabungan.
+//! Perkiraan tanggal tercapai memakai laju rata-rata sejak setoran pertama, sedangkan
+//! kebutuhan per bulan dihitung dari sisa target dibagi bulan kalender menuju tenggat.
+
+use crate::error::AppError;
+use crate::models::goal::{
+    ContributionInput, Goal, GoalContribution, GoalInput, GoalProgress, NewGoal,
+};
+use crate::models::money::{Currency, Money};
+use crate::models::transfer::NewTransfer;
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::goal_repository::GoalRepository;
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveTime};
+
+/// Rata-rata jumlah hari dalam sebulan, untuk mengubah laju harian menjadi bulanan.
+const DAYS_PER_MONTH: f64 = 365.25 / 12.0;
+
+/// Service target tabungan, bergantung pada `GoalRepository` dan `AccountRepository`.
+pub struct GoalService<G, A> {
+    goals: G,
+    accounts: A,
+}
+
+impl<G: GoalRepository, A: AccountRepository> GoalService<G, A> {
+    pub fn new(goals: G, accounts: A) -> Self {
+        GoalService { goals, accounts }
+    }
+
+    /// Memvalidasi input. Bila target terhubung ke akun, mata uangnya mengikuti akun.
+    fn build_goal<'i>(&self, input: &'i GoalInput) -> Result<NewGoal<'i>, AppError> {
+        let name = input.name.trim();
+        if name.is_empty() {
+            return Err(AppError::validation(
+                "name",
+                "Nama target tidak boleh kosong.",
+            ));
+        }
+
+        let currency = match input.account_id {
+            Some(account_id) => self.accounts.find_by_id(account_id)?.currency,
+            None => input.currency,
+        };
+        let target_amount = Money::from_major_as(input.target_amount, currency, "target_amount")?;
+        if !target_amount.is_positive() {
+            return Err(AppError::validation(
+                "target_amount",
+                "Jumlah target harus lebih besar dari nol.",
+            ));
+        }
+
+        Ok(NewGoal {
+            name,
+            target_amount,
+            target_date: input.target_date,
+            account_id: input.account_id,
+        })
+    }
+
+    /// Membuat target baru.
+    pub fn create_goal(&self, input: &GoalInput) -> Result<(), AppError> {
+        let goal = self.build_goal(input)?;
+        self.goals.create(&goal)
+    }
+
+    /// Mengambil semua target.
+    pub fn get_all_goals(&self) -> Result<Vec<Goal>, AppError> {
+        self.goals.find_all()
+    }
+
+    /// Mengambil satu target berdasarkan ID-nya.
+    pub fn get_goal_by_id(&self, id: i64) -> Result<Goal, AppError> {
+        self.goals.find_by_id(id)
+    }
+
+    /// Memperbarui target yang ada. Mata uang tidak bisa diubah setelah ada setoran.
+    pub fn update_goal(&self, id: i64, input: &GoalInput) -> Result<(), AppError> {
+        let current = self.goals.find_by_id(id)?;
+        let goal = self.build_goal(input)?;
+        if goal.target_amount.currency() != current.currency
+            && !self.goals.find_contributions(id)?.is_empty()
+        {
+            return Err(AppError::Conflict(format!(
+                "Mata uang target '{}' tidak bisa diubah karena sudah ada setoran.",
+                current.name
+            )));
+        }
+        self.goals.update(id, &goal)
+    }
+
+    /// Menghapus target berdasarkan ID.
+    pub fn delete_goal(&self, id: i64) -> Result<(), AppError> {
+        self.goals.delete(id)
+    }
+
+    /// Mencatat setoran (atau penarikan bila negatif). Dengan `from_account_id`, uangnya
+    /// sekaligus ditransfer dari akun tersebut ke akun target (atau sebaliknya untuk penarikan).
+    pub fn add_contribution(
+        &self,
+        goal_id: i64,
+        input: &ContributionInput,
+    ) -> Result<(), AppError> {
+        let goal = self.goals.find_by_id(goal_id)?;
+        let amount = Money::from_major(input.amount, goal.currency)?;
+        if amount.is_zero() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah setoran tidak boleh nol.",
+            ));
+        }
+        if amount.is_negative() {
+            let saved = sum(goal.currency, &self.goals.find_contributions(goal_id)?)?;
+            if saved.checked_add(amount)?.is_negative() {
+                return Err(AppError::validation(
+                    "amount",
+                    format!("Penarikan melebihi tabungan yang terkumpul ({}).", saved),
+                ));
+            }
+        }
+
+        let description = format!("Tabungan: {}", goal.name);
+        let transfer = match input.from_account_id {
+            Some(from_account_id) => Some(self.build_transfer(
+                &goal,
+                from_account_id,
+                amount,
+                input.date,
+                &description,
+            )?),
+            None => None,
+        };
+        let note = input
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        self.goals
+            .add_contribution(goal_id, amount, input.date, note, transfer.as_ref())
+    }
+
+    /// Menyusun transfer antara `from_account_id` dan akun target untuk sebuah setoran.
+    fn build_transfer<'d>(
+        &self,
+        goal: &Goal,
+        from_account_id: i64,
+        amount: Money,
+        date: NaiveDate,
+        description: &'d str,
+    ) -> Result<NewTransfer<'d>, AppError> {
+        let goal_account_id = goal.account_id.ok_or_else(|| {
+            AppError::validation(
+                "from_account_id",
+                format!("Target '{}' tidak terhubung ke akun.", goal.name),
+            )
+        })?;
+        if from_account_id == goal_account_id {
+            return Err(AppError::validation(
+                "from_account_id",
+                "Akun sumber tidak boleh sama dengan akun target.",
+            ));
+        }
+        let from = self.accounts.find_by_id(from_account_id)?;
+        if from.currency != goal.currency {
+            return Err(AppError::validation(
+                "from_account_id",
+                format!(
+                    "Mata uang akun '{}' ({}) berbeda dengan target ({}).",
+                    from.name, from.currency, goal.currency
+                ),
+            ));
+        }
+
+        // Penarikan memindahkan uang kembali dari akun target ke akun sumber.
+        let (from_account_id, to_account_id, amount) = if amount.is_negative() {
+            (goal_account_id, from.id, amount.negated())
+        } else {
+            (from.id, goal_account_id, amount)
+        };
+        Ok(NewTransfer {
+            description: Some(description),
+            from_account_id,
+            to_account_id,
+            amount,
+            fee: Money::zero(goal.currency),
+            rate: 1.0,
+            received_amount: amount,
+            date: date.and_time(NaiveTime::MIN),
+        })
+    }
+
+    /// Mengambil semua setoran sebuah target.
+    pub fn get_contributions(&self, goal_id: i64) -> Result<Vec<GoalContribution>, AppError> {
+        self.goals.find_by_id(goal_id)?;
+        self.goals.find_contributions(goal_id)
+    }
+
+    /// Menghapus setoran beserta transfer yang ditautkan.
+    pub fn delete_contribution(&self, id: i64) -> Result<(), AppError> {
+        self.goals.delete_contribution(id)
+    }
+
+    /// Perkembangan semua target per `date` (default hari ini).
+    pub fn get_goal_progress(
+        &self,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<GoalProgress>, AppError> {
+        let date = date.unwrap_or_else(|| Local::now().date_naive());
+        self.goals
+            .find_all()?
+            .into_iter()
+            .map(|goal| self.progress(goal, date))
+            .collect()
+    }
+
+    fn progress(&self, goal: Goal, date: NaiveDate) -> Result<GoalProgress, AppError> {
+        let contributions: Vec<GoalContribution> = self
+            .goals
+            .find_contributions(goal.id)?
+            .into_iter()
+            .filter(|c| c.date <= date)
+            .collect();
+
+        // Tanggal terakhir saldo tabungan naik melewati target.
+        let mut saved = Money::zero(goal.currency);
+        let mut reached_on = None;
+        for contribution in &contributions {
+            let before = saved;
+            saved = saved.checked_add(contribution.amount)?;
+            if before.minor_units() < goal.target_amount.minor_units()
+                && saved.minor_units() >= goal.target_amount.minor_units()
+            {
+                reached_on = Some(contribution.date);
+            }
+        }
+        let remaining = goal.target_amount.checked_sub(saved)?;
+        let remaining = if remaining.is_positive() {
+            remaining
+        } else {
+            Money::zero(goal.currency)
+        };
+        let percent_complete =
+            saved.minor_units() as f64 / goal.target_amount.minor_units() as f64 * 100.0;
+
+        let elapsed_days = contributions
+            .first()
+            .map(|first| (date - first.date).num_days() + 1);
+        let average_monthly_saving = elapsed_days.map(|days| {
+            let monthly = saved.minor_units() as f64 * DAYS_PER_MONTH / days as f64;
+            Money::new(monthly.round() as i64, goal.currency)
+        });
+
+        let projected_completion_date = if remaining.is_zero() {
+            reached_on
+        } else {
+            match elapsed_days {
+                Some(days) if saved.is_positive() => {
+                    let needed = i128::from(remaining.minor_units()) * i128::from(days);
+                    let saved = i128::from(saved.minor_units());
+                    u64::try_from((needed + saved - 1) / saved)
+                        .ok()
+                        .and_then(|days| date.checked_add_days(Days::new(days)))
+                }
+                _ => None,
+            }
+        };
+
+        let required_monthly_saving = goal.target_date.map(|target_date| {
+            let months = i64::from(months_between(date, target_date).max(1));
+            let required = (remaining.minor_units() + months - 1) / months;
+            Money::new(required, goal.currency)
+        });
+
+        let on_track = goal.target_date.map(|target_date| {
+            projected_completion_date.is_some_and(|projected| projected <= target_date)
+        });
+
+        Ok(GoalProgress {
+            goal,
+            saved,
+            remaining,
+            percent_complete,
+            average_monthly_saving,
+            projected_completion_date,
+            required_monthly_saving,
+            on_track,
+        })
+    }
+}
+
+/// Menjumlahkan setoran dalam mata uang target.
+fn sum(currency: Currency, contributions: &[GoalContribution]) -> Result<Money, AppError> {
+    contributions
+        .iter()
+        .try_fold(Money::zero(currency), |total, c| {
+            total.checked_add(c.amount)
+        })
+}
+
+/// Jumlah bulan kalender penuh dari `from` sampai `to` (negatif bila `to` sudah lewat).
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    let months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    if to.day() < from.day() {
+        months - 1
+    } else {
+        months
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::goal_repository::SqliteGoalRepository;
+    use crate::repositories::transfer_repository::{SqliteTransferRepository, TransferRepository};
+    use crate::services::test_support::{add_account, balances, date, idr, usd};
+
+    fn service(
+        db: &Database,
+    ) -> GoalService<SqliteGoalRepository<'_>, SqliteAccountRepository<'_>> {
+        GoalService::new(
+            SqliteGoalRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Database dengan akun 1 Bank (IDR 5.000.000), 2 Tabungan (IDR 0), dan 3 Dolar (USD 0).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(5_000_000.0));
+        add_account(&db, "Tabungan", idr(0.0));
+        add_account(&db, "Dolar", Money::zero(usd()));
+        db
+    }
+
+    fn goal(
+        target_amount: f64,
+        target_date: Option<NaiveDate>,
+        account_id: Option<i64>,
+    ) -> GoalInput {
+        GoalInput {
+            name: "Motor".to_string(),
+            target_amount,
+            currency: Currency::IDR,
+            target_date,
+            account_id,
+        }
+    }
+
+    fn contribution(
+        amount: f64,
+        date: NaiveDate,
+        from_account_id: Option<i64>,
+    ) -> ContributionInput {
+        ContributionInput {
+            amount,
+            date,
+            note: None,
+            from_account_id,
+        }
+    }
+
+    #[test]
+    fn projects_completion_and_required_monthly_saving() {
+        let db = fixture();
+        let goals = service(&db);
+        goals
+            .create_goal(&goal(12_000_000.0, Some(date(2024, 12, 31)), None))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(1_000_000.0, date(2024, 1, 1), None))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(1_000_000.0, date(2024, 1, 31), None))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(5_000_000.0, date(2024, 3, 1), None))
+            .unwrap();
+
+        // 2 juta dalam 31 hari: sisa 10 juta butuh 155 hari lagi.
+        let progress = goals.get_goal_progress(Some(date(2024, 1, 31))).unwrap();
+        let progress = &progress[0];
+        assert_eq!(progress.saved.minor_units(), 200_000_000);
+        assert_eq!(progress.remaining.minor_units(), 1_000_000_000);
+        assert_eq!(progress.projected_completion_date, Some(date(2024, 7, 4)));
+        // 11 bulan menuju 31 Desember, dibulatkan ke atas.
+        assert_eq!(
+            progress.required_monthly_saving.unwrap().minor_units(),
+            90_909_091
+        );
+        assert_eq!(progress.on_track, Some(true));
+    }
+
+    #[test]
+    fn completed_goal_reports_date_target_was_reached() {
+        let db = fixture();
+        let goals = service(&db);
+        goals
+            .create_goal(&goal(1_000_000.0, Some(date(2024, 3, 31)), None))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(600_000.0, date(2024, 1, 10), None))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(500_000.0, date(2024, 2, 10), None))
+            .unwrap();
+
+        let progress = goals.get_goal_progress(Some(date(2024, 5, 1))).unwrap();
+        let progress = &progress[0];
+        assert_eq!(progress.remaining.minor_units(), 0);
+        assert_eq!(progress.projected_completion_date, Some(date(2024, 2, 10)));
+        assert_eq!(progress.required_monthly_saving.unwrap().minor_units(), 0);
+        assert_eq!(progress.on_track, Some(true));
+
+        let err = goals
+            .add_contribution(1, &contribution(-2_000_000.0, date(2024, 5, 1), None))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("amount"));
+    }
+
+    #[test]
+    fn linked_contribution_transfers_money_and_delete_restores_it() {
+        let db = fixture();
+        let goals = service(&db);
+        goals
+            .create_goal(&goal(10_000_000.0, None, Some(2)))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(1_500_000.0, date(2024, 5, 1), Some(1)))
+            .unwrap();
+        goals
+            .add_contribution(1, &contribution(-500_000.0, date(2024, 5, 2), Some(1)))
+            .unwrap();
+        assert_eq!(balances(&db), vec![400_000_000, 100_000_000, 0]);
+
+        let contributions = goals.get_contributions(1).unwrap();
+        assert!(contributions.iter().all(|c| c.transfer_id.is_some()));
+
+        // Transfer setoran tidak bisa dihapus sendiri, karena setorannya akan tetap terhitung.
+        let transfers = SqliteTransferRepository::new(&db);
+        let err = transfers
+            .delete(contributions[0].transfer_id.unwrap())
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        assert_eq!(balances(&db), vec![400_000_000, 100_000_000, 0]);
+
+        goals.delete_contribution(contributions[1].id).unwrap();
+        goals.delete_contribution(contributions[0].id).unwrap();
+        assert_eq!(balances(&db), vec![500_000_000, 0, 0]);
+        assert_eq!(goals.get_goal_progress(None).unwrap()[0].on_track, None);
+    }
+
+    #[test]
+    fn rejects_invalid_goals_and_transfer_sources() {
+        let db = fixture();
+        let goals = service(&db);
+
+        let err = goals.create_goal(&goal(0.0, None, None)).unwrap_err();
+        assert_eq!(err.field(), Some("target_amount"));
+
+        // Mata uang mengikuti akun yang ditautkan.
+        goals.create_goal(&goal(100.0, None, Some(3))).unwrap();
+        assert_eq!(goals.get_goal_by_id(1).unwrap().currency.code(), "USD");
+        let err = goals
+            .add_contribution(1, &contribution(10.0, date(2024, 5, 1), Some(1)))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("from_account_id"));
+
+        goals.create_goal(&goal(100.0, None, None)).unwrap();
+        let err = goals
+            .add_contribution(2, &contribution(10.0, date(2024, 5, 1), Some(1)))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("from_account_id"));
+
+        goals
+            .add_contribution(1, &contribution(10.0, date(2024, 5, 1), None))
+            .unwrap();
+        let err = goals.update_goal(1, &goal(100.0, None, None)).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+}
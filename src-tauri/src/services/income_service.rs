@@ -0,0 +1,231 @@
+//! Modul ini berisi logika bisnis yang terkait dengan pemasukan.
+
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::income::Income;
+use crate::models::money::Money;
+use crate::models::transaction_query::{Page, TransactionQuery};
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::category_repository::CategoryRepository;
+use crate::repositories::income_repository::IncomeRepository;
+use chrono::NaiveDateTime;
+
+/// Service pemasukan. Validasi membutuhkan data kategori dan akun, sehingga service ini
+/// bergantung pada tiga repository.
+pub struct IncomeService<R, C, A> {
+    incomes: R,
+    categories: C,
+    accounts: A,
+}
+
+impl<R: IncomeRepository, C: CategoryRepository, A: AccountRepository> IncomeService<R, C, A> {
+    pub fn new(incomes: R, categories: C, accounts: A) -> Self {
+        IncomeService {
+            incomes,
+            categories,
+            accounts,
+        }
+    }
+
+    /// Memvalidasi input pemasukan dan mengubah jumlahnya ke mata uang akun.
+    fn validate(
+        &self,
+        description: &str,
+        amount: f64,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<Money, AppError> {
+        if description.trim().is_empty() {
+            return Err(AppError::validation(
+                "description",
+                "Deskripsi pemasukan tidak boleh kosong.",
+            ));
+        }
+
+        let category = self.categories.find_by_id(category_id)?;
+        if category.category_type != CategoryType::Income {
+            return Err(AppError::validation(
+                "category_id",
+                format!("Kategori '{}' bukan kategori pemasukan.", category.name),
+            ));
+        }
+
+        let account = self.accounts.find_by_id(account_id)?;
+        let amount = Money::from_major(amount, account.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah pemasukan harus lebih besar dari nol.",
+            ));
+        }
+
+        Ok(amount)
+    }
+
+    /// Mencatat pemasukan baru dan memperbarui saldo akunnya.
+    pub fn create_income(
+        &self,
+        description: &str,
+        amount: f64,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let amount = self.validate(description, amount, category_id, account_id)?;
+        self.incomes
+            .create(description.trim(), amount, date, category_id, account_id)
+    }
+
+    /// Mengambil semua pemasukan.
+    pub fn get_all_incomes(&self) -> Result<Vec<Income>, AppError> {
+        self.incomes.find_all()
+    }
+
+    /// Mengambil satu pemasukan berdasarkan ID-nya.
+    pub fn get_income_by_id(&self, id: i64) -> Result<Income, AppError> {
+        self.incomes.find_by_id(id)
+    }
+
+    /// Mencari pemasukan dengan filter, urutan, dan paginasi yang dijalankan di SQL.
+    pub fn query_incomes(&self, query: &TransactionQuery) -> Result<Page<Income>, AppError> {
+        query.validate()?;
+        self.incomes.find_page(query)
+    }
+
+    /// Memperbarui pemasukan yang ada.
+    pub fn update_income(
+        &self,
+        id: i64,
+        description: &str,
+        amount: f64,
+        date: NaiveDateTime,
+        category_id: i64,
+        account_id: i64,
+    ) -> Result<(), AppError> {
+        let amount = self.validate(description, amount, category_id, account_id)?;
+        self.incomes.update(
+            id,
+            description.trim(),
+            amount,
+            date,
+            category_id,
+            account_id,
+        )
+    }
+
+    /// Menghapus pemasukan berdasarkan ID.
+    pub fn delete_income(&self, id: i64) -> Result<(), AppError> {
+        self.incomes.delete(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::repositories::income_repository::SqliteIncomeRepository;
+    use crate::services::test_support::{add_account, add_category, at, balance, idr, usd};
+
+    fn service(
+        db: &Database,
+    ) -> IncomeService<
+        SqliteIncomeRepository<'_>,
+        SqliteCategoryRepository<'_>,
+        SqliteAccountRepository<'_>,
+    > {
+        IncomeService::new(
+            SqliteIncomeRepository::new(db),
+            SqliteCategoryRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Kategori 1 Gaji (Income) dan 2 Makan (Expense); akun 1 Bank (IDR 100.000) dan
+    /// akun 2 Dolar (USD 0).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_category(&db, "Makan", CategoryType::Expense);
+        add_account(&db, "Bank", idr(100_000.0));
+        add_account(&db, "Dolar", Money::zero(usd()));
+        db
+    }
+
+    #[test]
+    fn create_update_delete_keep_balance_in_sync() {
+        let db = fixture();
+        let incomes = service(&db);
+
+        incomes
+            .create_income("  Gaji Mei ", 5_000_000.0, at(2024, 5, 25), 1, 1)
+            .unwrap();
+        assert_eq!(balance(&db, 1), 510_000_000);
+        let saved = incomes.get_income_by_id(1).unwrap();
+        assert_eq!(saved.description, "Gaji Mei");
+        assert_eq!(saved.account_id, Some(1));
+
+        incomes
+            .update_income(1, "Gaji Mei", 4_000_000.0, at(2024, 5, 25), 1, 1)
+            .unwrap();
+        assert_eq!(balance(&db, 1), 410_000_000);
+
+        incomes.delete_income(1).unwrap();
+        assert_eq!(balance(&db, 1), 10_000_000);
+        assert!(incomes.get_all_incomes().unwrap().is_empty());
+
+        let err = incomes.delete_income(1).unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+        assert!(err.to_string().starts_with("Pemasukan dengan ID 1"));
+    }
+
+    #[test]
+    fn rejects_empty_description_expense_category_and_non_positive_amount() {
+        let db = fixture();
+        let incomes = service(&db);
+
+        let err = incomes
+            .create_income(" ", 10.0, at(2024, 5, 1), 1, 1)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("description"));
+
+        let err = incomes
+            .create_income("Makan", 10.0, at(2024, 5, 1), 2, 1)
+            .unwrap_err();
+        assert_eq!(err.field(), Some("category_id"));
+
+        for amount in [0.0, -10.0, 0.001] {
+            let err = incomes
+                .create_income("Bonus", amount, at(2024, 5, 1), 1, 1)
+                .unwrap_err();
+            assert_eq!(err.field(), Some("amount"), "jumlah {}", amount);
+        }
+
+        assert_eq!(balance(&db, 1), 10_000_000);
+        assert!(incomes.get_all_incomes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn amount_follows_account_currency() {
+        let db = fixture();
+        let incomes = service(&db);
+
+        // Jumlah dibaca dalam mata uang akun tujuan, bukan IDR bawaan.
+        incomes
+            .create_income("Freelance", 12.345, at(2024, 5, 1), 1, 2)
+            .unwrap();
+        let saved = incomes.get_income_by_id(1).unwrap();
+        assert_eq!(saved.amount, Money::new(1_235, usd()));
+        assert_eq!(balance(&db, 2), 1_235);
+
+        // Memindahkan pemasukan ke akun IDR mengubah mata uangnya dan saldo kedua akun.
+        incomes
+            .update_income(1, "Freelance", 200_000.0, at(2024, 5, 1), 1, 1)
+            .unwrap();
+        let saved = incomes.get_income_by_id(1).unwrap();
+        assert_eq!(saved.amount, idr(200_000.0));
+        assert_eq!(balance(&db, 1), 30_000_000);
+        assert_eq!(balance(&db, 2), 0);
+    }
+}
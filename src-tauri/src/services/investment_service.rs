@@ -0,0 +1,862 @@
+//! Modul ini berisi logika bisnis untuk akun investasi.
+//! Kepemilikan dihitung dari riwayat transaksi dengan metode biaya rata-rata, dinilai dengan
+//! harga terakhir yang diketahui, dan kinerjanya diukur dengan time-weighted return.
+
+use crate::error::AppError;
+use crate::models::account::{Account, AccountType};
+use crate::models::investment::{
+    Holding, Instrument, InstrumentInput, InstrumentPrice, InvestmentTransaction,
+    InvestmentTransactionInput, InvestmentTransactionKind, NewInvestmentTransaction,
+    PortfolioValuation, PriceSource,
+};
+use crate::models::money::Money;
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::investment_repository::InvestmentRepository;
+use chrono::{Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+/// Toleransi pembulatan unit (unit disimpan sebagai pecahan desimal).
+const UNIT_EPSILON: f64 = 1e-9;
+
+/// Titik harga per instrumen, urut tanggal: harga tercatat dan harga tersirat dari transaksi.
+type PricePoints = HashMap<i64, Vec<(NaiveDate, f64)>>;
+
+/// Service investasi, bergantung pada `InvestmentRepository` dan `AccountRepository`.
+pub struct InvestmentService<I, A> {
+    investments: I,
+    accounts: A,
+}
+
+impl<I: InvestmentRepository, A: AccountRepository> InvestmentService<I, A> {
+    pub fn new(investments: I, accounts: A) -> Self {
+        InvestmentService {
+            investments,
+            accounts,
+        }
+    }
+
+    /// Membuat instrumen baru. Simbol disimpan dalam huruf besar.
+    pub fn create_instrument(&self, input: &InstrumentInput) -> Result<(), AppError> {
+        let (symbol, name) = validate_instrument(input)?;
+        self.investments
+            .create_instrument(&symbol, name, input.kind, input.currency)
+    }
+
+    /// Mengambil semua instrumen.
+    pub fn get_all_instruments(&self) -> Result<Vec<Instrument>, AppError> {
+        self.investments.find_all_instruments()
+    }
+
+    /// Mengambil satu instrumen berdasarkan ID-nya.
+    pub fn get_instrument_by_id(&self, id: i64) -> Result<Instrument, AppError> {
+        self.investments.find_instrument_by_id(id)
+    }
+
+    /// Memperbarui instrumen. Mata uang tidak bisa diubah setelah ada transaksi.
+    pub fn update_instrument(&self, id: i64, input: &InstrumentInput) -> Result<(), AppError> {
+        let current = self.investments.find_instrument_by_id(id)?;
+        let (symbol, name) = validate_instrument(input)?;
+        if input.currency != current.currency && self.has_transactions(id)? {
+            return Err(AppError::Conflict(
+                "Mata uang instrumen yang sudah memiliki transaksi tidak dapat diubah.".to_string(),
+            ));
+        }
+        self.investments
+            .update_instrument(id, &symbol, name, input.kind, input.currency)
+    }
+
+    /// Menghapus instrumen beserta riwayat harganya. Instrumen yang masih memiliki transaksi
+    /// tidak boleh dihapus.
+    pub fn delete_instrument(&self, id: i64) -> Result<(), AppError> {
+        self.investments.find_instrument_by_id(id)?;
+        if self.has_transactions(id)? {
+            return Err(AppError::Conflict(
+                "Instrumen masih memiliki transaksi dan tidak dapat dihapus.".to_string(),
+            ));
+        }
+        self.investments.delete_instrument(id)
+    }
+
+    fn has_transactions(&self, instrument_id: i64) -> Result<bool, AppError> {
+        Ok(self
+            .investments
+            .find_transactions(None)?
+            .iter()
+            .any(|t| t.instrument_id == instrument_id))
+    }
+
+    /// Mencatat transaksi beli, jual, atau dividen. Akun harus berjenis investasi dan bermata
+    /// uang sama dengan instrumen; penjualan tidak boleh melebihi unit yang dimiliki.
+    pub fn add_transaction(&self, input: &InvestmentTransactionInput) -> Result<(), AppError> {
+        let account = self.investment_account(input.account_id)?;
+        let instrument = self
+            .investments
+            .find_instrument_by_id(input.instrument_id)?;
+        if instrument.currency != account.currency {
+            return Err(AppError::validation(
+                "instrument_id",
+                "Mata uang instrumen harus sama dengan mata uang akun.",
+            ));
+        }
+
+        let units = match input.kind {
+            InvestmentTransactionKind::Buy | InvestmentTransactionKind::Sell => {
+                if !input.units.is_finite() || input.units <= 0.0 {
+                    return Err(AppError::validation(
+                        "units",
+                        "Jumlah unit harus lebih besar dari nol.",
+                    ));
+                }
+                input.units
+            }
+            InvestmentTransactionKind::Dividend => 0.0,
+        };
+        let amount = Money::from_major(input.amount, account.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Nilai transaksi harus lebih besar dari nol.",
+            ));
+        }
+        let fee = Money::from_major_as(input.fee.unwrap_or(0.0), account.currency, "fee")?;
+        if fee.is_negative() {
+            return Err(AppError::validation("fee", "Biaya tidak boleh negatif."));
+        }
+
+        if input.kind == InvestmentTransactionKind::Sell {
+            // Transaksi baru diurutkan setelah transaksi lain pada waktu yang sama, sama
+            // seperti urutan ID saat dibaca kembali.
+            let mut flows = self.unit_flows(account.id, instrument.id, None)?;
+            let position = flows.partition_point(|(date, _)| *date <= input.date);
+            flows.insert(position, (input.date, -units));
+            if !units_stay_non_negative(&flows) {
+                return Err(AppError::validation(
+                    "units",
+                    "Jumlah unit yang dijual melebihi unit yang dimiliki.",
+                ));
+            }
+        }
+
+        let note = input
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        self.investments.add_transaction(&NewInvestmentTransaction {
+            account_id: account.id,
+            instrument_id: instrument.id,
+            kind: input.kind,
+            units,
+            amount,
+            fee,
+            date: input.date,
+            note,
+        })
+    }
+
+    /// Mengambil transaksi investasi, seluruhnya atau hanya milik satu akun.
+    pub fn get_transactions(
+        &self,
+        account_id: Option<i64>,
+    ) -> Result<Vec<InvestmentTransaction>, AppError> {
+        self.investments.find_transactions(account_id)
+    }
+
+    /// Menghapus transaksi investasi dan mengembalikan kas akunnya. Pembelian yang unitnya
+    /// sudah terjual tidak boleh dihapus.
+    pub fn delete_transaction(&self, id: i64) -> Result<(), AppError> {
+        let transaction = self.investments.find_transaction_by_id(id)?;
+        if transaction.kind == InvestmentTransactionKind::Buy {
+            let flows =
+                self.unit_flows(transaction.account_id, transaction.instrument_id, Some(id))?;
+            if !units_stay_non_negative(&flows) {
+                return Err(AppError::Conflict(
+                    "Transaksi beli tidak dapat dihapus karena unitnya sudah dijual.".to_string(),
+                ));
+            }
+        }
+        self.investments.delete_transaction(id)
+    }
+
+    /// Perubahan unit (positif untuk beli, negatif untuk jual) satu instrumen di satu akun,
+    /// urut waktu, tanpa transaksi `exclude_id`.
+    fn unit_flows(
+        &self,
+        account_id: i64,
+        instrument_id: i64,
+        exclude_id: Option<i64>,
+    ) -> Result<Vec<(chrono::NaiveDateTime, f64)>, AppError> {
+        Ok(self
+            .investments
+            .find_transactions(Some(account_id))?
+            .into_iter()
+            .filter(|t| t.instrument_id == instrument_id && Some(t.id) != exclude_id)
+            .map(|t| (t.date, unit_change(&t)))
+            .collect())
+    }
+
+    fn investment_account(&self, account_id: i64) -> Result<Account, AppError> {
+        let account = self.accounts.find_by_id(account_id)?;
+        if account.account_type != AccountType::Investment {
+            return Err(AppError::validation(
+                "account_id",
+                "Akun yang dipilih bukan akun investasi.",
+            ));
+        }
+        Ok(account)
+    }
+
+    /// Menyimpan harga manual satu instrumen pada tanggal tertentu.
+    pub fn add_price(
+        &self,
+        instrument_id: i64,
+        date: NaiveDate,
+        price: f64,
+    ) -> Result<(), AppError> {
+        validate_price(price)?;
+        self.investments.find_instrument_by_id(instrument_id)?;
+        self.investments
+            .upsert_price(instrument_id, date, price, PriceSource::Manual)
+    }
+
+    /// Mengambil riwayat harga sebuah instrumen.
+    pub fn get_prices(&self, instrument_id: i64) -> Result<Vec<InstrumentPrice>, AppError> {
+        self.investments.find_prices(instrument_id)
+    }
+
+    /// Menghapus harga berdasarkan ID.
+    pub fn delete_price(&self, id: i64) -> Result<(), AppError> {
+        self.investments.delete_price(id)
+    }
+
+    /// Mengimpor harga dari file CSV dengan kolom `date,symbol,price` (tanggal berformat
+    /// YYYY-MM-DD, simbol harus sudah terdaftar). Baris header, baris kosong, dan baris yang
+    /// diawali `#` dilewati. Semua baris disimpan dalam satu transaksi; satu baris rusak
+    /// membatalkan impor. Mengembalikan jumlah harga yang diimpor.
+    pub fn import_prices_csv(&self, path: &str) -> Result<usize, AppError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AppError::Io(format!("Gagal membaca file CSV: {}", e)))?;
+        let symbols: HashMap<String, i64> = self
+            .investments
+            .find_all_instruments()?
+            .into_iter()
+            .map(|i| (i.symbol.to_uppercase(), i.id))
+            .collect();
+        let prices = parse_prices_csv(&content, &symbols)?;
+        self.investments.upsert_prices(&prices, PriceSource::Csv)?;
+        Ok(prices.len())
+    }
+
+    /// Kepemilikan per akun dan instrumen per `date` (default hari ini), seluruh akun atau
+    /// hanya `account_id`.
+    pub fn get_holdings(
+        &self,
+        account_id: Option<i64>,
+        date: Option<NaiveDate>,
+    ) -> Result<Vec<Holding>, AppError> {
+        let date = date.unwrap_or_else(|| Local::now().date_naive());
+        holdings(&self.investments, account_id, date)
+    }
+
+    /// Nilai portofolio satu akun investasi per `date_to` (default hari ini) beserta
+    /// time-weighted return selama `date_from` (default transaksi pertama) sampai `date_to`.
+    pub fn get_portfolio_valuation(
+        &self,
+        account_id: i64,
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+    ) -> Result<PortfolioValuation, AppError> {
+        let account = self.investment_account(account_id)?;
+        let transactions = self.investments.find_transactions(Some(account_id))?;
+        let date_to = date_to.unwrap_or_else(|| Local::now().date_naive());
+        let date_from = date_from
+            .or_else(|| transactions.first().map(|t| t.date.date()))
+            .unwrap_or(date_to);
+        if date_from > date_to {
+            return Err(AppError::validation(
+                "date_from",
+                "Tanggal awal tidak boleh setelah tanggal akhir.",
+            ));
+        }
+
+        let prices = price_points(&self.investments, &transactions)?;
+        let holdings = build_holdings(&self.investments, &transactions, &prices, date_to)?;
+
+        let currency = account.currency;
+        let mut cost_basis = Money::zero(currency);
+        let mut market_value = Money::zero(currency);
+        let mut realized_gain = Money::zero(currency);
+        let mut dividends = Money::zero(currency);
+        for holding in &holdings {
+            cost_basis = cost_basis.checked_add(holding.cost_basis)?;
+            market_value =
+                market_value.checked_add(holding.market_value.unwrap_or(holding.cost_basis))?;
+            realized_gain = realized_gain.checked_add(holding.realized_gain)?;
+            dividends = dividends.checked_add(holding.dividends)?;
+        }
+
+        Ok(PortfolioValuation {
+            account_id,
+            currency,
+            date_from,
+            date_to,
+            time_weighted_return: time_weighted_return(&transactions, &prices, date_from, date_to),
+            holdings,
+            cash_balance: account.balance,
+            cost_basis,
+            market_value,
+            unrealized_gain: market_value.checked_sub(cost_basis)?,
+            realized_gain,
+            dividends,
+        })
+    }
+}
+
+/// Kepemilikan per akun dan instrumen per `date`, seluruh akun atau hanya `account_id`.
+/// Dipakai juga oleh perhitungan kekayaan bersih.
+pub(crate) fn holdings(
+    investments: &impl InvestmentRepository,
+    account_id: Option<i64>,
+    date: NaiveDate,
+) -> Result<Vec<Holding>, AppError> {
+    let transactions = investments.find_transactions(account_id)?;
+    let prices = price_points(investments, &transactions)?;
+    build_holdings(investments, &transactions, &prices, date)
+}
+
+/// Menggabungkan harga tercatat dan harga tersirat transaksi (`amount / units`) untuk
+/// setiap instrumen pada `transactions`. Pada tanggal yang sama harga tercatat diutamakan.
+fn price_points(
+    investments: &impl InvestmentRepository,
+    transactions: &[InvestmentTransaction],
+) -> Result<PricePoints, AppError> {
+    let mut points: PricePoints = HashMap::new();
+    for t in transactions {
+        if t.kind != InvestmentTransactionKind::Dividend {
+            points
+                .entry(t.instrument_id)
+                .or_default()
+                .push((t.date.date(), t.amount.to_major() / t.units));
+        }
+    }
+    for (instrument_id, list) in points.iter_mut() {
+        for price in investments.find_prices(*instrument_id)? {
+            list.push((price.date, price.price));
+        }
+        // Pengurutan stabil: harga tercatat tetap berada setelah harga tersirat.
+        list.sort_by_key(|(date, _)| *date);
+    }
+    Ok(points)
+}
+
+fn build_holdings(
+    investments: &impl InvestmentRepository,
+    transactions: &[InvestmentTransaction],
+    prices: &PricePoints,
+    date: NaiveDate,
+) -> Result<Vec<Holding>, AppError> {
+    let instruments: HashMap<i64, Instrument> = investments
+        .find_all_instruments()?
+        .into_iter()
+        .map(|i| (i.id, i))
+        .collect();
+
+    let mut positions: BTreeMap<(i64, i64), Position> = BTreeMap::new();
+    for t in transactions.iter().filter(|t| t.date.date() <= date) {
+        positions
+            .entry((t.account_id, t.instrument_id))
+            .or_insert_with(|| Position::new(t.currency))
+            .apply(t)?;
+    }
+
+    let mut holdings = Vec::with_capacity(positions.len());
+    for ((account_id, instrument_id), position) in positions {
+        let instrument = instruments
+            .get(&instrument_id)
+            .cloned()
+            .ok_or_else(|| AppError::not_found("Instrumen", instrument_id))?;
+        let latest = prices
+            .get(&instrument_id)
+            .and_then(|points| price_on(points, date));
+        let market_value = latest
+            .map(|(_, price)| Money::from_major(position.units * price, instrument.currency))
+            .transpose()?;
+        let unrealized_gain = market_value
+            .map(|value| value.checked_sub(position.cost_basis))
+            .transpose()?;
+
+        holdings.push(Holding {
+            account_id,
+            instrument,
+            units: position.units,
+            cost_basis: position.cost_basis,
+            price: latest.map(|(_, price)| price),
+            price_date: latest.map(|(date, _)| date),
+            market_value,
+            unrealized_gain,
+            realized_gain: position.realized_gain,
+            dividends: position.dividends,
+        });
+    }
+    holdings.sort_by(|a, b| {
+        (a.account_id, &a.instrument.symbol).cmp(&(b.account_id, &b.instrument.symbol))
+    });
+    Ok(holdings)
+}
+
+/// Posisi berjalan satu instrumen di satu akun dengan metode biaya rata-rata.
+struct Position {
+    units: f64,
+    cost_basis: Money,
+    realized_gain: Money,
+    dividends: Money,
+}
+
+impl Position {
+    fn new(currency: crate::models::money::Currency) -> Self {
+        Position {
+            units: 0.0,
+            cost_basis: Money::zero(currency),
+            realized_gain: Money::zero(currency),
+            dividends: Money::zero(currency),
+        }
+    }
+
+    /// Pembelian menambah biaya sebesar `amount + fee`; penjualan mengurangi biaya secara
+    /// proporsional dan mencatat laba `amount - fee - biaya unit terjual`.
+    fn apply(&mut self, t: &InvestmentTransaction) -> Result<(), AppError> {
+        match t.kind {
+            InvestmentTransactionKind::Buy => {
+                self.units += t.units;
+                self.cost_basis = self.cost_basis.checked_add(t.amount.checked_add(t.fee)?)?;
+            }
+            InvestmentTransactionKind::Sell => {
+                let sold_cost = if t.units >= self.units - UNIT_EPSILON {
+                    self.units = 0.0;
+                    self.cost_basis
+                } else {
+                    let fraction = t.units / self.units;
+                    self.units -= t.units;
+                    Money::new(
+                        (self.cost_basis.minor_units() as f64 * fraction).round() as i64,
+                        self.cost_basis.currency(),
+                    )
+                };
+                self.cost_basis = self.cost_basis.checked_sub(sold_cost)?;
+                let proceeds = t.amount.checked_sub(t.fee)?;
+                self.realized_gain = self
+                    .realized_gain
+                    .checked_add(proceeds.checked_sub(sold_cost)?)?;
+            }
+            InvestmentTransactionKind::Dividend => {
+                self.dividends = self.dividends.checked_add(t.amount.checked_sub(t.fee)?)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unit_change(t: &InvestmentTransaction) -> f64 {
+    match t.kind {
+        InvestmentTransactionKind::Buy => t.units,
+        InvestmentTransactionKind::Sell => -t.units,
+        InvestmentTransactionKind::Dividend => 0.0,
+    }
+}
+
+fn units_stay_non_negative(flows: &[(chrono::NaiveDateTime, f64)]) -> bool {
+    let mut units = 0.0;
+    flows.iter().all(|(_, change)| {
+        units += change;
+        units >= -UNIT_EPSILON
+    })
+}
+
+/// Harga terakhir pada atau sebelum `date`.
+fn price_on(points: &[(NaiveDate, f64)], date: NaiveDate) -> Option<(NaiveDate, f64)> {
+    let end = points.partition_point(|(d, _)| *d <= date);
+    end.checked_sub(1).map(|i| points[i])
+}
+
+/// Nilai pasar (satuan mayor) dari unit hasil transaksi yang tanggalnya lolos `include`,
+/// dinilai dengan harga pada `price_date`.
+fn units_value(
+    transactions: &[InvestmentTransaction],
+    prices: &PricePoints,
+    include: impl Fn(NaiveDate) -> bool,
+    price_date: NaiveDate,
+) -> f64 {
+    let mut units: HashMap<i64, f64> = HashMap::new();
+    for t in transactions.iter().filter(|t| include(t.date.date())) {
+        *units.entry(t.instrument_id).or_default() += unit_change(t);
+    }
+    units
+        .into_iter()
+        .filter(|(_, units)| *units > UNIT_EPSILON)
+        .map(|(instrument_id, units)| {
+            let price = prices
+                .get(&instrument_id)
+                .and_then(|points| price_on(points, price_date))
+                .map_or(0.0, |(_, price)| price);
+            units * price
+        })
+        .sum()
+}
+
+/// Time-weighted return selama `from` sampai `to`. Periode dipecah pada setiap tanggal beli
+/// atau jual sehingga arus kas masuk/keluar tidak memengaruhi hasil; dividen dihitung sebagai
+/// imbal hasil periodenya. Periode dengan nilai awal nol dilewati, dan hasilnya kosong bila
+/// tidak ada kepemilikan selama rentang tersebut.
+fn time_weighted_return(
+    transactions: &[InvestmentTransaction],
+    prices: &PricePoints,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Option<f64> {
+    let mut boundaries: Vec<NaiveDate> = transactions
+        .iter()
+        .filter(|t| t.kind != InvestmentTransactionKind::Dividend)
+        .map(|t| t.date.date())
+        .filter(|date| *date >= from && *date <= to)
+        .collect();
+    boundaries.push(to);
+    boundaries.dedup();
+
+    let mut start_value = units_value(
+        transactions,
+        prices,
+        |date| date < from,
+        from.pred_opt().unwrap_or(from),
+    );
+    let mut period_start = from;
+    let mut growth = 1.0;
+    let mut measured = false;
+
+    for boundary in boundaries {
+        // Nilai akhir periode dihitung sebelum arus kas pada tanggal batas.
+        let end_value = units_value(transactions, prices, |date| date < boundary, boundary);
+        let dividends: f64 = transactions
+            .iter()
+            .filter(|t| {
+                t.kind == InvestmentTransactionKind::Dividend
+                    && t.date.date() >= period_start
+                    && t.date.date() <= boundary
+            })
+            .map(|t| t.amount.to_major() - t.fee.to_major())
+            .sum();
+        if start_value > 0.0 {
+            growth *= (end_value + dividends) / start_value;
+            measured = true;
+        }
+
+        start_value = units_value(transactions, prices, |date| date <= boundary, boundary);
+        period_start = boundary.succ_opt().unwrap_or(boundary);
+    }
+
+    measured.then_some(growth - 1.0)
+}
+
+fn validate_instrument(input: &InstrumentInput) -> Result<(String, &str), AppError> {
+    let symbol = input.symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err(AppError::validation(
+            "symbol",
+            "Simbol instrumen tidak boleh kosong.",
+        ));
+    }
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation(
+            "name",
+            "Nama instrumen tidak boleh kosong.",
+        ));
+    }
+    Ok((symbol, name))
+}
+
+fn validate_price(price: f64) -> Result<(), AppError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(AppError::validation(
+            "price",
+            "Harga harus lebih besar dari nol.",
+        ));
+    }
+    Ok(())
+}
+
+fn parse_prices_csv(
+    content: &str,
+    symbols: &HashMap<String, i64>,
+) -> Result<Vec<(i64, NaiveDate, f64)>, AppError> {
+    let mut prices = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if index == 0 && fields[0].eq_ignore_ascii_case("date") {
+            continue;
+        }
+        if fields.len() != 3 {
+            return Err(csv_error(line_no, "harus berisi 3 kolom."));
+        }
+
+        let date = NaiveDate::parse_from_str(fields[0], "%Y-%m-%d")
+            .map_err(|_| csv_error(line_no, format!("tanggal tidak valid '{}'.", fields[0])))?;
+        let instrument_id = *symbols.get(&fields[1].to_uppercase()).ok_or_else(|| {
+            csv_error(
+                line_no,
+                format!("instrumen '{}' tidak ditemukan.", fields[1]),
+            )
+        })?;
+        let price: f64 = fields[2]
+            .parse()
+            .map_err(|_| csv_error(line_no, format!("harga tidak valid '{}'.", fields[2])))?;
+        validate_price(price).map_err(|e| csv_error(line_no, e))?;
+
+        prices.push((instrument_id, date, price));
+    }
+
+    Ok(prices)
+}
+
+/// Error validasi untuk satu baris CSV, dengan nomor baris di awal pesan.
+fn csv_error(line_no: usize, message: impl std::fmt::Display) -> AppError {
+    AppError::validation("path", format!("Baris {}: {}", line_no, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::investment::InstrumentKind;
+    use crate::models::money::Currency;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::investment_repository::SqliteInvestmentRepository;
+    use crate::services::test_support::{add_account, add_investment_account, at, date, idr};
+
+    fn service(
+        db: &Database,
+    ) -> InvestmentService<SqliteInvestmentRepository<'_>, SqliteAccountRepository<'_>> {
+        InvestmentService::new(
+            SqliteInvestmentRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Akun kas 1, akun investasi 2 (kas 1 juta), dan instrumen BBCA.
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(0.0));
+        add_investment_account(&db, "RDN", idr(1_000_000.0));
+        service(&db)
+            .create_instrument(&InstrumentInput {
+                symbol: " bbca ".to_string(),
+                name: "Bank Central Asia".to_string(),
+                kind: InstrumentKind::Stock,
+                currency: Currency::IDR,
+            })
+            .unwrap();
+        db
+    }
+
+    fn trade(
+        account_id: i64,
+        kind: InvestmentTransactionKind,
+        units: f64,
+        amount: f64,
+        fee: f64,
+        d: u32,
+    ) -> InvestmentTransactionInput {
+        InvestmentTransactionInput {
+            account_id,
+            instrument_id: 1,
+            kind,
+            units,
+            amount,
+            fee: Some(fee),
+            date: at(2024, 3, d),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn transactions_settle_cash_and_guard_units() {
+        let db = fixture();
+        let investments = service(&db);
+        let accounts = SqliteAccountRepository::new(&db);
+
+        let err = investments
+            .add_transaction(&trade(
+                1,
+                InvestmentTransactionKind::Buy,
+                10.0,
+                1_000.0,
+                0.0,
+                1,
+            ))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("account_id"));
+
+        investments
+            .add_transaction(&trade(
+                2,
+                InvestmentTransactionKind::Buy,
+                100.0,
+                100_000.0,
+                500.0,
+                1,
+            ))
+            .unwrap();
+        assert_eq!(accounts.find_by_id(2).unwrap().balance, idr(899_500.0));
+
+        let err = investments
+            .add_transaction(&trade(
+                2,
+                InvestmentTransactionKind::Sell,
+                150.0,
+                150_000.0,
+                0.0,
+                5,
+            ))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("units"));
+
+        // Menjual sebelum tanggal pembelian juga ditolak.
+        let mut early = trade(2, InvestmentTransactionKind::Sell, 10.0, 10_000.0, 0.0, 1);
+        early.date = date(2024, 3, 1).and_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(
+            investments.add_transaction(&early).unwrap_err().field(),
+            Some("units")
+        );
+
+        investments
+            .add_transaction(&trade(
+                2,
+                InvestmentTransactionKind::Sell,
+                40.0,
+                48_000.0,
+                100.0,
+                5,
+            ))
+            .unwrap();
+        assert_eq!(accounts.find_by_id(2).unwrap().balance, idr(947_400.0));
+
+        let err = investments.delete_transaction(1).unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        assert_eq!(
+            investments.delete_instrument(1).unwrap_err().code(),
+            "CONFLICT"
+        );
+
+        investments.delete_transaction(2).unwrap();
+        investments.delete_transaction(1).unwrap();
+        assert_eq!(accounts.find_by_id(2).unwrap().balance, idr(1_000_000.0));
+        accounts.recalculate_balance(2).unwrap();
+        assert_eq!(accounts.find_by_id(2).unwrap().balance, idr(1_000_000.0));
+    }
+
+    #[test]
+    fn holdings_use_average_cost_and_latest_price() {
+        let db = fixture();
+        let investments = service(&db);
+        let buy = InvestmentTransactionKind::Buy;
+        investments
+            .add_transaction(&trade(2, buy, 100.0, 100_000.0, 0.0, 1))
+            .unwrap();
+        investments
+            .add_transaction(&trade(2, buy, 100.0, 120_000.0, 0.0, 2))
+            .unwrap();
+        investments
+            .add_transaction(&trade(
+                2,
+                InvestmentTransactionKind::Sell,
+                50.0,
+                70_000.0,
+                1_000.0,
+                3,
+            ))
+            .unwrap();
+        investments
+            .add_transaction(&trade(
+                2,
+                InvestmentTransactionKind::Dividend,
+                0.0,
+                3_000.0,
+                300.0,
+                4,
+            ))
+            .unwrap();
+        investments
+            .add_price(1, date(2024, 3, 10), 1_500.0)
+            .unwrap();
+
+        // Sebelum harga tercatat, harga diambil dari transaksi terakhir (70.000 / 50).
+        let holdings = investments
+            .get_holdings(Some(2), Some(date(2024, 3, 5)))
+            .unwrap();
+        assert_eq!(holdings.len(), 1);
+        assert_eq!(holdings[0].instrument.symbol, "BBCA");
+        assert_eq!(holdings[0].units, 150.0);
+        assert_eq!(holdings[0].cost_basis, idr(165_000.0));
+        assert_eq!(holdings[0].price, Some(1_400.0));
+        assert_eq!(holdings[0].realized_gain, idr(14_000.0));
+        assert_eq!(holdings[0].dividends, idr(2_700.0));
+
+        let valuation = investments
+            .get_portfolio_valuation(2, None, Some(date(2024, 3, 10)))
+            .unwrap();
+        assert_eq!(valuation.date_from, date(2024, 3, 1));
+        assert_eq!(valuation.market_value, idr(225_000.0));
+        assert_eq!(valuation.unrealized_gain, idr(60_000.0));
+        assert_eq!(valuation.cash_balance, idr(851_700.0));
+    }
+
+    #[test]
+    fn time_weighted_return_ignores_cash_flows() {
+        let db = fixture();
+        let investments = service(&db);
+        let buy = InvestmentTransactionKind::Buy;
+        // Naik 10% lalu turun 10%, dengan setoran besar di tengah periode.
+        investments
+            .add_transaction(&trade(2, buy, 100.0, 100_000.0, 0.0, 1))
+            .unwrap();
+        investments
+            .add_price(1, date(2024, 3, 10), 1_100.0)
+            .unwrap();
+        investments
+            .add_transaction(&trade(2, buy, 500.0, 550_000.0, 0.0, 10))
+            .unwrap();
+        investments.add_price(1, date(2024, 3, 20), 990.0).unwrap();
+
+        let valuation = investments
+            .get_portfolio_valuation(2, Some(date(2024, 3, 1)), Some(date(2024, 3, 20)))
+            .unwrap();
+        let twr = valuation.time_weighted_return.unwrap();
+        assert!((twr - -0.01).abs() < 1e-9, "twr = {}", twr);
+
+        let before = investments
+            .get_portfolio_valuation(2, Some(date(2024, 3, 1)), Some(date(2024, 3, 1)))
+            .unwrap();
+        assert_eq!(before.time_weighted_return, None);
+    }
+
+    #[test]
+    fn parse_prices_csv_matches_symbols_case_insensitively() {
+        let symbols = HashMap::from([("BBCA".to_string(), 1)]);
+        let prices = parse_prices_csv(
+            "date,symbol,price\n# komentar\n2024-03-01,bbca,9875.5\n",
+            &symbols,
+        )
+        .unwrap();
+        assert_eq!(prices, vec![(1, date(2024, 3, 1), 9_875.5)]);
+
+        let err =
+            parse_prices_csv("2024-03-01,BBCA,9875\n2024-03-02,TLKM,3000\n", &symbols).unwrap_err();
+        assert!(err.to_string().starts_with("Baris 2:"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Modul ini berisi logika bisnis untuk buku besar gabungan: pemasukan, pengeluaran,
+//! dan transfer dalam satu aliran kronologis dengan saldo berjalan per akun.
+
+use crate::error::AppError;
+use crate::models::ledger::{LedgerEntry, LedgerQuery};
+use crate::models::transaction_query::Page;
+use crate::repositories::ledger_repository::LedgerRepository;
+
+/// Service buku besar, bergantung pada `LedgerRepository`.
+pub struct LedgerService<L> {
+    ledger: L,
+}
+
+impl<L: LedgerRepository> LedgerService<L> {
+    pub fn new(ledger: L) -> Self {
+        LedgerService { ledger }
+    }
+
+    /// Mengambil satu halaman buku besar sesuai filter.
+    pub fn get_ledger(&self, query: &LedgerQuery) -> Result<Page<LedgerEntry>, AppError> {
+        query.validate()?;
+        self.ledger.find_page(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::category::CategoryType;
+    use crate::models::ledger::LedgerEntryType;
+    use crate::models::transaction_query::SortDirection;
+    use crate::models::transfer::NewTransfer;
+    use crate::repositories::account_repository::{AccountRepository, SqliteAccountRepository};
+    use crate::repositories::expense_repository::{ExpenseRepository, SqliteExpenseRepository};
+    use crate::repositories::income_repository::{IncomeRepository, SqliteIncomeRepository};
+    use crate::repositories::ledger_repository::SqliteLedgerRepository;
+    use crate::repositories::transfer_repository::{SqliteTransferRepository, TransferRepository};
+    use crate::services::test_support::{add_account, add_category, at, idr};
+
+    /// Akun 1 (saldo awal Rp10,00) dan akun 2 (saldo awal 0) dengan pemasukan, pengeluaran,
+    /// dan satu transfer di antara keduanya.
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_category(&db, "Makan", CategoryType::Expense);
+        add_account(&db, "Bank", idr(10.0));
+        add_account(&db, "Dompet", idr(0.0));
+
+        SqliteIncomeRepository::new(&db)
+            .create("Gaji", idr(50.0), at(2024, 6, 1), 1, 1)
+            .unwrap();
+        SqliteExpenseRepository::new(&db)
+            .create("Makan", idr(7.0), at(2024, 6, 3), 2, 1)
+            .unwrap();
+        SqliteTransferRepository::new(&db)
+            .create(&NewTransfer {
+                description: Some("Tarik tunai"),
+                from_account_id: 1,
+                to_account_id: 2,
+                amount: idr(20.0),
+                fee: idr(0.5),
+                rate: 1.0,
+                received_amount: idr(20.0),
+                date: at(2024, 6, 2),
+            })
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn ledger_merges_sources_with_running_balance() {
+        let db = fixture();
+        let ledger = LedgerService::new(SqliteLedgerRepository::new(&db));
+
+        let page = ledger
+            .get_ledger(&LedgerQuery {
+                sort_direction: SortDirection::Asc,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(page.total_count, 4);
+        let summary: Vec<_> = page
+            .items
+            .iter()
+            .map(|e| {
+                (
+                    e.entry_type,
+                    e.account_id,
+                    e.amount.minor_units(),
+                    e.balance_after,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (LedgerEntryType::Income, Some(1), 5_000, Some(idr(60.0))),
+                (
+                    LedgerEntryType::TransferOut,
+                    Some(1),
+                    -2_050,
+                    Some(idr(39.5))
+                ),
+                (LedgerEntryType::TransferIn, Some(2), 2_000, Some(idr(20.0))),
+                (LedgerEntryType::Expense, Some(1), -700, Some(idr(32.5))),
+            ]
+        );
+        // Saldo berjalan terakhir sama dengan saldo akun yang tersimpan.
+        let bank = SqliteAccountRepository::new(&db).find_by_id(1).unwrap();
+        assert_eq!(bank.balance, idr(32.5));
+    }
+
+    #[test]
+    fn running_balance_survives_filtering_and_paging() {
+        let db = fixture();
+        let ledger = LedgerService::new(SqliteLedgerRepository::new(&db));
+
+        let page = ledger
+            .get_ledger(&LedgerQuery {
+                account_id: Some(1),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(page.total_count, 3);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].entry_type, LedgerEntryType::Expense);
+        assert_eq!(page.items[0].balance_after, Some(idr(32.5)));
+
+        let page = ledger
+            .get_ledger(&LedgerQuery {
+                account_id: Some(1),
+                date_from: Some(at(2024, 6, 2)),
+                date_to: Some(at(2024, 6, 2)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].description.as_deref(), Some("Tarik tunai"));
+        assert_eq!(page.items[0].balance_after, Some(idr(39.5)));
+    }
+}
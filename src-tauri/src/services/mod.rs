@@ -1 +1,18 @@
 pub mod account_service;
+pub mod budget_service;
+pub mod category_service;
+pub mod currency_service;
+pub mod debt_service;
+pub mod expense_service;
+pub mod goal_service;
+pub mod income_service;
+pub mod investment_service;
+pub mod ledger_service;
+pub mod net_worth_service;
+pub mod recurring_service;
+pub mod report_service;
+pub mod search_service;
+pub mod transfer_service;
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,487 @@
+//! Modul ini berisi logika bisnis untuk kekayaan bersih.
+//! Kekayaan bersih dihitung dari saldo akun kas, nilai akun investasi, serta sisa pokok
+//! utang dan piutang. Cuplikannya disimpan harian per mata uang, lalu riwayatnya dikonversi
+//! ke mata uang pelaporan memakai kurs pada tanggal setiap cuplikan.
+
+use crate::error::AppError;
+use crate::models::account::AccountType;
+use crate::models::debt::DebtDirection;
+use crate::models::money::{Currency, Money};
+use crate::models::net_worth::{NetWorth, NetWorthPoint, NetWorthSnapshot};
+use crate::models::report::ReportPeriod;
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::debt_repository::DebtRepository;
+use crate::repositories::exchange_rate_repository::ExchangeRateRepository;
+use crate::repositories::investment_repository::InvestmentRepository;
+use crate::repositories::net_worth_repository::NetWorthRepository;
+use crate::services::currency_service::CurrencyService;
+use crate::services::{debt_service, investment_service};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Jeda antar pencatatan cuplikan kekayaan bersih selama aplikasi berjalan. Cuplikan hari
+/// ini diperbarui setiap kali, sehingga cuplikan terakhir suatu hari mencerminkan akhir hari.
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Service kekayaan bersih, bergantung pada `NetWorthRepository`, `AccountRepository`,
+/// `InvestmentRepository`, `DebtRepository`, dan `CurrencyService` untuk konversi.
+pub struct NetWorthService<N, A, I, D, R> {
+    snapshots: N,
+    accounts: A,
+    investments: I,
+    debts: D,
+    currencies: CurrencyService<R, A>,
+}
+
+impl<N, A, I, D, R> NetWorthService<N, A, I, D, R>
+where
+    N: NetWorthRepository,
+    A: AccountRepository,
+    I: InvestmentRepository,
+    D: DebtRepository,
+    R: ExchangeRateRepository,
+{
+    pub fn new(
+        snapshots: N,
+        accounts: A,
+        investments: I,
+        debts: D,
+        currencies: CurrencyService<R, A>,
+    ) -> Self {
+        NetWorthService {
+            snapshots,
+            accounts,
+            investments,
+            debts,
+            currencies,
+        }
+    }
+
+    /// Kekayaan bersih saat ini dalam `reporting_currency`, per `date` (hari ini).
+    pub fn get_net_worth(
+        &self,
+        date: NaiveDate,
+        reporting_currency: &str,
+    ) -> Result<NetWorth, AppError> {
+        let target = Currency::parse(reporting_currency)?;
+        let snapshots = self.compute_snapshots(date)?;
+        self.combine(date, &snapshots, target)
+    }
+
+    /// Menghitung dan menyimpan cuplikan kekayaan bersih untuk `date`, menggantikan cuplikan
+    /// hari yang sama yang sudah ada.
+    pub fn record_snapshot(&self, date: NaiveDate) -> Result<(), AppError> {
+        let snapshots = self.compute_snapshots(date)?;
+        self.snapshots.replace_snapshots(date, &snapshots)
+    }
+
+    /// Riwayat kekayaan bersih dari cuplikan tersimpan, satu titik per `period` (cuplikan
+    /// terakhir dalam periode itu), dalam `reporting_currency`.
+    pub fn get_net_worth_history(
+        &self,
+        period: ReportPeriod,
+        date_from: Option<NaiveDate>,
+        date_to: Option<NaiveDate>,
+        reporting_currency: &str,
+    ) -> Result<Vec<NetWorthPoint>, AppError> {
+        if let (Some(from), Some(to)) = (date_from, date_to) {
+            if from > to {
+                return Err(AppError::validation(
+                    "date_to",
+                    "Tanggal akhir tidak boleh sebelum tanggal awal.",
+                ));
+            }
+        }
+        let target = Currency::parse(reporting_currency)?;
+
+        // Cuplikan urut tanggal; setiap tanggal baru dalam periode yang sama menggantikan
+        // tanggal sebelumnya.
+        let mut latest: Vec<(String, NaiveDate, Vec<NetWorthSnapshot>)> = Vec::new();
+        for snapshot in self.snapshots.find_snapshots(date_from, date_to)? {
+            let label = period_label(period, snapshot.date);
+            match latest.last_mut() {
+                Some((last_label, date, rows)) if *last_label == label => {
+                    if *date != snapshot.date {
+                        *date = snapshot.date;
+                        rows.clear();
+                    }
+                    rows.push(snapshot);
+                }
+                _ => latest.push((label, snapshot.date, vec![snapshot])),
+            }
+        }
+
+        latest
+            .into_iter()
+            .map(|(period, date, rows)| {
+                Ok(NetWorthPoint {
+                    period,
+                    net_worth: self.combine(date, &rows, target)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Rincian aset dan kewajiban per mata uang. Saldo akun selalu saldo saat ini, sedangkan
+    /// kepemilikan investasi dan sisa utang dihitung per `date`.
+    fn compute_snapshots(&self, date: NaiveDate) -> Result<Vec<NetWorthSnapshot>, AppError> {
+        let mut snapshots: HashMap<Currency, NetWorthSnapshot> = HashMap::new();
+
+        for account in self.accounts.find_all()? {
+            let snapshot = snapshots
+                .entry(account.currency)
+                .or_insert_with(|| NetWorthSnapshot::zero(date, account.currency));
+            match account.account_type {
+                AccountType::Investment => {
+                    snapshot.investments = snapshot.investments.checked_add(account.balance)?;
+                }
+                AccountType::Cash if account.balance.is_negative() => {
+                    snapshot.overdrafts =
+                        snapshot.overdrafts.checked_add(account.balance.negated())?;
+                }
+                AccountType::Cash => {
+                    snapshot.cash = snapshot.cash.checked_add(account.balance)?;
+                }
+            }
+        }
+
+        for holding in investment_service::holdings(&self.investments, None, date)? {
+            let value = holding.market_value.unwrap_or(holding.cost_basis);
+            let snapshot = snapshots
+                .entry(value.currency())
+                .or_insert_with(|| NetWorthSnapshot::zero(date, value.currency()));
+            snapshot.investments = snapshot.investments.checked_add(value)?;
+        }
+
+        for debt in self.debts.find_all()? {
+            if debt.start_date > date {
+                continue;
+            }
+            let payments: Vec<_> = self
+                .debts
+                .find_payments(debt.id)?
+                .into_iter()
+                .filter(|payment| payment.date.date() <= date)
+                .collect();
+            let schedule = self.debts.find_installments(debt.id)?;
+            let status = debt_service::status(debt, schedule, &payments, date)?;
+            let remaining = status.remaining_principal;
+
+            let snapshot = snapshots
+                .entry(remaining.currency())
+                .or_insert_with(|| NetWorthSnapshot::zero(date, remaining.currency()));
+            match status.debt.direction {
+                DebtDirection::Payable => {
+                    snapshot.debts = snapshot.debts.checked_add(remaining)?;
+                }
+                DebtDirection::Receivable => {
+                    snapshot.receivables = snapshot.receivables.checked_add(remaining)?;
+                }
+            }
+        }
+
+        let mut snapshots: Vec<NetWorthSnapshot> = snapshots.into_values().collect();
+        snapshots.sort_by(|a, b| a.currency.code().cmp(b.currency.code()));
+        Ok(snapshots)
+    }
+
+    /// Menjumlahkan cuplikan beberapa mata uang ke mata uang `target` dengan kurs `date`.
+    fn combine(
+        &self,
+        date: NaiveDate,
+        snapshots: &[NetWorthSnapshot],
+        target: Currency,
+    ) -> Result<NetWorth, AppError> {
+        let total = |component: fn(&NetWorthSnapshot) -> Money| {
+            let amounts: Vec<Money> = snapshots.iter().map(component).collect();
+            self.currencies.convert_total(&amounts, target, date)
+        };
+        let cash = total(|s| s.cash)?;
+        let investments = total(|s| s.investments)?;
+        let receivables = total(|s| s.receivables)?;
+        let debts = total(|s| s.debts)?;
+        let overdrafts = total(|s| s.overdrafts)?;
+
+        let total_assets = cash.checked_add(investments)?.checked_add(receivables)?;
+        let total_liabilities = debts.checked_add(overdrafts)?;
+        Ok(NetWorth {
+            date,
+            currency: target,
+            cash,
+            investments,
+            receivables,
+            total_assets,
+            debts,
+            overdrafts,
+            total_liabilities,
+            net_worth: total_assets.checked_sub(total_liabilities)?,
+        })
+    }
+}
+
+/// Label periode dengan format yang sama seperti `ReportRepository::totals_by_period`.
+fn period_label(period: ReportPeriod, date: NaiveDate) -> String {
+    let format = match period {
+        ReportPeriod::Day => "%Y-%m-%d",
+        ReportPeriod::Week => "%G-W%V",
+        ReportPeriod::Month => "%Y-%m",
+        ReportPeriod::Year => "%Y",
+    };
+    date.format(format).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::category::CategoryType;
+    use crate::models::debt::{DebtInput, DebtPaymentInput};
+    use crate::models::investment::{
+        InstrumentInput, InstrumentKind, InvestmentTransactionInput, InvestmentTransactionKind,
+    };
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::repositories::debt_repository::SqliteDebtRepository;
+    use crate::repositories::exchange_rate_repository::SqliteExchangeRateRepository;
+    use crate::repositories::investment_repository::SqliteInvestmentRepository;
+    use crate::repositories::net_worth_repository::SqliteNetWorthRepository;
+    use crate::services::debt_service::DebtService;
+    use crate::services::investment_service::InvestmentService;
+    use crate::services::test_support::{
+        add_account, add_category, add_investment_account, at, date, idr, usd,
+    };
+
+    fn service(
+        db: &Database,
+    ) -> NetWorthService<
+        SqliteNetWorthRepository<'_>,
+        SqliteAccountRepository<'_>,
+        SqliteInvestmentRepository<'_>,
+        SqliteDebtRepository<'_>,
+        SqliteExchangeRateRepository<'_>,
+    > {
+        NetWorthService::new(
+            SqliteNetWorthRepository::new(db),
+            SqliteAccountRepository::new(db),
+            SqliteInvestmentRepository::new(db),
+            SqliteDebtRepository::new(db),
+            CurrencyService::new(
+                SqliteExchangeRateRepository::new(db),
+                SqliteAccountRepository::new(db),
+            ),
+        )
+    }
+
+    /// Bank 5 juta, kartu kredit -1 juta, akun investasi dengan kas 100 ribu dan 10 unit
+    /// reksa dana senilai 1 juta, serta utang 2 juta yang sudah dibayar 500 ribu.
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(5_000_000.0));
+        add_account(&db, "Kartu Kredit", idr(-1_000_000.0));
+        add_investment_account(&db, "Reksa Dana", idr(1_100_000.0));
+
+        let investments = InvestmentService::new(
+            SqliteInvestmentRepository::new(&db),
+            SqliteAccountRepository::new(&db),
+        );
+        investments
+            .create_instrument(&InstrumentInput {
+                symbol: "RDPU".to_string(),
+                name: "Reksa Dana Pasar Uang".to_string(),
+                kind: InstrumentKind::MutualFund,
+                currency: Currency::IDR,
+            })
+            .unwrap();
+        investments
+            .add_transaction(&InvestmentTransactionInput {
+                account_id: 3,
+                instrument_id: 1,
+                kind: InvestmentTransactionKind::Buy,
+                units: 10.0,
+                amount: 1_000_000.0,
+                fee: None,
+                date: at(2024, 1, 2),
+                note: None,
+            })
+            .unwrap();
+
+        add_category(&db, "Cicilan", CategoryType::Expense);
+        let debts = DebtService::new(
+            SqliteDebtRepository::new(&db),
+            SqliteAccountRepository::new(&db),
+            SqliteCategoryRepository::new(&db),
+        );
+        debts
+            .create_debt(&DebtInput {
+                counterparty: "Budi".to_string(),
+                direction: DebtDirection::Payable,
+                principal: 2_000_000.0,
+                currency: Currency::IDR,
+                start_date: date(2024, 1, 1),
+                due_date: None,
+                interest_rate: None,
+                installment_count: None,
+                interest_method: Default::default(),
+                category_id: None,
+                description: None,
+                account_id: None,
+            })
+            .unwrap();
+        debts
+            .add_payment(
+                1,
+                &DebtPaymentInput {
+                    amount: 500_000.0,
+                    date: at(2024, 1, 5),
+                    account_id: 1,
+                    category_id: 1,
+                    description: None,
+                },
+            )
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn net_worth_breaks_down_assets_and_liabilities() {
+        let db = fixture();
+        let net_worth = service(&db)
+            .get_net_worth(date(2024, 1, 10), "IDR")
+            .unwrap();
+
+        // Pembayaran utang mengurangi saldo bank menjadi 4,5 juta.
+        assert_eq!(net_worth.cash, idr(4_500_000.0));
+        assert_eq!(net_worth.investments, idr(1_100_000.0));
+        assert_eq!(net_worth.receivables, idr(0.0));
+        assert_eq!(net_worth.total_assets, idr(5_600_000.0));
+        assert_eq!(net_worth.debts, idr(1_500_000.0));
+        assert_eq!(net_worth.overdrafts, idr(1_000_000.0));
+        assert_eq!(net_worth.total_liabilities, idr(2_500_000.0));
+        assert_eq!(net_worth.net_worth, idr(3_100_000.0));
+    }
+
+    #[test]
+    fn lending_from_an_account_keeps_net_worth() {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(5_000_000.0));
+        add_category(&db, "Pelunasan", CategoryType::Income);
+        let debts = DebtService::new(
+            SqliteDebtRepository::new(&db),
+            SqliteAccountRepository::new(&db),
+            SqliteCategoryRepository::new(&db),
+        );
+        debts
+            .create_debt(&DebtInput {
+                counterparty: "Adik".to_string(),
+                direction: DebtDirection::Receivable,
+                principal: 2_000_000.0,
+                currency: Currency::IDR,
+                start_date: date(2024, 1, 1),
+                due_date: None,
+                interest_rate: None,
+                installment_count: None,
+                interest_method: Default::default(),
+                category_id: None,
+                description: None,
+                account_id: Some(1),
+            })
+            .unwrap();
+
+        // Uang berpindah dari bank ke piutang; kekayaan bersih tidak berubah.
+        let net_worth = service(&db).get_net_worth(date(2024, 1, 2), "IDR").unwrap();
+        assert_eq!(net_worth.cash, idr(3_000_000.0));
+        assert_eq!(net_worth.receivables, idr(2_000_000.0));
+        assert_eq!(net_worth.net_worth, idr(5_000_000.0));
+
+        debts
+            .add_payment(
+                1,
+                &DebtPaymentInput {
+                    amount: 2_000_000.0,
+                    date: at(2024, 2, 1),
+                    account_id: 1,
+                    category_id: 1,
+                    description: None,
+                },
+            )
+            .unwrap();
+        let net_worth = service(&db).get_net_worth(date(2024, 2, 2), "IDR").unwrap();
+        assert_eq!(net_worth.cash, idr(5_000_000.0));
+        assert_eq!(net_worth.receivables, idr(0.0));
+        assert_eq!(net_worth.net_worth, idr(5_000_000.0));
+    }
+
+    #[test]
+    fn history_keeps_last_snapshot_per_period() {
+        let db = fixture();
+        let net_worth = service(&db);
+        net_worth.record_snapshot(date(2024, 1, 10)).unwrap();
+        net_worth.record_snapshot(date(2024, 1, 31)).unwrap();
+        // Mencatat ulang hari yang sama menggantikan cuplikannya.
+        net_worth.record_snapshot(date(2024, 1, 31)).unwrap();
+
+        let investments = InvestmentService::new(
+            SqliteInvestmentRepository::new(&db),
+            SqliteAccountRepository::new(&db),
+        );
+        investments
+            .add_price(1, date(2024, 2, 1), 120_000.0)
+            .unwrap();
+        net_worth.record_snapshot(date(2024, 2, 1)).unwrap();
+
+        let history = net_worth
+            .get_net_worth_history(ReportPeriod::Month, None, None, "IDR")
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].period, "2024-01");
+        assert_eq!(history[0].net_worth.date, date(2024, 1, 31));
+        assert_eq!(history[0].net_worth.net_worth, idr(3_100_000.0));
+        assert_eq!(history[1].period, "2024-02");
+        assert_eq!(history[1].net_worth.investments, idr(1_300_000.0));
+        assert_eq!(history[1].net_worth.net_worth, idr(3_300_000.0));
+
+        let daily = net_worth
+            .get_net_worth_history(
+                ReportPeriod::Day,
+                Some(date(2024, 1, 11)),
+                Some(date(2024, 1, 31)),
+                "IDR",
+            )
+            .unwrap();
+        assert_eq!(daily.len(), 1);
+
+        let err = net_worth
+            .get_net_worth_history(
+                ReportPeriod::Day,
+                Some(date(2024, 2, 1)),
+                Some(date(2024, 1, 1)),
+                "IDR",
+            )
+            .unwrap_err();
+        assert_eq!(err.field(), Some("date_to"));
+    }
+
+    #[test]
+    fn history_converts_each_snapshot_with_its_own_rate() {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Dolar", Money::new(10_000, usd()));
+        let net_worth = service(&db);
+        net_worth
+            .currencies
+            .add_exchange_rate(date(2024, 1, 1), "USD", "IDR", 15_000.0)
+            .unwrap();
+        net_worth
+            .currencies
+            .add_exchange_rate(date(2024, 2, 1), "USD", "IDR", 16_000.0)
+            .unwrap();
+        net_worth.record_snapshot(date(2024, 1, 15)).unwrap();
+        net_worth.record_snapshot(date(2024, 2, 15)).unwrap();
+
+        let history = net_worth
+            .get_net_worth_history(ReportPeriod::Month, None, None, "IDR")
+            .unwrap();
+        assert_eq!(history[0].net_worth.cash, idr(1_500_000.0));
+        assert_eq!(history[1].net_worth.cash, idr(1_600_000.0));
+    }
+}
@@ -0,0 +1,695 @@
+//! Modul ini berisi logika bisnis untuk transaksi berulang (gaji, sewa, langganan, cicilan).
+//!
+//! Setiap kejadian yang sudah jatuh tempo dibuat menjadi pemasukan atau pengeluaran biasa
+//! saat aplikasi dimulai dan secara berkala selama aplikasi berjalan. Pembuatan bersifat
+//! idempoten: setiap kejadian dicatat per tanggal jadwalnya dan tidak pernah dibuat dua kali.
+
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::money::Money;
+use crate::models::recurring::{
+    NewRecurringRule, OccurrenceEditInput, OccurrenceRecord, OccurrenceStatus, RecurrenceFrequency,
+    RecurringOccurrence, RecurringRule, RecurringRuleInput, Schedule,
+};
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::category_repository::CategoryRepository;
+use crate::repositories::recurring_repository::RecurringRepository;
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+/// Jeda antar pemeriksaan transaksi berulang yang jatuh tempo selama aplikasi berjalan.
+pub const MATERIALIZE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Jumlah kejadian pada pratinjau bila `count` tidak diisi.
+pub const DEFAULT_PREVIEW_COUNT: u32 = 10;
+/// Batas atas `count` pada pratinjau.
+pub const MAX_PREVIEW_COUNT: u32 = 100;
+
+/// Service transaksi berulang. Validasi membutuhkan data kategori dan akun.
+pub struct RecurringService<R, C, A> {
+    rules: R,
+    categories: C,
+    accounts: A,
+}
+
+impl<R: RecurringRepository, C: CategoryRepository, A: AccountRepository>
+    RecurringService<R, C, A>
+{
+    pub fn new(rules: R, categories: C, accounts: A) -> Self {
+        RecurringService {
+            rules,
+            categories,
+            accounts,
+        }
+    }
+
+    /// Memvalidasi input dan mengubah jumlahnya ke mata uang akun.
+    fn build_rule<'i>(
+        &self,
+        input: &'i RecurringRuleInput,
+    ) -> Result<NewRecurringRule<'i>, AppError> {
+        let description = input.description.trim();
+        if description.is_empty() {
+            return Err(AppError::validation(
+                "description",
+                "Deskripsi transaksi berulang tidak boleh kosong.",
+            ));
+        }
+
+        let transaction_type = CategoryType::try_from(input.transaction_type)
+            .map_err(|e| AppError::validation("transaction_type", e.to_string()))?;
+        let category = self.categories.find_by_id(input.category_id)?;
+        if category.category_type != transaction_type {
+            return Err(AppError::validation(
+                "category_id",
+                format!(
+                    "Tipe kategori '{}' tidak sesuai dengan jenis transaksi.",
+                    category.name
+                ),
+            ));
+        }
+
+        let account = self.accounts.find_by_id(input.account_id)?;
+        let amount = Money::from_major(input.amount, account.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah transaksi berulang harus lebih besar dari nol.",
+            ));
+        }
+
+        Ok(NewRecurringRule {
+            transaction_type,
+            description,
+            amount,
+            category_id: category.id,
+            account_id: account.id,
+            schedule: build_schedule(input)?,
+        })
+    }
+
+    /// Membuat aturan transaksi berulang. Kejadian sejak `start_date` yang sudah jatuh
+    /// tempo ikut dibuat pada pemeriksaan berikutnya.
+    pub fn create_rule(&self, input: &RecurringRuleInput) -> Result<(), AppError> {
+        let rule = self.build_rule(input)?;
+        self.rules.create(&rule)
+    }
+
+    /// Mengambil semua aturan transaksi berulang.
+    pub fn get_all_rules(&self) -> Result<Vec<RecurringRule>, AppError> {
+        self.rules.find_all()
+    }
+
+    /// Mengambil satu aturan berdasarkan ID-nya.
+    pub fn get_rule_by_id(&self, id: i64) -> Result<RecurringRule, AppError> {
+        self.rules.find_by_id(id)
+    }
+
+    /// Memperbarui aturan. Hanya kejadian yang belum dibuat yang terpengaruh.
+    ///
+    /// Jumlah pada catatan kejadian disimpan dalam mata uang aturan, sehingga akun tidak bisa
+    /// diganti ke akun bermata uang lain setelah ada kejadian yang dibuat, dilewati, atau diubah.
+    ///
+    /// Bila jadwal aturan yang sudah pernah membuat transaksi diubah, tanggal jadwal lama
+    /// dan baru tidak lagi bisa dicocokkan. Agar kejadian lampau tidak dibuat ulang dengan
+    /// tanggal baru, pembuatan otomatis dimulai dari hari setelah kejadian terakhir yang
+    /// dibuat atau dari `today`, mana yang lebih akhir.
+    pub fn update_rule(
+        &self,
+        id: i64,
+        input: &RecurringRuleInput,
+        today: NaiveDate,
+    ) -> Result<(), AppError> {
+        let rule = self.build_rule(input)?;
+        let current = self.rules.find_by_id(id)?;
+        if rule.amount.currency() != current.currency
+            && !self.rules.find_occurrences(id)?.is_empty()
+        {
+            return Err(AppError::Conflict(format!(
+                "Mata uang transaksi berulang '{}' tidak bisa diubah karena sudah ada kejadian \
+                 yang tercatat.",
+                current.description
+            )));
+        }
+
+        let mut materialize_from = current.materialize_from;
+        if rule.schedule != current.schedule {
+            let last_created = self
+                .rules
+                .find_occurrences(id)?
+                .into_iter()
+                .filter(|record| record.status == OccurrenceStatus::Created)
+                .map(|record| record.scheduled_date)
+                .max();
+            if let Some(last_created) = last_created {
+                let next = last_created.succ_opt().unwrap_or(last_created);
+                materialize_from = materialize_from.max(Some(next.max(today)));
+            }
+        }
+
+        self.rules.update(id, &rule, materialize_from)
+    }
+
+    /// Menghapus aturan. Transaksi yang sudah dibuat tetap ada.
+    pub fn delete_rule(&self, id: i64) -> Result<(), AppError> {
+        self.rules.delete(id)
+    }
+
+    /// Pratinjau `count` kejadian berikutnya mulai `from` (inklusif), beserta statusnya.
+    pub fn preview_occurrences(
+        &self,
+        rule_id: i64,
+        from: NaiveDate,
+        count: Option<u32>,
+    ) -> Result<Vec<RecurringOccurrence>, AppError> {
+        if matches!(count, Some(count) if count == 0 || count > MAX_PREVIEW_COUNT) {
+            return Err(AppError::validation(
+                "count",
+                format!("Jumlah kejadian harus antara 1 dan {}.", MAX_PREVIEW_COUNT),
+            ));
+        }
+        let count = count.unwrap_or(DEFAULT_PREVIEW_COUNT);
+
+        let rule = self.rules.find_by_id(rule_id)?;
+        let records = self.records_by_date(rule_id)?;
+        Ok(rule
+            .schedule
+            .occurrences()
+            .skip_while(|date| *date < from)
+            .take(count as usize)
+            .map(|date| occurrence(&rule, date, records.get(&date)))
+            .collect())
+    }
+
+    /// Melewati satu kejadian sehingga transaksinya tidak dibuat.
+    pub fn skip_occurrence(&self, rule_id: i64, scheduled_date: NaiveDate) -> Result<(), AppError> {
+        let rule = self.rules.find_by_id(rule_id)?;
+        ensure_scheduled(&rule, scheduled_date)?;
+
+        self.rules.save_occurrence(&OccurrenceRecord {
+            rule_id,
+            scheduled_date,
+            status: OccurrenceStatus::Skipped,
+            description: None,
+            amount: None,
+            date: None,
+            entry_id: None,
+        })
+    }
+
+    /// Mengubah deskripsi, jumlah, atau tanggal satu kejadian yang belum dibuat tanpa
+    /// mengubah aturannya. Kejadian yang dilewati menjadi aktif kembali; input kosong
+    /// mengembalikan kejadian ke nilai aturan.
+    pub fn edit_occurrence(
+        &self,
+        rule_id: i64,
+        scheduled_date: NaiveDate,
+        input: &OccurrenceEditInput,
+    ) -> Result<(), AppError> {
+        let rule = self.rules.find_by_id(rule_id)?;
+        ensure_scheduled(&rule, scheduled_date)?;
+
+        let description = match input.description.as_deref().map(str::trim) {
+            Some("") => {
+                return Err(AppError::validation(
+                    "description",
+                    "Deskripsi transaksi tidak boleh kosong.",
+                ))
+            }
+            description => description.map(str::to_string),
+        };
+        let amount = input
+            .amount
+            .map(|amount| Money::from_major(amount, rule.currency))
+            .transpose()?;
+        if amount.is_some_and(|amount| !amount.is_positive()) {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah transaksi harus lebih besar dari nol.",
+            ));
+        }
+
+        self.rules.save_occurrence(&OccurrenceRecord {
+            rule_id,
+            scheduled_date,
+            status: OccurrenceStatus::Edited,
+            description,
+            amount,
+            date: input.date,
+            entry_id: None,
+        })
+    }
+
+    /// Membuat semua transaksi berulang yang jatuh tempo sampai `today` (inklusif) dan
+    /// belum dibuat. Aman dipanggil berulang kali. Satu aturan yang gagal tidak
+    /// menghentikan aturan lain; error pertamanya dikembalikan setelah semua diproses.
+    /// Mengembalikan jumlah transaksi yang dibuat.
+    pub fn materialize_due(&self, today: NaiveDate) -> Result<usize, AppError> {
+        let mut created = 0;
+        let mut first_error = None;
+        for rule in self.rules.find_all()? {
+            match self.materialize_rule(&rule, today) {
+                Ok(count) => created += count,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(created),
+        }
+    }
+
+    fn materialize_rule(&self, rule: &RecurringRule, today: NaiveDate) -> Result<usize, AppError> {
+        let records = self.records_by_date(rule.id)?;
+
+        // Kejadian sebelum `materialize_from` tidak pernah dibuat otomatis.
+        let after_cutoff =
+            |date: &NaiveDate| rule.materialize_from.is_none_or(|from| *date >= from);
+
+        // Kejadian yang dimajukan ke hari ini atau sebelumnya ikut jatuh tempo walau
+        // tanggal jadwalnya belum tiba. Catatan milik jadwal lama diabaikan.
+        let mut due: BTreeSet<NaiveDate> = rule
+            .schedule
+            .occurrences()
+            .take_while(|date| *date <= today)
+            .filter(after_cutoff)
+            .collect();
+        due.extend(
+            records
+                .values()
+                .filter(|record| record.date.is_some_and(|date| date <= today))
+                .map(|record| record.scheduled_date)
+                .filter(|date| after_cutoff(date) && rule.schedule.contains(*date)),
+        );
+
+        let mut created = 0;
+        for scheduled_date in due {
+            let occurrence = occurrence(rule, scheduled_date, records.get(&scheduled_date));
+            if occurrence.status == OccurrenceStatus::Created
+                || occurrence.status == OccurrenceStatus::Skipped
+                || occurrence.date > today
+            {
+                continue;
+            }
+            let date = occurrence.date.and_time(NaiveTime::MIN);
+            if self.rules.materialize(
+                rule,
+                scheduled_date,
+                &occurrence.description,
+                occurrence.amount,
+                date,
+            )? {
+                created += 1;
+            }
+        }
+        Ok(created)
+    }
+
+    fn records_by_date(
+        &self,
+        rule_id: i64,
+    ) -> Result<HashMap<NaiveDate, OccurrenceRecord>, AppError> {
+        Ok(self
+            .rules
+            .find_occurrences(rule_id)?
+            .into_iter()
+            .map(|record| (record.scheduled_date, record))
+            .collect())
+    }
+}
+
+/// Memvalidasi bagian jadwal dari input.
+fn build_schedule(input: &RecurringRuleInput) -> Result<Schedule, AppError> {
+    let interval = input.interval.unwrap_or(1);
+    if interval == 0 {
+        return Err(AppError::validation(
+            "interval",
+            "Interval pengulangan minimal 1.",
+        ));
+    }
+
+    match (input.weekday, input.week_of_month) {
+        (None, None) => {}
+        (Some(weekday), Some(week)) => {
+            if input.frequency != RecurrenceFrequency::Monthly {
+                return Err(AppError::validation(
+                    "weekday",
+                    "Hari ke-n dalam bulan hanya berlaku untuk jadwal bulanan.",
+                ));
+            }
+            if weekday > 6 {
+                return Err(AppError::validation(
+                    "weekday",
+                    "Hari harus antara 0 (Senin) dan 6 (Minggu).",
+                ));
+            }
+            if !matches!(week, -1 | 1..=4) {
+                return Err(AppError::validation(
+                    "week_of_month",
+                    "Minggu harus antara 1 dan 4, atau -1 untuk minggu terakhir.",
+                ));
+            }
+        }
+        (Some(_), None) => {
+            return Err(AppError::validation(
+                "week_of_month",
+                "Minggu ke-berapa wajib diisi bersama hari.",
+            ))
+        }
+        (None, Some(_)) => {
+            return Err(AppError::validation(
+                "weekday",
+                "Hari wajib diisi bersama minggu ke-berapa.",
+            ))
+        }
+    }
+
+    if input.end_date.is_some_and(|end| end < input.start_date) {
+        return Err(AppError::validation(
+            "end_date",
+            "Tanggal akhir tidak boleh sebelum tanggal mulai.",
+        ));
+    }
+    if input.max_occurrences == Some(0) {
+        return Err(AppError::validation(
+            "max_occurrences",
+            "Jumlah kejadian minimal 1.",
+        ));
+    }
+
+    Ok(Schedule {
+        frequency: input.frequency,
+        interval,
+        start_date: input.start_date,
+        end_date: input.end_date,
+        max_occurrences: input.max_occurrences,
+        weekday: input.weekday,
+        week_of_month: input.week_of_month,
+    })
+}
+
+/// Memastikan `date` adalah salah satu tanggal jadwal aturan.
+fn ensure_scheduled(rule: &RecurringRule, date: NaiveDate) -> Result<(), AppError> {
+    if rule.schedule.contains(date) {
+        Ok(())
+    } else {
+        Err(AppError::validation(
+            "scheduled_date",
+            format!("Tanggal {} tidak ada dalam jadwal transaksi ini.", date),
+        ))
+    }
+}
+
+/// Menggabungkan nilai aturan dengan catatan kejadian (jika ada).
+fn occurrence(
+    rule: &RecurringRule,
+    scheduled_date: NaiveDate,
+    record: Option<&OccurrenceRecord>,
+) -> RecurringOccurrence {
+    RecurringOccurrence {
+        rule_id: rule.id,
+        scheduled_date,
+        date: record.and_then(|r| r.date).unwrap_or(scheduled_date),
+        description: record
+            .and_then(|r| r.description.clone())
+            .unwrap_or_else(|| rule.description.clone()),
+        amount: record.and_then(|r| r.amount).unwrap_or(rule.amount),
+        status: record.map_or(OccurrenceStatus::Pending, |r| r.status),
+        entry_id: record.and_then(|r| r.entry_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::SqliteCategoryRepository;
+    use crate::repositories::expense_repository::{ExpenseRepository, SqliteExpenseRepository};
+    use crate::repositories::recurring_repository::SqliteRecurringRepository;
+    use crate::services::test_support::{add_account, add_category, balance, date, idr, usd};
+
+    type Service<'a> = RecurringService<
+        SqliteRecurringRepository<'a>,
+        SqliteCategoryRepository<'a>,
+        SqliteAccountRepository<'a>,
+    >;
+
+    fn service(db: &Database) -> Service<'_> {
+        RecurringService::new(
+            SqliteRecurringRepository::new(db),
+            SqliteCategoryRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Kategori 1 Gaji (Income), 2 Langganan (Expense), dan akun 1 (IDR 1.000.000).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_category(&db, "Langganan", CategoryType::Expense);
+        add_account(&db, "Bank", idr(1_000_000.0));
+        db
+    }
+
+    fn monthly_subscription(start: NaiveDate) -> RecurringRuleInput {
+        RecurringRuleInput {
+            transaction_type: 2,
+            description: "Internet".to_string(),
+            amount: 300_000.0,
+            category_id: 2,
+            account_id: 1,
+            frequency: RecurrenceFrequency::Monthly,
+            interval: None,
+            start_date: start,
+            end_date: None,
+            max_occurrences: None,
+            weekday: None,
+            week_of_month: None,
+        }
+    }
+
+    fn schedule(frequency: RecurrenceFrequency, start: NaiveDate) -> Schedule {
+        Schedule {
+            frequency,
+            interval: 1,
+            start_date: start,
+            end_date: None,
+            max_occurrences: None,
+            weekday: None,
+            week_of_month: None,
+        }
+    }
+
+    #[test]
+    fn monthly_schedule_clamps_to_end_of_month() {
+        let dates: Vec<NaiveDate> = schedule(RecurrenceFrequency::Monthly, date(2024, 1, 31))
+            .occurrences()
+            .take(3)
+            .collect();
+        assert_eq!(
+            dates,
+            vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31)]
+        );
+
+        let leap = schedule(RecurrenceFrequency::Yearly, date(2024, 2, 29));
+        assert_eq!(leap.occurrences().nth(1), Some(date(2025, 2, 28)));
+    }
+
+    #[test]
+    fn nth_weekday_schedule_respects_start_count_and_end() {
+        // Jumat terakhir setiap bulan, mulai 30 Mei 2024 (Jumat terakhir Mei adalah 31 Mei).
+        let mut last_friday = schedule(RecurrenceFrequency::Monthly, date(2024, 5, 30));
+        last_friday.weekday = Some(4);
+        last_friday.week_of_month = Some(-1);
+        last_friday.max_occurrences = Some(3);
+        let dates: Vec<NaiveDate> = last_friday.occurrences().collect();
+        assert_eq!(
+            dates,
+            vec![date(2024, 5, 31), date(2024, 6, 28), date(2024, 7, 26)]
+        );
+
+        // Senin pertama; 1 Juli 2024 adalah hari Senin. Mulai 2 Juli berarti Juli terlewat.
+        let mut first_monday = schedule(RecurrenceFrequency::Monthly, date(2024, 7, 2));
+        first_monday.weekday = Some(0);
+        first_monday.week_of_month = Some(1);
+        first_monday.end_date = Some(date(2024, 9, 1));
+        let dates: Vec<NaiveDate> = first_monday.occurrences().collect();
+        assert_eq!(dates, vec![date(2024, 8, 5)]);
+
+        let mut biweekly = schedule(RecurrenceFrequency::Weekly, date(2024, 5, 6));
+        biweekly.interval = 2;
+        assert_eq!(biweekly.occurrences().nth(2), Some(date(2024, 6, 3)));
+    }
+
+    #[test]
+    fn materializing_is_idempotent_and_updates_balance() {
+        let db = fixture();
+        let recurring = service(&db);
+        recurring
+            .create_rule(&monthly_subscription(date(2024, 3, 15)))
+            .unwrap();
+
+        assert_eq!(recurring.materialize_due(date(2024, 5, 20)).unwrap(), 3);
+        assert_eq!(recurring.materialize_due(date(2024, 5, 20)).unwrap(), 0);
+        assert_eq!(balance(&db, 1), 10_000_000);
+
+        let expenses = SqliteExpenseRepository::new(&db).find_all().unwrap();
+        assert_eq!(expenses.len(), 3);
+        assert_eq!(expenses[2].date, date(2024, 5, 15).and_time(NaiveTime::MIN));
+
+        // Transaksi yang dihapus pengguna tidak dibuat ulang.
+        SqliteExpenseRepository::new(&db).delete(1).unwrap();
+        assert_eq!(recurring.materialize_due(date(2024, 5, 20)).unwrap(), 0);
+    }
+
+    #[test]
+    fn skipped_and_edited_occurrences() {
+        let db = fixture();
+        let recurring = service(&db);
+        recurring
+            .create_rule(&monthly_subscription(date(2024, 3, 15)))
+            .unwrap();
+
+        recurring.skip_occurrence(1, date(2024, 4, 15)).unwrap();
+        let edit = OccurrenceEditInput {
+            description: Some("Internet + TV".to_string()),
+            amount: Some(450_000.0),
+            date: Some(date(2024, 5, 10)),
+        };
+        recurring
+            .edit_occurrence(1, date(2024, 6, 15), &edit)
+            .unwrap();
+        let err = recurring.skip_occurrence(1, date(2024, 4, 16)).unwrap_err();
+        assert_eq!(err.field(), Some("scheduled_date"));
+
+        let preview = recurring
+            .preview_occurrences(1, date(2024, 4, 1), Some(3))
+            .unwrap();
+        let statuses: Vec<OccurrenceStatus> = preview.iter().map(|o| o.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                OccurrenceStatus::Skipped,
+                OccurrenceStatus::Pending,
+                OccurrenceStatus::Edited
+            ]
+        );
+        assert_eq!(preview[2].date, date(2024, 5, 10));
+
+        // Maret, Mei, dan kejadian Juni yang dimajukan ke 10 Mei.
+        assert_eq!(recurring.materialize_due(date(2024, 5, 20)).unwrap(), 3);
+        assert_eq!(balance(&db, 1), 100_000_000 - 30_000_000 * 2 - 45_000_000);
+
+        let err = recurring
+            .edit_occurrence(1, date(2024, 6, 15), &OccurrenceEditInput::default())
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+    }
+
+    #[test]
+    fn schedule_change_does_not_recreate_past_occurrences() {
+        let db = fixture();
+        let recurring = service(&db);
+        recurring
+            .create_rule(&monthly_subscription(date(2024, 1, 1)))
+            .unwrap();
+        assert_eq!(recurring.materialize_due(date(2024, 3, 10)).unwrap(), 3);
+
+        // Tanggal tagihan dipindah ke tanggal 15 pada 10 Maret: 15 Januari dan 15 Februari
+        // sudah terbayar lewat kejadian lama, jadi hanya 15 Maret yang dibuat.
+        let input = monthly_subscription(date(2024, 1, 15));
+        recurring.update_rule(1, &input, date(2024, 3, 10)).unwrap();
+        assert_eq!(
+            recurring.get_rule_by_id(1).unwrap().materialize_from,
+            Some(date(2024, 3, 10))
+        );
+        assert_eq!(recurring.materialize_due(date(2024, 3, 20)).unwrap(), 1);
+        assert_eq!(recurring.materialize_due(date(2024, 3, 20)).unwrap(), 0);
+
+        let dates: Vec<NaiveDate> = SqliteExpenseRepository::new(&db)
+            .find_all()
+            .unwrap()
+            .iter()
+            .map(|expense| expense.date.date())
+            .collect();
+        assert_eq!(dates.len(), 4);
+        assert!(dates.contains(&date(2024, 3, 15)));
+        assert!(!dates.contains(&date(2024, 1, 15)));
+        assert!(!dates.contains(&date(2024, 2, 15)));
+        assert_eq!(balance(&db, 1), 100_000_000 - 30_000_000 * 4);
+
+        // Perubahan selain jadwal tidak menggeser batasnya.
+        let mut input = monthly_subscription(date(2024, 1, 15));
+        input.amount = 350_000.0;
+        recurring.update_rule(1, &input, date(2024, 5, 1)).unwrap();
+        assert_eq!(
+            recurring.get_rule_by_id(1).unwrap().materialize_from,
+            Some(date(2024, 3, 10))
+        );
+    }
+
+    #[test]
+    fn currency_is_fixed_once_occurrences_exist() {
+        let db = fixture();
+        add_account(&db, "Dolar", Money::zero(usd()));
+        let recurring = service(&db);
+        recurring
+            .create_rule(&monthly_subscription(date(2024, 1, 1)))
+            .unwrap();
+
+        // Belum ada kejadian: akun boleh diganti ke mata uang lain.
+        let mut input = monthly_subscription(date(2024, 1, 1));
+        input.account_id = 2;
+        input.amount = 20.0;
+        recurring.update_rule(1, &input, date(2024, 1, 1)).unwrap();
+        assert_eq!(recurring.get_rule_by_id(1).unwrap().currency, usd());
+
+        recurring.skip_occurrence(1, date(2024, 1, 1)).unwrap();
+        let mut input = monthly_subscription(date(2024, 1, 1));
+        input.amount = 300_000.0;
+        let err = recurring
+            .update_rule(1, &input, date(2024, 1, 1))
+            .unwrap_err();
+        assert_eq!(err.code(), "CONFLICT");
+        let rule = recurring.get_rule_by_id(1).unwrap();
+        assert_eq!(rule.amount, Money::new(2_000, usd()));
+
+        // Akun lain dengan mata uang yang sama tetap diperbolehkan.
+        add_account(&db, "Dolar 2", Money::zero(usd()));
+        input.account_id = 3;
+        input.amount = 25.0;
+        recurring.update_rule(1, &input, date(2024, 1, 1)).unwrap();
+        assert_eq!(recurring.get_rule_by_id(1).unwrap().account_id, 3);
+    }
+
+    #[test]
+    fn rule_type_must_match_category_and_schedule_must_be_valid() {
+        let db = fixture();
+        let recurring = service(&db);
+
+        let mut input = monthly_subscription(date(2024, 3, 15));
+        input.category_id = 1;
+        assert_eq!(
+            recurring.create_rule(&input).unwrap_err().field(),
+            Some("category_id")
+        );
+
+        let mut input = monthly_subscription(date(2024, 3, 15));
+        input.weekday = Some(4);
+        assert_eq!(
+            recurring.create_rule(&input).unwrap_err().field(),
+            Some("week_of_month")
+        );
+
+        let mut input = monthly_subscription(date(2024, 3, 15));
+        input.end_date = Some(date(2024, 3, 1));
+        assert_eq!(
+            recurring.create_rule(&input).unwrap_err().field(),
+            Some("end_date")
+        );
+    }
+}
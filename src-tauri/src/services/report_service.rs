@@ -0,0 +1,329 @@
+//! Modul ini berisi logika bisnis untuk laporan ringkasan dan tren keuangan dashboard.
+//! Semua agregasi dijalankan di SQL; service hanya memvalidasi filter.
+
+use crate::error::AppError;
+use crate::models::category::CategoryType;
+use crate::models::report::{
+    AccountTotal, CategoryTotal, IncomeExpenseTotal, PeriodTotal, ReportPeriod, ReportQuery,
+};
+use crate::repositories::report_repository::ReportRepository;
+
+/// Jumlah kategori teratas bila `limit` tidak diisi.
+const DEFAULT_TOP_CATEGORIES: u32 = 5;
+
+/// Service laporan, bergantung pada `ReportRepository`.
+pub struct ReportService<R> {
+    reports: R,
+}
+
+impl<R: ReportRepository> ReportService<R> {
+    pub fn new(reports: R) -> Self {
+        ReportService { reports }
+    }
+
+    /// Total pemasukan dan pengeluaran per hari, minggu, bulan, atau tahun.
+    pub fn get_totals_by_period(
+        &self,
+        period: ReportPeriod,
+        query: &ReportQuery,
+    ) -> Result<Vec<PeriodTotal>, AppError> {
+        query.validate()?;
+        self.reports.totals_by_period(period, query)
+    }
+
+    /// Total per kategori. `category_type` (1 = Income, 2 = Expense) bersifat opsional.
+    /// Dengan `rollup`, total sub-kategori ikut dijumlahkan ke setiap induknya.
+    pub fn get_totals_by_category(
+        &self,
+        category_type: Option<i16>,
+        rollup: bool,
+        query: &ReportQuery,
+    ) -> Result<Vec<CategoryTotal>, AppError> {
+        query.validate()?;
+        let category_type = parse_category_type(category_type)?;
+        self.reports
+            .totals_by_category(category_type, None, rollup, query)
+    }
+
+    /// Total pemasukan dan pengeluaran per akun.
+    pub fn get_totals_by_account(
+        &self,
+        query: &ReportQuery,
+    ) -> Result<Vec<AccountTotal>, AppError> {
+        query.validate()?;
+        self.reports.totals_by_account(query)
+    }
+
+    /// Pemasukan dibandingkan pengeluaran beserta selisih bersihnya.
+    pub fn get_income_expense_summary(
+        &self,
+        query: &ReportQuery,
+    ) -> Result<Vec<IncomeExpenseTotal>, AppError> {
+        query.validate()?;
+        self.reports.income_expense_summary(query)
+    }
+
+    /// `limit` kategori dengan total terbesar; default kategori pengeluaran dan 5 teratas.
+    pub fn get_top_categories(
+        &self,
+        category_type: Option<i16>,
+        limit: Option<u32>,
+        query: &ReportQuery,
+    ) -> Result<Vec<CategoryTotal>, AppError> {
+        query.validate()?;
+        let category_type = parse_category_type(category_type)?.unwrap_or(CategoryType::Expense);
+        let limit = limit.unwrap_or(DEFAULT_TOP_CATEGORIES);
+        if limit == 0 {
+            return Err(AppError::validation(
+                "limit",
+                "Jumlah kategori harus lebih besar dari nol.",
+            ));
+        }
+        self.reports
+            .totals_by_category(Some(category_type), Some(limit), false, query)
+    }
+}
+
+fn parse_category_type(value: Option<i16>) -> Result<Option<CategoryType>, AppError> {
+    value
+        .map(|value| {
+            CategoryType::try_from(value)
+                .map_err(|e| AppError::validation("category_type", e.to_string()))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::debt::{DebtDirection, DebtInput, DebtPaymentInput};
+    use crate::models::money::{Currency, Money};
+    use crate::models::transfer::NewTransfer;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::category_repository::{CategoryRepository, SqliteCategoryRepository};
+    use crate::repositories::debt_repository::SqliteDebtRepository;
+    use crate::repositories::expense_repository::{ExpenseRepository, SqliteExpenseRepository};
+    use crate::repositories::income_repository::{IncomeRepository, SqliteIncomeRepository};
+    use crate::repositories::report_repository::SqliteReportRepository;
+    use crate::repositories::transfer_repository::{SqliteTransferRepository, TransferRepository};
+    use crate::services::debt_service::DebtService;
+    use crate::services::test_support::{add_account, add_category, at, date, idr, usd};
+
+    /// Kategori: 1 Gaji (Income), 2 Makan, 3 Transportasi (Expense).
+    /// Akun: 1 Bank, 2 Dompet (IDR), 3 Dolar (USD).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_category(&db, "Makan", CategoryType::Expense);
+        add_category(&db, "Transportasi", CategoryType::Expense);
+        add_account(&db, "Bank", idr(0.0));
+        add_account(&db, "Dompet", idr(0.0));
+        add_account(&db, "Dolar", Money::zero(usd()));
+
+        let incomes = SqliteIncomeRepository::new(&db);
+        incomes
+            .create("Gaji Mei", idr(100.0), at(2024, 5, 1), 1, 1)
+            .unwrap();
+        incomes
+            .create("Gaji Juni", idr(100.0), at(2024, 6, 1), 1, 1)
+            .unwrap();
+        incomes
+            .create("Honor", Money::new(500, usd()), at(2024, 6, 2), 1, 3)
+            .unwrap();
+        let expenses = SqliteExpenseRepository::new(&db);
+        expenses
+            .create("Makan", idr(30.0), at(2024, 5, 2), 2, 1)
+            .unwrap();
+        expenses
+            .create("Ojek", idr(10.0), at(2024, 5, 31), 3, 2)
+            .unwrap();
+        expenses
+            .create("Makan", idr(40.0), at(2024, 6, 3), 2, 1)
+            .unwrap();
+        SqliteTransferRepository::new(&db)
+            .create(&NewTransfer {
+                description: None,
+                from_account_id: 1,
+                to_account_id: 2,
+                amount: idr(50.0),
+                fee: idr(0.0),
+                rate: 1.0,
+                received_amount: idr(50.0),
+                date: at(2024, 5, 10),
+            })
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn totals_by_month_exclude_transfers_and_split_currencies() {
+        let db = fixture();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+
+        let totals = reports
+            .get_totals_by_period(ReportPeriod::Month, &ReportQuery::default())
+            .unwrap();
+
+        let summary: Vec<_> = totals
+            .iter()
+            .map(|t| {
+                (
+                    t.period.as_str(),
+                    t.totals.currency.code().to_string(),
+                    t.totals.net.minor_units(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2024-05", "IDR".to_string(), 6_000),
+                ("2024-06", "IDR".to_string(), 6_000),
+                ("2024-06", "USD".to_string(), 500),
+            ]
+        );
+    }
+
+    #[test]
+    fn date_filter_includes_whole_last_day() {
+        let db = fixture();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+        let query = ReportQuery {
+            date_from: Some(date(2024, 5, 1)),
+            date_to: Some(date(2024, 5, 31)),
+            account_ids: vec![],
+        };
+
+        let summary = reports.get_income_expense_summary(&query).unwrap();
+
+        assert_eq!(
+            summary,
+            vec![IncomeExpenseTotal {
+                currency: Currency::IDR,
+                income: idr(100.0),
+                expense: idr(40.0),
+                net: idr(60.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn top_categories_and_account_totals() {
+        let db = fixture();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+        let query = ReportQuery::default();
+
+        let top = reports.get_top_categories(None, Some(1), &query).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].category_name, "Makan");
+        assert_eq!(top[0].total, idr(70.0));
+        assert_eq!(top[0].transaction_count, 2);
+
+        let by_category = reports
+            .get_totals_by_category(Some(1), false, &query)
+            .unwrap();
+        assert_eq!(by_category.len(), 2);
+
+        let by_account = reports.get_totals_by_account(&query).unwrap();
+        let names: Vec<_> = by_account
+            .iter()
+            .map(|t| (t.account_name.as_str(), t.totals.net.minor_units()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Bank", 13_000), ("Dolar", 500), ("Dompet", -1_000)]
+        );
+    }
+
+    #[test]
+    fn debt_payments_are_left_out_of_reports() {
+        let db = fixture();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+        let query = ReportQuery::default();
+        let before = reports.get_income_expense_summary(&query).unwrap();
+
+        let debts = DebtService::new(
+            SqliteDebtRepository::new(&db),
+            SqliteAccountRepository::new(&db),
+            SqliteCategoryRepository::new(&db),
+        );
+        debts
+            .create_debt(&DebtInput {
+                counterparty: "Adik".to_string(),
+                direction: DebtDirection::Receivable,
+                principal: 50.0,
+                currency: Currency::IDR,
+                start_date: date(2024, 5, 1),
+                due_date: None,
+                interest_rate: None,
+                installment_count: None,
+                interest_method: Default::default(),
+                category_id: None,
+                description: None,
+                account_id: Some(1),
+            })
+            .unwrap();
+        debts
+            .add_payment(
+                1,
+                &DebtPaymentInput {
+                    amount: 50.0,
+                    date: at(2024, 6, 1),
+                    account_id: 1,
+                    category_id: 1,
+                    description: None,
+                },
+            )
+            .unwrap();
+
+        let after = reports.get_income_expense_summary(&query).unwrap();
+        let totals = |summary: &[IncomeExpenseTotal]| -> Vec<(i64, i64)> {
+            summary
+                .iter()
+                .map(|t| (t.income.minor_units(), t.expense.minor_units()))
+                .collect()
+        };
+        assert_eq!(totals(&after), totals(&before));
+    }
+
+    #[test]
+    fn weeks_use_iso_numbering() {
+        let db = fixture();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+
+        let weeks = reports
+            .get_totals_by_period(ReportPeriod::Week, &ReportQuery::default())
+            .unwrap();
+
+        assert_eq!(weeks[0].period, "2024-W18");
+    }
+
+    #[test]
+    fn rollup_adds_child_totals_to_parents() {
+        let db = fixture();
+        // 4 Kuliner menjadi induk dari 2 Makan.
+        add_category(&db, "Kuliner", CategoryType::Expense);
+        SqliteCategoryRepository::new(&db)
+            .update(2, "Makan", CategoryType::Expense, Some(4), None, None)
+            .unwrap();
+        let reports = ReportService::new(SqliteReportRepository::new(&db));
+
+        let totals = reports
+            .get_totals_by_category(Some(2), true, &ReportQuery::default())
+            .unwrap();
+
+        let summary: Vec<_> = totals
+            .iter()
+            .map(|t| (t.category_name.as_str(), t.parent_id, t.total.minor_units()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Kuliner", None, 7_000),
+                ("Makan", Some(4), 7_000),
+                ("Transportasi", None, 1_000),
+            ]
+        );
+    }
+}
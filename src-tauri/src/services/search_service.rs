@@ -0,0 +1,210 @@
+//! Modul ini berisi logika bisnis untuk pencarian teks penuh atas deskripsi transaksi.
+//!
+//! Teks dari pengguna tidak pernah diteruskan mentah ke FTS5. Setiap kata diubah menjadi
+//! pencarian awalan (`parkir` cocok dengan "parkiran"), teks di antara tanda kutip
+//! menjadi pencarian frasa persis, dan semua bagian harus cocok (AND).
+//! Hasil diurutkan berdasarkan relevansi (bm25).
+
+use crate::error::AppError;
+use crate::models::search::SearchResult;
+use crate::repositories::search_repository::{SearchRepository, MATCH_END, MATCH_START};
+
+/// Jumlah hasil bila `limit` tidak diisi.
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+/// Batas atas jumlah hasil per pencarian.
+const MAX_SEARCH_LIMIT: u32 = 100;
+
+/// Service pencarian, bergantung pada `SearchRepository`.
+pub struct SearchService<S> {
+    search: S,
+}
+
+impl<S: SearchRepository> SearchService<S> {
+    pub fn new(search: S) -> Self {
+        SearchService { search }
+    }
+
+    /// Mencari pemasukan dan pengeluaran yang deskripsinya cocok dengan `query`.
+    pub fn search_transactions(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<SearchResult>, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if limit == 0 || limit > MAX_SEARCH_LIMIT {
+            return Err(AppError::validation(
+                "limit",
+                format!("Jumlah hasil harus antara 1 dan {}.", MAX_SEARCH_LIMIT),
+            ));
+        }
+        let match_query = build_match_query(query).ok_or_else(|| {
+            AppError::validation("query", "Kata kunci pencarian tidak boleh kosong.")
+        })?;
+
+        let mut results = self.search.search(&match_query, limit)?;
+        for result in &mut results {
+            result.snippet = render_snippet(&result.snippet);
+        }
+        Ok(results)
+    }
+}
+
+/// Mengubah teks pencarian pengguna menjadi ekspresi `MATCH` FTS5 yang aman.
+/// Mengembalikan `None` bila tidak ada kata yang bisa dicari.
+fn build_match_query(input: &str) -> Option<String> {
+    let mut terms = Vec::new();
+
+    for (index, part) in input.split('"').enumerate() {
+        // Bagian dengan indeks ganjil berada di antara tanda kutip.
+        if index % 2 == 1 {
+            if !part.trim().is_empty() {
+                terms.push(format!("\"{}\"", part.trim()));
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            let word = word.trim_end_matches('*');
+            if word.chars().any(char::is_alphanumeric) {
+                terms.push(format!("\"{}\"*", word));
+            }
+        }
+    }
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Meng-escape potongan teks untuk HTML lalu mengganti penanda kecocokan dengan `<mark>`.
+fn render_snippet(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len() + 16);
+    for c in raw.chars() {
+        match c {
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::models::category::CategoryType;
+    use crate::models::ledger::LedgerEntryType;
+    use crate::repositories::expense_repository::{ExpenseRepository, SqliteExpenseRepository};
+    use crate::repositories::income_repository::{IncomeRepository, SqliteIncomeRepository};
+    use crate::repositories::search_repository::SqliteSearchRepository;
+    use crate::services::test_support::{add_account, add_category, at, idr};
+
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_category(&db, "Gaji", CategoryType::Income);
+        add_category(&db, "Transportasi", CategoryType::Expense);
+        add_account(&db, "Dompet", idr(10_000.0));
+        let expenses = SqliteExpenseRepository::new(&db);
+        expenses
+            .create("Biaya parkir mall", idr(5_000.0), at(2024, 3, 14), 2, 1)
+            .unwrap();
+        expenses
+            .create("Parkiran kantor <B1>", idr(3_000.0), at(2024, 4, 2), 2, 1)
+            .unwrap();
+        expenses
+            .create("Bensin motor", idr(20_000.0), at(2024, 4, 3), 2, 1)
+            .unwrap();
+        SqliteIncomeRepository::new(&db)
+            .create(
+                "Penggantian biaya parkir",
+                idr(5_000.0),
+                at(2024, 3, 20),
+                1,
+                1,
+            )
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn build_match_query_quotes_terms_and_keeps_phrases() {
+        assert_eq!(
+            build_match_query("parkir \"biaya parkir\" mall*").as_deref(),
+            Some("\"parkir\"* \"biaya parkir\" \"mall\"*")
+        );
+        assert_eq!(build_match_query("  \"\" * - "), None);
+    }
+
+    #[test]
+    fn prefix_search_matches_incomes_and_expenses_with_highlights() {
+        let db = fixture();
+        let search = SearchService::new(SqliteSearchRepository::new(&db));
+
+        let results = search.search_transactions("park", None).unwrap();
+
+        assert_eq!(results.len(), 3);
+        let parkiran = results
+            .iter()
+            .find(|r| r.description.starts_with("Parkiran"))
+            .unwrap();
+        assert_eq!(parkiran.snippet, "<mark>Parkiran</mark> kantor &lt;B1&gt;");
+        assert!(results
+            .iter()
+            .any(|r| r.entry_type == LedgerEntryType::Income));
+        assert!(results.iter().all(|r| r.amount.is_positive()));
+    }
+
+    #[test]
+    fn phrase_search_requires_exact_order() {
+        let db = fixture();
+        let search = SearchService::new(SqliteSearchRepository::new(&db));
+
+        let results = search
+            .search_transactions("\"biaya parkir\"", None)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let results = search
+            .search_transactions("\"parkir biaya\"", None)
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn index_follows_updates_and_deletes() {
+        let db = fixture();
+        let search = SearchService::new(SqliteSearchRepository::new(&db));
+        let expenses = SqliteExpenseRepository::new(&db);
+
+        expenses
+            .update(3, "Bensin mobil", idr(20_000.0), at(2024, 4, 3), 2, 1)
+            .unwrap();
+        assert!(search
+            .search_transactions("motor", None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(search.search_transactions("mobil", None).unwrap().len(), 1);
+
+        expenses.delete(3).unwrap();
+        assert!(search
+            .search_transactions("bensin", None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        let db = fixture();
+        let search = SearchService::new(SqliteSearchRepository::new(&db));
+
+        let err = search.search_transactions("   ", None).unwrap_err();
+        assert_eq!(err.field(), Some("query"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Helper bersama untuk pengujian service: tanggal, nilai uang, serta kategori dan akun awal.
+//! Modul pengujian tiap service cukup menyusun data yang khusus untuk skenarionya.
+
+use crate::db::Database;
+use crate::models::account::AccountType;
+use crate::models::category::CategoryType;
+use crate::models::money::{Currency, Money};
+use crate::repositories::account_repository::{AccountRepository, SqliteAccountRepository};
+use crate::repositories::category_repository::{CategoryRepository, SqliteCategoryRepository};
+use chrono::{NaiveDate, NaiveDateTime};
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Waktu transaksi pada tanggal tersebut pukul 09.00.
+pub fn at(y: i32, m: u32, d: u32) -> NaiveDateTime {
+    date(y, m, d).and_hms_opt(9, 0, 0).unwrap()
+}
+
+/// Nominal rupiah dalam satuan utama, misal `idr(1_500.5)`.
+pub fn idr(major: f64) -> Money {
+    Money::from_major(major, Currency::IDR).unwrap()
+}
+
+pub fn usd() -> Currency {
+    Currency::parse("USD").unwrap()
+}
+
+/// Membuat kategori tingkat atas; ID-nya berurutan sesuai urutan pembuatan.
+pub fn add_category(db: &Database, name: &str, category_type: CategoryType) {
+    add_subcategory(db, name, category_type, None);
+}
+
+pub fn add_subcategory(
+    db: &Database,
+    name: &str,
+    category_type: CategoryType,
+    parent_id: Option<i64>,
+) {
+    SqliteCategoryRepository::new(db)
+        .create(name, category_type, parent_id, None, None)
+        .unwrap();
+}
+
+/// Membuat akun kas dengan saldo awal `balance`.
+pub fn add_account(db: &Database, name: &str, balance: Money) {
+    SqliteAccountRepository::new(db)
+        .create(name, None, balance, AccountType::Cash)
+        .unwrap();
+}
+
+/// Membuat akun investasi dengan kas awal `balance`.
+pub fn add_investment_account(db: &Database, name: &str, balance: Money) {
+    SqliteAccountRepository::new(db)
+        .create(name, None, balance, AccountType::Investment)
+        .unwrap();
+}
+
+/// Saldo akun dalam satuan terkecil.
+pub fn balance(db: &Database, account_id: i64) -> i64 {
+    SqliteAccountRepository::new(db)
+        .find_by_id(account_id)
+        .unwrap()
+        .balance
+        .minor_units()
+}
+
+/// Saldo semua akun dalam satuan terkecil, urut sesuai `find_all`.
+pub fn balances(db: &Database) -> Vec<i64> {
+    SqliteAccountRepository::new(db)
+        .find_all()
+        .unwrap()
+        .iter()
+        .map(|account| account.balance.minor_units())
+        .collect()
+}
@@ -0,0 +1,206 @@
+//! Modul ini berisi logika bisnis untuk transfer antar akun.
+//! Transfer mendebit akun asal dan mengkredit akun tujuan dalam satu transaksi database,
+//! dan tidak pernah dihitung sebagai pemasukan maupun pengeluaran.
+
+use crate::error::AppError;
+use crate::models::money::Money;
+use crate::models::transfer::{NewTransfer, Transfer, TransferInput};
+use crate::repositories::account_repository::AccountRepository;
+use crate::repositories::transfer_repository::TransferRepository;
+
+/// Service transfer, bergantung pada `TransferRepository` dan `AccountRepository`.
+pub struct TransferService<T, A> {
+    transfers: T,
+    accounts: A,
+}
+
+impl<T: TransferRepository, A: AccountRepository> TransferService<T, A> {
+    pub fn new(transfers: T, accounts: A) -> Self {
+        TransferService {
+            transfers,
+            accounts,
+        }
+    }
+
+    /// Memvalidasi input dan menghitung jumlah yang diterima akun tujuan.
+    fn build_transfer<'i>(&self, input: &'i TransferInput) -> Result<NewTransfer<'i>, AppError> {
+        if input.from_account_id == input.to_account_id {
+            return Err(AppError::validation(
+                "to_account_id",
+                "Akun asal dan tujuan tidak boleh sama.",
+            ));
+        }
+
+        let from = self.accounts.find_by_id(input.from_account_id)?;
+        let to = self.accounts.find_by_id(input.to_account_id)?;
+
+        let amount = Money::from_major(input.amount, from.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah transfer harus lebih besar dari nol.",
+            ));
+        }
+        let fee = Money::from_major_as(input.fee.unwrap_or(0.0), from.currency, "fee")?;
+        if fee.is_negative() {
+            return Err(AppError::validation(
+                "fee",
+                "Biaya transfer tidak boleh negatif.",
+            ));
+        }
+
+        let rate = if from.currency == to.currency {
+            match input.rate {
+                Some(rate) if rate != 1.0 => {
+                    return Err(AppError::validation(
+                        "rate",
+                        "Kurs hanya berlaku untuk transfer antar mata uang.",
+                    ))
+                }
+                _ => 1.0,
+            }
+        } else {
+            input.rate.ok_or_else(|| {
+                AppError::validation(
+                    "rate",
+                    format!(
+                        "Kurs {} ke {} wajib diisi untuk transfer antar mata uang.",
+                        from.currency, to.currency
+                    ),
+                )
+            })?
+        };
+        let received_amount = amount.convert(rate, to.currency)?;
+        if !received_amount.is_positive() {
+            return Err(AppError::validation(
+                "amount",
+                "Jumlah yang diterima harus lebih besar dari nol.",
+            ));
+        }
+
+        Ok(NewTransfer {
+            description: input.description.as_deref(),
+            from_account_id: from.id,
+            to_account_id: to.id,
+            amount,
+            fee,
+            rate,
+            received_amount,
+            date: input.date,
+        })
+    }
+
+    /// Membuat transfer baru.
+    pub fn create_transfer(&self, input: &TransferInput) -> Result<(), AppError> {
+        let transfer = self.build_transfer(input)?;
+        self.transfers.create(&transfer)
+    }
+
+    /// Mengambil semua transfer.
+    pub fn get_all_transfers(&self) -> Result<Vec<Transfer>, AppError> {
+        self.transfers.find_all()
+    }
+
+    /// Mengambil satu transfer berdasarkan ID-nya.
+    pub fn get_transfer_by_id(&self, id: i64) -> Result<Transfer, AppError> {
+        self.transfers.find_by_id(id)
+    }
+
+    /// Memperbarui transfer yang ada.
+    pub fn update_transfer(&self, id: i64, input: &TransferInput) -> Result<(), AppError> {
+        let transfer = self.build_transfer(input)?;
+        self.transfers.update(id, &transfer)
+    }
+
+    /// Menghapus transfer berdasarkan ID.
+    pub fn delete_transfer(&self, id: i64) -> Result<(), AppError> {
+        self.transfers.delete(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::repositories::account_repository::SqliteAccountRepository;
+    use crate::repositories::transfer_repository::SqliteTransferRepository;
+    use crate::services::test_support::{add_account, at, balances, idr, usd};
+
+    fn service(
+        db: &Database,
+    ) -> TransferService<SqliteTransferRepository<'_>, SqliteAccountRepository<'_>> {
+        TransferService::new(
+            SqliteTransferRepository::new(db),
+            SqliteAccountRepository::new(db),
+        )
+    }
+
+    /// Database dengan akun 1 (IDR 1.000.000), akun 2 (IDR 0), dan akun 3 (USD 0).
+    fn fixture() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        add_account(&db, "Bank", idr(1_000_000.0));
+        add_account(&db, "Dompet", idr(0.0));
+        add_account(&db, "Dolar", Money::zero(usd()));
+        db
+    }
+
+    fn input(to: i64, amount: f64, fee: Option<f64>, rate: Option<f64>) -> TransferInput {
+        TransferInput {
+            description: None,
+            from_account_id: 1,
+            to_account_id: to,
+            amount,
+            fee,
+            rate,
+            date: at(2024, 5, 1),
+        }
+    }
+
+    #[test]
+    fn transfer_with_fee_moves_balances_and_delete_restores_them() {
+        let db = fixture();
+        let transfers = service(&db);
+
+        transfers
+            .create_transfer(&input(2, 200_000.0, Some(6_500.0), None))
+            .unwrap();
+        assert_eq!(balances(&db), vec![79_350_000, 20_000_000, 0]);
+
+        transfers.delete_transfer(1).unwrap();
+        assert_eq!(balances(&db), vec![100_000_000, 0, 0]);
+    }
+
+    #[test]
+    fn cross_currency_transfer_requires_rate() {
+        let db = fixture();
+        let transfers = service(&db);
+
+        let err = transfers
+            .create_transfer(&input(3, 160_000.0, None, None))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("rate"));
+
+        transfers
+            .create_transfer(&input(3, 160_000.0, None, Some(0.0000625)))
+            .unwrap();
+        let transfer = transfers.get_transfer_by_id(1).unwrap();
+        assert_eq!(transfer.received_amount.minor_units(), 1_000);
+        assert_eq!(balances(&db), vec![84_000_000, 0, 1_000]);
+    }
+
+    #[test]
+    fn rejects_same_account_and_rate_for_same_currency() {
+        let db = fixture();
+        let transfers = service(&db);
+
+        let err = transfers
+            .create_transfer(&input(1, 10.0, None, None))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("to_account_id"));
+
+        let err = transfers
+            .create_transfer(&input(2, 10.0, None, Some(2.0)))
+            .unwrap_err();
+        assert_eq!(err.field(), Some("rate"));
+    }
+}